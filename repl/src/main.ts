@@ -64,10 +64,68 @@ export const resultsEditor = monaco.editor.create(document.getElementById("outpu
 
 loadInitialValue();
 
+type Evaluation = ReturnType<typeof nana.evaluate>;
+type Values = Evaluation['values'];
+
+// Values arrive as one flat list, with lists and tagged tuples pointing at
+// their items by index.
+function printValue(values: Values, index: number): string {
+  const value = values[index];
+  const printAll = (indexes: ArrayLike<number>) =>
+    Array.from(indexes, (i) => printValue(values, i));
+
+  switch (value.tag) {
+    case 'number':
+    case 'symbol':
+    case 'function-ref':
+      return value.val;
+    case 'string':
+      return `"${value.val}"`;
+    case 'keyword':
+      return `:${value.val}`;
+    case 'list':
+      return `[${printAll(value.val).join(' ')}]`;
+    case 'tagged-tuple':
+      return `${printValue(values, value.val.tag)}(${printAll(value.val.values).join(' ')})`;
+    case 'error': {
+      const { variety, message, location, details } = value.val;
+      const parts = [`:${variety}`];
+      if (message !== undefined) parts.push(`"${message}"`);
+      parts.push(...printAll(details));
+      if (location !== undefined) parts.push(`:at("${location}")`);
+      return `:error(${parts.join(' ')})`;
+    }
+    case 'process':
+      return `Process(${value.val})`;
+  }
+}
+
+function printEvaluation(evaluation: Evaluation): string {
+  if (evaluation.diagnostics.length > 0) {
+    return evaluation.diagnostics
+      .map((d) => `# ${d.location}: ${d.message}\n#   ${d.snippet}`)
+      .join('\n');
+  }
+
+  const sections = [];
+  if (evaluation.logs.length > 0) {
+    sections.push(evaluation.logs.map((line) => `# ${line}`).join('\n'));
+  }
+  sections.push(Array.from(evaluation.results, (i) => printValue(evaluation.values, i)).join('\n'));
+  if (evaluation.spawned.length > 0) {
+    sections.push('#-------- Results from processes in order of completion ----------------');
+    sections.push(Array.from(evaluation.spawned, (i) => printValue(evaluation.values, i)).join('\n'));
+  }
+  return sections.join('\n');
+}
+
+// Code that loops forever would otherwise freeze the page
+const limits = { maxSteps: 50_000_000n, timeLimitMs: 5_000n };
+
 function evaluateEditor() {
   try {
-    const result = nana.evaluate(myEditor.getValue());
-    resultsEditor.setValue(result);
+    const result = nana.evaluateWithLimits(myEditor.getValue(), limits);
+    resultsEditor.setValue(printEvaluation(result));
   } catch (error) {
     if (error instanceof Error) {
       resultsEditor.setValue(`${error.name}: ${error.message}`);
@@ -1,172 +1,3774 @@
-#[allow(dead_code)]
+// Generated by `wit-bindgen` 0.41.0. DO NOT EDIT!
+// Options used:
+//   * runtime_path: "wit_bindgen_rt"
+#[allow(dead_code, clippy::all)]
 pub mod component {
-    #[allow(dead_code)]
+  pub mod nana {
+
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
     pub mod nana {
-        #[allow(dead_code, clippy::all)]
-        pub mod nana {
-            #[used]
-            #[doc(hidden)]
-            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
-            use super::super::super::_rt;
-            #[allow(unused_unsafe, clippy::all)]
-            pub fn evaluate(name: &str) -> _rt::String {
-                unsafe {
-                    #[repr(align(4))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
-                    let vec0 = name;
-                    let ptr0 = vec0.as_ptr().cast::<u8>();
-                    let len0 = vec0.len();
-                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "component:nana/nana")]
-                    extern "C" {
-                        #[link_name = "evaluate"]
-                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::_rt;
+      /// WIT types can't contain themselves, so the values in an evaluation are
+      /// kept in one flat list. Lists and tagged tuples refer to their items by
+      /// their index in that list.
+      pub type ValueIndex = u32;
+      #[derive(Clone)]
+      pub struct TaggedTuple {
+        pub tag: ValueIndex,
+        pub values: _rt::Vec::<ValueIndex>,
+      }
+      impl ::core::fmt::Debug for TaggedTuple {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("TaggedTuple").field("tag", &self.tag).field("values", &self.values).finish()
+        }
+      }
+      /// An :error(:variety "message" ... :at("file:line:col")) tuple
+      #[derive(Clone)]
+      pub struct ErrorValue {
+        pub variety: _rt::String,
+        pub message: Option<_rt::String>,
+        pub location: Option<_rt::String>,
+        /// Anything else the error carried
+        pub details: _rt::Vec::<ValueIndex>,
+      }
+      impl ::core::fmt::Debug for ErrorValue {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("ErrorValue").field("variety", &self.variety).field("message", &self.message).field("location", &self.location).field("details", &self.details).finish()
+        }
+      }
+      #[derive(Clone)]
+      pub enum Value {
+        /// Sent as decimal digits because Nana's numbers can be larger than
+        /// any WIT integer type
+        Number(_rt::String),
+        String(_rt::String),
+        Keyword(_rt::String),
+        Symbol(_rt::String),
+        List(_rt::Vec::<ValueIndex>),
+        TaggedTuple(TaggedTuple),
+        /// Functions and macros can't leave the interpreter, so only their
+        /// printed form is sent
+        FunctionRef(_rt::String),
+        Error(ErrorValue),
+        /// A handle to a spawned process, identified by its id
+        Process(u64),
+      }
+      impl ::core::fmt::Debug for Value {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          match self {
+            Value::Number(e) => {
+              f.debug_tuple("Value::Number").field(e).finish()
+            }
+            Value::String(e) => {
+              f.debug_tuple("Value::String").field(e).finish()
+            }
+            Value::Keyword(e) => {
+              f.debug_tuple("Value::Keyword").field(e).finish()
+            }
+            Value::Symbol(e) => {
+              f.debug_tuple("Value::Symbol").field(e).finish()
+            }
+            Value::List(e) => {
+              f.debug_tuple("Value::List").field(e).finish()
+            }
+            Value::TaggedTuple(e) => {
+              f.debug_tuple("Value::TaggedTuple").field(e).finish()
+            }
+            Value::FunctionRef(e) => {
+              f.debug_tuple("Value::FunctionRef").field(e).finish()
+            }
+            Value::Error(e) => {
+              f.debug_tuple("Value::Error").field(e).finish()
+            }
+            Value::Process(e) => {
+              f.debug_tuple("Value::Process").field(e).finish()
+            }
+          }
+        }
+      }
+      /// Something that stopped the code from being parsed. Code with
+      /// diagnostics isn't run.
+      #[derive(Clone)]
+      pub struct Diagnostic {
+        pub message: _rt::String,
+        pub location: _rt::String,
+        pub line: u32,
+        pub column: u32,
+        pub snippet: _rt::String,
+      }
+      impl ::core::fmt::Debug for Diagnostic {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("Diagnostic").field("message", &self.message).field("location", &self.location).field("line", &self.line).field("column", &self.column).field("snippet", &self.snippet).finish()
+        }
+      }
+      #[derive(Clone)]
+      pub struct Evaluation {
+        pub values: _rt::Vec::<Value>,
+        /// The result of each top level form, in order
+        pub results: _rt::Vec::<ValueIndex>,
+        /// The results of spawned processes, in order of completion
+        pub spawned: _rt::Vec::<ValueIndex>,
+        pub logs: _rt::Vec::<_rt::String>,
+        pub diagnostics: _rt::Vec::<Diagnostic>,
+      }
+      impl ::core::fmt::Debug for Evaluation {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("Evaluation").field("values", &self.values).field("results", &self.results).field("spawned", &self.spawned).field("logs", &self.logs).field("diagnostics", &self.diagnostics).finish()
+        }
+      }
+      /// Stops code that would otherwise run forever. Every step a process takes
+      /// counts towards max-steps.
+      #[repr(C)]
+      #[derive(Clone, Copy)]
+      pub struct Limits {
+        pub max_steps: Option<u64>,
+        pub time_limit_ms: Option<u64>,
+      }
+      impl ::core::fmt::Debug for Limits {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("Limits").field("max-steps", &self.max_steps).field("time-limit-ms", &self.time_limit_ms).finish()
+        }
+      }
+      /// An interpreter that keeps its definitions between calls to evaluate, so
+      /// hosts like a REPL can build up a program one cell at a time.
+
+      #[derive(Debug)]
+      #[repr(transparent)]
+      pub struct Session{
+        handle: _rt::Resource<Session>,
+      }
+
+      impl Session{
+        #[doc(hidden)]
+        pub unsafe fn from_handle(handle: u32) -> Self {
+          Self {
+            handle: unsafe { _rt::Resource::from_handle(handle) },
+          }
+        }
+
+        #[doc(hidden)]
+        pub fn take_handle(&self) -> u32 {
+          _rt::Resource::take_handle(&self.handle)
+        }
+
+        #[doc(hidden)]
+        pub fn handle(&self) -> u32 {
+          _rt::Resource::handle(&self.handle)
+        }
+      }
+      
+
+      unsafe impl _rt::WasmResource for Session{
+        #[inline]
+        unsafe fn drop(_handle: u32) {
+          #[cfg(not(target_arch = "wasm32"))]
+          unreachable!();
+
+          #[cfg(target_arch = "wasm32")]
+          {
+            #[link(wasm_import_module = "component:nana/nana")]
+            unsafe extern "C" {
+              #[link_name = "[resource-drop]session"]
+              fn drop(_: u32);
+            }
+
+            unsafe { drop(_handle) };
+          }
+        }
+      }
+      
+      #[allow(unused_unsafe, clippy::all)]
+      pub fn evaluate(code: &str,) -> Evaluation{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 10*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 10*::core::mem::size_of::<*const u8>()]);
+          let vec0 = code;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "component:nana/nana")]
+          unsafe extern "C" {
+            #[link_name = "evaluate"]
+            fn wit_import2(_: *mut u8, _: usize, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8, ){ unreachable!() }
+          unsafe { wit_import2(ptr0.cast_mut(), len0, ptr1) };
+          let l3 = *ptr1.add(0).cast::<*mut u8>();
+          let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base44 = l3;
+          let len44 = l4;
+          let mut result44 = _rt::Vec::with_capacity(len44);
+          for i in 0..len44 {
+            let base = base44.add(i * (8+10*::core::mem::size_of::<*const u8>()));
+            let e44 = {
+              let l5 = i32::from(*base.add(0).cast::<u8>());
+              let v43 = match l5 {
+                0 => {
+                  let e43 = {
+                    let l6 = *base.add(8).cast::<*mut u8>();
+                    let l7 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len8 = l7;
+                    let bytes8 = _rt::Vec::from_raw_parts(l6.cast(), len8, len8);
+
+                    _rt::string_lift(bytes8)
+                  };
+                  Value::Number(e43)
+                }
+                1 => {
+                  let e43 = {
+                    let l9 = *base.add(8).cast::<*mut u8>();
+                    let l10 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len11 = l10;
+                    let bytes11 = _rt::Vec::from_raw_parts(l9.cast(), len11, len11);
+
+                    _rt::string_lift(bytes11)
+                  };
+                  Value::String(e43)
+                }
+                2 => {
+                  let e43 = {
+                    let l12 = *base.add(8).cast::<*mut u8>();
+                    let l13 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len14 = l13;
+                    let bytes14 = _rt::Vec::from_raw_parts(l12.cast(), len14, len14);
+
+                    _rt::string_lift(bytes14)
+                  };
+                  Value::Keyword(e43)
+                }
+                3 => {
+                  let e43 = {
+                    let l15 = *base.add(8).cast::<*mut u8>();
+                    let l16 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len17 = l16;
+                    let bytes17 = _rt::Vec::from_raw_parts(l15.cast(), len17, len17);
+
+                    _rt::string_lift(bytes17)
+                  };
+                  Value::Symbol(e43)
+                }
+                4 => {
+                  let e43 = {
+                    let l18 = *base.add(8).cast::<*mut u8>();
+                    let l19 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len20 = l19;
+
+                    _rt::Vec::from_raw_parts(l18.cast(), len20, len20)
+                  };
+                  Value::List(e43)
+                }
+                5 => {
+                  let e43 = {
+                    let l21 = *base.add(8).cast::<i32>();
+                    let l22 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                    let l23 = *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len24 = l23;
+
+                    TaggedTuple{
+                      tag: l21 as u32,
+                      values: _rt::Vec::from_raw_parts(l22.cast(), len24, len24),
+                    }
+                  };
+                  Value::TaggedTuple(e43)
+                }
+                6 => {
+                  let e43 = {
+                    let l25 = *base.add(8).cast::<*mut u8>();
+                    let l26 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len27 = l26;
+                    let bytes27 = _rt::Vec::from_raw_parts(l25.cast(), len27, len27);
+
+                    _rt::string_lift(bytes27)
+                  };
+                  Value::FunctionRef(e43)
+                }
+                7 => {
+                  let e43 = {
+                    let l28 = *base.add(8).cast::<*mut u8>();
+                    let l29 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len30 = l29;
+                    let bytes30 = _rt::Vec::from_raw_parts(l28.cast(), len30, len30);
+                    let l31 = i32::from(*base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                    let l35 = i32::from(*base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                    let l39 = *base.add(8+8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                    let l40 = *base.add(8+9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len41 = l40;
+
+                    ErrorValue{
+                      variety: _rt::string_lift(bytes30),
+                      message: match l31 {
+                        0 => None,
+                        1 => {
+                          let e = {
+                            let l32 = *base.add(8+3*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                            let l33 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                            let len34 = l33;
+                            let bytes34 = _rt::Vec::from_raw_parts(l32.cast(), len34, len34);
+
+                            _rt::string_lift(bytes34)
+                          };
+                          Some(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                      },
+                      location: match l35 {
+                        0 => None,
+                        1 => {
+                          let e = {
+                            let l36 = *base.add(8+6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                            let l37 = *base.add(8+7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                            let len38 = l37;
+                            let bytes38 = _rt::Vec::from_raw_parts(l36.cast(), len38, len38);
+
+                            _rt::string_lift(bytes38)
+                          };
+                          Some(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                      },
+                      details: _rt::Vec::from_raw_parts(l39.cast(), len41, len41),
+                    }
+                  };
+                  Value::Error(e43)
+                }
+                n => {
+                  debug_assert_eq!(n, 8, "invalid enum discriminant");
+                  let e43 = {
+                    let l42 = *base.add(8).cast::<i64>();
+
+                    l42 as u64
+                  };
+                  Value::Process(e43)
+                }
+              };
+
+              v43
+            };
+            result44.push(e44);
+          }
+          _rt::cabi_dealloc(base44, len44 * (8+10*::core::mem::size_of::<*const u8>()), 8);
+          let l45 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l46 = *ptr1.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let len47 = l46;
+          let l48 = *ptr1.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l49 = *ptr1.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let len50 = l49;
+          let l51 = *ptr1.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l52 = *ptr1.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base56 = l51;
+          let len56 = l52;
+          let mut result56 = _rt::Vec::with_capacity(len56);
+          for i in 0..len56 {
+            let base = base56.add(i * (2*::core::mem::size_of::<*const u8>()));
+            let e56 = {
+              let l53 = *base.add(0).cast::<*mut u8>();
+              let l54 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len55 = l54;
+              let bytes55 = _rt::Vec::from_raw_parts(l53.cast(), len55, len55);
+
+              _rt::string_lift(bytes55)
+            };
+            result56.push(e56);
+          }
+          _rt::cabi_dealloc(base56, len56 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+          let l57 = *ptr1.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l58 = *ptr1.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base70 = l57;
+          let len70 = l58;
+          let mut result70 = _rt::Vec::with_capacity(len70);
+          for i in 0..len70 {
+            let base = base70.add(i * (8+6*::core::mem::size_of::<*const u8>()));
+            let e70 = {
+              let l59 = *base.add(0).cast::<*mut u8>();
+              let l60 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len61 = l60;
+              let bytes61 = _rt::Vec::from_raw_parts(l59.cast(), len61, len61);
+              let l62 = *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l63 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len64 = l63;
+              let bytes64 = _rt::Vec::from_raw_parts(l62.cast(), len64, len64);
+              let l65 = *base.add(4*::core::mem::size_of::<*const u8>()).cast::<i32>();
+              let l66 = *base.add(4+4*::core::mem::size_of::<*const u8>()).cast::<i32>();
+              let l67 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l68 = *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len69 = l68;
+              let bytes69 = _rt::Vec::from_raw_parts(l67.cast(), len69, len69);
+
+              Diagnostic{
+                message: _rt::string_lift(bytes61),
+                location: _rt::string_lift(bytes64),
+                line: l65 as u32,
+                column: l66 as u32,
+                snippet: _rt::string_lift(bytes69),
+              }
+            };
+            result70.push(e70);
+          }
+          _rt::cabi_dealloc(base70, len70 * (8+6*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+          let result71 = Evaluation{
+            values: result44,
+            results: _rt::Vec::from_raw_parts(l45.cast(), len47, len47),
+            spawned: _rt::Vec::from_raw_parts(l48.cast(), len50, len50),
+            logs: result56,
+            diagnostics: result70,
+          };
+          result71
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Like evaluate, but once a limit is reached the form that was running
+      /// gets an :error(:out-of-fuel ...) as its result, and nothing after it runs.
+      pub fn evaluate_with_limits(code: &str,limits: Limits,) -> Evaluation{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 10*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 10*::core::mem::size_of::<*const u8>()]);
+          let vec0 = code;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let Limits{ max_steps:max_steps1, time_limit_ms:time_limit_ms1, } = limits;
+          let (result2_0,result2_1,) = match max_steps1 {
+            Some(e) => (1i32, _rt::as_i64(e)),
+            None => {
+              (0i32, 0i64)
+            },
+          };let (result3_0,result3_1,) = match time_limit_ms1 {
+            Some(e) => (1i32, _rt::as_i64(e)),
+            None => {
+              (0i32, 0i64)
+            },
+          };let ptr4 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "component:nana/nana")]
+          unsafe extern "C" {
+            #[link_name = "evaluate-with-limits"]
+            fn wit_import5(_: *mut u8, _: usize, _: i32, _: i64, _: i32, _: i64, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import5(_: *mut u8, _: usize, _: i32, _: i64, _: i32, _: i64, _: *mut u8, ){ unreachable!() }
+          unsafe { wit_import5(ptr0.cast_mut(), len0, result2_0, result2_1, result3_0, result3_1, ptr4) };
+          let l6 = *ptr4.add(0).cast::<*mut u8>();
+          let l7 = *ptr4.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base47 = l6;
+          let len47 = l7;
+          let mut result47 = _rt::Vec::with_capacity(len47);
+          for i in 0..len47 {
+            let base = base47.add(i * (8+10*::core::mem::size_of::<*const u8>()));
+            let e47 = {
+              let l8 = i32::from(*base.add(0).cast::<u8>());
+              let v46 = match l8 {
+                0 => {
+                  let e46 = {
+                    let l9 = *base.add(8).cast::<*mut u8>();
+                    let l10 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len11 = l10;
+                    let bytes11 = _rt::Vec::from_raw_parts(l9.cast(), len11, len11);
+
+                    _rt::string_lift(bytes11)
+                  };
+                  Value::Number(e46)
+                }
+                1 => {
+                  let e46 = {
+                    let l12 = *base.add(8).cast::<*mut u8>();
+                    let l13 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len14 = l13;
+                    let bytes14 = _rt::Vec::from_raw_parts(l12.cast(), len14, len14);
+
+                    _rt::string_lift(bytes14)
+                  };
+                  Value::String(e46)
+                }
+                2 => {
+                  let e46 = {
+                    let l15 = *base.add(8).cast::<*mut u8>();
+                    let l16 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len17 = l16;
+                    let bytes17 = _rt::Vec::from_raw_parts(l15.cast(), len17, len17);
+
+                    _rt::string_lift(bytes17)
+                  };
+                  Value::Keyword(e46)
+                }
+                3 => {
+                  let e46 = {
+                    let l18 = *base.add(8).cast::<*mut u8>();
+                    let l19 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len20 = l19;
+                    let bytes20 = _rt::Vec::from_raw_parts(l18.cast(), len20, len20);
+
+                    _rt::string_lift(bytes20)
+                  };
+                  Value::Symbol(e46)
+                }
+                4 => {
+                  let e46 = {
+                    let l21 = *base.add(8).cast::<*mut u8>();
+                    let l22 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len23 = l22;
+
+                    _rt::Vec::from_raw_parts(l21.cast(), len23, len23)
+                  };
+                  Value::List(e46)
+                }
+                5 => {
+                  let e46 = {
+                    let l24 = *base.add(8).cast::<i32>();
+                    let l25 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                    let l26 = *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len27 = l26;
+
+                    TaggedTuple{
+                      tag: l24 as u32,
+                      values: _rt::Vec::from_raw_parts(l25.cast(), len27, len27),
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
-                        unreachable!()
+                  };
+                  Value::TaggedTuple(e46)
+                }
+                6 => {
+                  let e46 = {
+                    let l28 = *base.add(8).cast::<*mut u8>();
+                    let l29 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len30 = l29;
+                    let bytes30 = _rt::Vec::from_raw_parts(l28.cast(), len30, len30);
+
+                    _rt::string_lift(bytes30)
+                  };
+                  Value::FunctionRef(e46)
+                }
+                7 => {
+                  let e46 = {
+                    let l31 = *base.add(8).cast::<*mut u8>();
+                    let l32 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len33 = l32;
+                    let bytes33 = _rt::Vec::from_raw_parts(l31.cast(), len33, len33);
+                    let l34 = i32::from(*base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                    let l38 = i32::from(*base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                    let l42 = *base.add(8+8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                    let l43 = *base.add(8+9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len44 = l43;
+
+                    ErrorValue{
+                      variety: _rt::string_lift(bytes33),
+                      message: match l34 {
+                        0 => None,
+                        1 => {
+                          let e = {
+                            let l35 = *base.add(8+3*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                            let l36 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                            let len37 = l36;
+                            let bytes37 = _rt::Vec::from_raw_parts(l35.cast(), len37, len37);
+
+                            _rt::string_lift(bytes37)
+                          };
+                          Some(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                      },
+                      location: match l38 {
+                        0 => None,
+                        1 => {
+                          let e = {
+                            let l39 = *base.add(8+6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                            let l40 = *base.add(8+7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                            let len41 = l40;
+                            let bytes41 = _rt::Vec::from_raw_parts(l39.cast(), len41, len41);
+
+                            _rt::string_lift(bytes41)
+                          };
+                          Some(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                      },
+                      details: _rt::Vec::from_raw_parts(l42.cast(), len44, len44),
                     }
-                    wit_import(ptr0.cast_mut(), len0, ptr1);
-                    let l2 = *ptr1.add(0).cast::<*mut u8>();
-                    let l3 = *ptr1.add(4).cast::<usize>();
-                    let len4 = l3;
-                    let bytes4 = _rt::Vec::from_raw_parts(l2.cast(), len4, len4);
-                    _rt::string_lift(bytes4)
+                  };
+                  Value::Error(e46)
                 }
+                n => {
+                  debug_assert_eq!(n, 8, "invalid enum discriminant");
+                  let e46 = {
+                    let l45 = *base.add(8).cast::<i64>();
+
+                    l45 as u64
+                  };
+                  Value::Process(e46)
+                }
+              };
+
+              v46
+            };
+            result47.push(e47);
+          }
+          _rt::cabi_dealloc(base47, len47 * (8+10*::core::mem::size_of::<*const u8>()), 8);
+          let l48 = *ptr4.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l49 = *ptr4.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let len50 = l49;
+          let l51 = *ptr4.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l52 = *ptr4.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let len53 = l52;
+          let l54 = *ptr4.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l55 = *ptr4.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base59 = l54;
+          let len59 = l55;
+          let mut result59 = _rt::Vec::with_capacity(len59);
+          for i in 0..len59 {
+            let base = base59.add(i * (2*::core::mem::size_of::<*const u8>()));
+            let e59 = {
+              let l56 = *base.add(0).cast::<*mut u8>();
+              let l57 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len58 = l57;
+              let bytes58 = _rt::Vec::from_raw_parts(l56.cast(), len58, len58);
+
+              _rt::string_lift(bytes58)
+            };
+            result59.push(e59);
+          }
+          _rt::cabi_dealloc(base59, len59 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+          let l60 = *ptr4.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l61 = *ptr4.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base73 = l60;
+          let len73 = l61;
+          let mut result73 = _rt::Vec::with_capacity(len73);
+          for i in 0..len73 {
+            let base = base73.add(i * (8+6*::core::mem::size_of::<*const u8>()));
+            let e73 = {
+              let l62 = *base.add(0).cast::<*mut u8>();
+              let l63 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len64 = l63;
+              let bytes64 = _rt::Vec::from_raw_parts(l62.cast(), len64, len64);
+              let l65 = *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l66 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len67 = l66;
+              let bytes67 = _rt::Vec::from_raw_parts(l65.cast(), len67, len67);
+              let l68 = *base.add(4*::core::mem::size_of::<*const u8>()).cast::<i32>();
+              let l69 = *base.add(4+4*::core::mem::size_of::<*const u8>()).cast::<i32>();
+              let l70 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l71 = *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len72 = l71;
+              let bytes72 = _rt::Vec::from_raw_parts(l70.cast(), len72, len72);
+
+              Diagnostic{
+                message: _rt::string_lift(bytes64),
+                location: _rt::string_lift(bytes67),
+                line: l68 as u32,
+                column: l69 as u32,
+                snippet: _rt::string_lift(bytes72),
+              }
+            };
+            result73.push(e73);
+          }
+          _rt::cabi_dealloc(base73, len73 * (8+6*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+          let result74 = Evaluation{
+            values: result47,
+            results: _rt::Vec::from_raw_parts(l48.cast(), len50, len50),
+            spawned: _rt::Vec::from_raw_parts(l51.cast(), len53, len53),
+            logs: result59,
+            diagnostics: result73,
+          };
+          result74
+        }
+      }
+      impl Session {
+        #[allow(unused_unsafe, clippy::all)]
+        pub fn new() -> Self{
+          unsafe {
+
+            #[cfg(target_arch = "wasm32")]
+            #[link(wasm_import_module = "component:nana/nana")]
+            unsafe extern "C" {
+              #[link_name = "[constructor]session"]
+              fn wit_import0() -> i32;
             }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            unsafe extern "C" fn wit_import0() -> i32{ unreachable!() }
+            let ret = unsafe { wit_import0() };
+            unsafe { Session::from_handle(ret as u32) }
+          }
         }
+      }
+      impl Session {
+        #[allow(unused_unsafe, clippy::all)]
+        /// Code that fails to parse defines nothing.
+        pub fn evaluate(&self,code: &str,) -> Evaluation{
+          unsafe {
+
+            #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+            #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+            struct RetArea([::core::mem::MaybeUninit::<u8>; 10*::core::mem::size_of::<*const u8>()]);
+            let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 10*::core::mem::size_of::<*const u8>()]);
+            let vec0 = code;
+            let ptr0 = vec0.as_ptr().cast::<u8>();
+            let len0 = vec0.len();
+            let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+            #[cfg(target_arch = "wasm32")]
+            #[link(wasm_import_module = "component:nana/nana")]
+            unsafe extern "C" {
+              #[link_name = "[method]session.evaluate"]
+              fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8, );
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            unsafe extern "C" fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8, ){ unreachable!() }
+            unsafe { wit_import2((self).handle() as i32, ptr0.cast_mut(), len0, ptr1) };
+            let l3 = *ptr1.add(0).cast::<*mut u8>();
+            let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+            let base44 = l3;
+            let len44 = l4;
+            let mut result44 = _rt::Vec::with_capacity(len44);
+            for i in 0..len44 {
+              let base = base44.add(i * (8+10*::core::mem::size_of::<*const u8>()));
+              let e44 = {
+                let l5 = i32::from(*base.add(0).cast::<u8>());
+                let v43 = match l5 {
+                  0 => {
+                    let e43 = {
+                      let l6 = *base.add(8).cast::<*mut u8>();
+                      let l7 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len8 = l7;
+                      let bytes8 = _rt::Vec::from_raw_parts(l6.cast(), len8, len8);
+
+                      _rt::string_lift(bytes8)
+                    };
+                    Value::Number(e43)
+                  }
+                  1 => {
+                    let e43 = {
+                      let l9 = *base.add(8).cast::<*mut u8>();
+                      let l10 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len11 = l10;
+                      let bytes11 = _rt::Vec::from_raw_parts(l9.cast(), len11, len11);
+
+                      _rt::string_lift(bytes11)
+                    };
+                    Value::String(e43)
+                  }
+                  2 => {
+                    let e43 = {
+                      let l12 = *base.add(8).cast::<*mut u8>();
+                      let l13 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len14 = l13;
+                      let bytes14 = _rt::Vec::from_raw_parts(l12.cast(), len14, len14);
+
+                      _rt::string_lift(bytes14)
+                    };
+                    Value::Keyword(e43)
+                  }
+                  3 => {
+                    let e43 = {
+                      let l15 = *base.add(8).cast::<*mut u8>();
+                      let l16 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len17 = l16;
+                      let bytes17 = _rt::Vec::from_raw_parts(l15.cast(), len17, len17);
+
+                      _rt::string_lift(bytes17)
+                    };
+                    Value::Symbol(e43)
+                  }
+                  4 => {
+                    let e43 = {
+                      let l18 = *base.add(8).cast::<*mut u8>();
+                      let l19 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len20 = l19;
+
+                      _rt::Vec::from_raw_parts(l18.cast(), len20, len20)
+                    };
+                    Value::List(e43)
+                  }
+                  5 => {
+                    let e43 = {
+                      let l21 = *base.add(8).cast::<i32>();
+                      let l22 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l23 = *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len24 = l23;
+
+                      TaggedTuple{
+                        tag: l21 as u32,
+                        values: _rt::Vec::from_raw_parts(l22.cast(), len24, len24),
+                      }
+                    };
+                    Value::TaggedTuple(e43)
+                  }
+                  6 => {
+                    let e43 = {
+                      let l25 = *base.add(8).cast::<*mut u8>();
+                      let l26 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len27 = l26;
+                      let bytes27 = _rt::Vec::from_raw_parts(l25.cast(), len27, len27);
+
+                      _rt::string_lift(bytes27)
+                    };
+                    Value::FunctionRef(e43)
+                  }
+                  7 => {
+                    let e43 = {
+                      let l28 = *base.add(8).cast::<*mut u8>();
+                      let l29 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len30 = l29;
+                      let bytes30 = _rt::Vec::from_raw_parts(l28.cast(), len30, len30);
+                      let l31 = i32::from(*base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l35 = i32::from(*base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l39 = *base.add(8+8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l40 = *base.add(8+9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len41 = l40;
+
+                      ErrorValue{
+                        variety: _rt::string_lift(bytes30),
+                        message: match l31 {
+                          0 => None,
+                          1 => {
+                            let e = {
+                              let l32 = *base.add(8+3*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                              let l33 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                              let len34 = l33;
+                              let bytes34 = _rt::Vec::from_raw_parts(l32.cast(), len34, len34);
+
+                              _rt::string_lift(bytes34)
+                            };
+                            Some(e)
+                          }
+                          _ => _rt::invalid_enum_discriminant(),
+                        },
+                        location: match l35 {
+                          0 => None,
+                          1 => {
+                            let e = {
+                              let l36 = *base.add(8+6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                              let l37 = *base.add(8+7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                              let len38 = l37;
+                              let bytes38 = _rt::Vec::from_raw_parts(l36.cast(), len38, len38);
+
+                              _rt::string_lift(bytes38)
+                            };
+                            Some(e)
+                          }
+                          _ => _rt::invalid_enum_discriminant(),
+                        },
+                        details: _rt::Vec::from_raw_parts(l39.cast(), len41, len41),
+                      }
+                    };
+                    Value::Error(e43)
+                  }
+                  n => {
+                    debug_assert_eq!(n, 8, "invalid enum discriminant");
+                    let e43 = {
+                      let l42 = *base.add(8).cast::<i64>();
+
+                      l42 as u64
+                    };
+                    Value::Process(e43)
+                  }
+                };
+
+                v43
+              };
+              result44.push(e44);
+            }
+            _rt::cabi_dealloc(base44, len44 * (8+10*::core::mem::size_of::<*const u8>()), 8);
+            let l45 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+            let l46 = *ptr1.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+            let len47 = l46;
+            let l48 = *ptr1.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+            let l49 = *ptr1.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+            let len50 = l49;
+            let l51 = *ptr1.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+            let l52 = *ptr1.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+            let base56 = l51;
+            let len56 = l52;
+            let mut result56 = _rt::Vec::with_capacity(len56);
+            for i in 0..len56 {
+              let base = base56.add(i * (2*::core::mem::size_of::<*const u8>()));
+              let e56 = {
+                let l53 = *base.add(0).cast::<*mut u8>();
+                let l54 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len55 = l54;
+                let bytes55 = _rt::Vec::from_raw_parts(l53.cast(), len55, len55);
+
+                _rt::string_lift(bytes55)
+              };
+              result56.push(e56);
+            }
+            _rt::cabi_dealloc(base56, len56 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+            let l57 = *ptr1.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+            let l58 = *ptr1.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+            let base70 = l57;
+            let len70 = l58;
+            let mut result70 = _rt::Vec::with_capacity(len70);
+            for i in 0..len70 {
+              let base = base70.add(i * (8+6*::core::mem::size_of::<*const u8>()));
+              let e70 = {
+                let l59 = *base.add(0).cast::<*mut u8>();
+                let l60 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len61 = l60;
+                let bytes61 = _rt::Vec::from_raw_parts(l59.cast(), len61, len61);
+                let l62 = *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l63 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len64 = l63;
+                let bytes64 = _rt::Vec::from_raw_parts(l62.cast(), len64, len64);
+                let l65 = *base.add(4*::core::mem::size_of::<*const u8>()).cast::<i32>();
+                let l66 = *base.add(4+4*::core::mem::size_of::<*const u8>()).cast::<i32>();
+                let l67 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l68 = *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len69 = l68;
+                let bytes69 = _rt::Vec::from_raw_parts(l67.cast(), len69, len69);
+
+                Diagnostic{
+                  message: _rt::string_lift(bytes61),
+                  location: _rt::string_lift(bytes64),
+                  line: l65 as u32,
+                  column: l66 as u32,
+                  snippet: _rt::string_lift(bytes69),
+                }
+              };
+              result70.push(e70);
+            }
+            _rt::cabi_dealloc(base70, len70 * (8+6*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+            let result71 = Evaluation{
+              values: result44,
+              results: _rt::Vec::from_raw_parts(l45.cast(), len47, len47),
+              spawned: _rt::Vec::from_raw_parts(l48.cast(), len50, len50),
+              logs: result56,
+              diagnostics: result70,
+            };
+            result71
+          }
+        }
+      }
+      impl Session {
+        #[allow(unused_unsafe, clippy::all)]
+        pub fn evaluate_with_limits(&self,code: &str,limits: Limits,) -> Evaluation{
+          unsafe {
+
+            #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+            #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+            struct RetArea([::core::mem::MaybeUninit::<u8>; 10*::core::mem::size_of::<*const u8>()]);
+            let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 10*::core::mem::size_of::<*const u8>()]);
+            let vec0 = code;
+            let ptr0 = vec0.as_ptr().cast::<u8>();
+            let len0 = vec0.len();
+            let Limits{ max_steps:max_steps1, time_limit_ms:time_limit_ms1, } = limits;
+            let (result2_0,result2_1,) = match max_steps1 {
+              Some(e) => (1i32, _rt::as_i64(e)),
+              None => {
+                (0i32, 0i64)
+              },
+            };let (result3_0,result3_1,) = match time_limit_ms1 {
+              Some(e) => (1i32, _rt::as_i64(e)),
+              None => {
+                (0i32, 0i64)
+              },
+            };let ptr4 = ret_area.0.as_mut_ptr().cast::<u8>();
+            #[cfg(target_arch = "wasm32")]
+            #[link(wasm_import_module = "component:nana/nana")]
+            unsafe extern "C" {
+              #[link_name = "[method]session.evaluate-with-limits"]
+              fn wit_import5(_: i32, _: *mut u8, _: usize, _: i32, _: i64, _: i32, _: i64, _: *mut u8, );
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            unsafe extern "C" fn wit_import5(_: i32, _: *mut u8, _: usize, _: i32, _: i64, _: i32, _: i64, _: *mut u8, ){ unreachable!() }
+            unsafe { wit_import5((self).handle() as i32, ptr0.cast_mut(), len0, result2_0, result2_1, result3_0, result3_1, ptr4) };
+            let l6 = *ptr4.add(0).cast::<*mut u8>();
+            let l7 = *ptr4.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+            let base47 = l6;
+            let len47 = l7;
+            let mut result47 = _rt::Vec::with_capacity(len47);
+            for i in 0..len47 {
+              let base = base47.add(i * (8+10*::core::mem::size_of::<*const u8>()));
+              let e47 = {
+                let l8 = i32::from(*base.add(0).cast::<u8>());
+                let v46 = match l8 {
+                  0 => {
+                    let e46 = {
+                      let l9 = *base.add(8).cast::<*mut u8>();
+                      let l10 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len11 = l10;
+                      let bytes11 = _rt::Vec::from_raw_parts(l9.cast(), len11, len11);
+
+                      _rt::string_lift(bytes11)
+                    };
+                    Value::Number(e46)
+                  }
+                  1 => {
+                    let e46 = {
+                      let l12 = *base.add(8).cast::<*mut u8>();
+                      let l13 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len14 = l13;
+                      let bytes14 = _rt::Vec::from_raw_parts(l12.cast(), len14, len14);
+
+                      _rt::string_lift(bytes14)
+                    };
+                    Value::String(e46)
+                  }
+                  2 => {
+                    let e46 = {
+                      let l15 = *base.add(8).cast::<*mut u8>();
+                      let l16 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len17 = l16;
+                      let bytes17 = _rt::Vec::from_raw_parts(l15.cast(), len17, len17);
+
+                      _rt::string_lift(bytes17)
+                    };
+                    Value::Keyword(e46)
+                  }
+                  3 => {
+                    let e46 = {
+                      let l18 = *base.add(8).cast::<*mut u8>();
+                      let l19 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len20 = l19;
+                      let bytes20 = _rt::Vec::from_raw_parts(l18.cast(), len20, len20);
+
+                      _rt::string_lift(bytes20)
+                    };
+                    Value::Symbol(e46)
+                  }
+                  4 => {
+                    let e46 = {
+                      let l21 = *base.add(8).cast::<*mut u8>();
+                      let l22 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len23 = l22;
+
+                      _rt::Vec::from_raw_parts(l21.cast(), len23, len23)
+                    };
+                    Value::List(e46)
+                  }
+                  5 => {
+                    let e46 = {
+                      let l24 = *base.add(8).cast::<i32>();
+                      let l25 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l26 = *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len27 = l26;
+
+                      TaggedTuple{
+                        tag: l24 as u32,
+                        values: _rt::Vec::from_raw_parts(l25.cast(), len27, len27),
+                      }
+                    };
+                    Value::TaggedTuple(e46)
+                  }
+                  6 => {
+                    let e46 = {
+                      let l28 = *base.add(8).cast::<*mut u8>();
+                      let l29 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len30 = l29;
+                      let bytes30 = _rt::Vec::from_raw_parts(l28.cast(), len30, len30);
+
+                      _rt::string_lift(bytes30)
+                    };
+                    Value::FunctionRef(e46)
+                  }
+                  7 => {
+                    let e46 = {
+                      let l31 = *base.add(8).cast::<*mut u8>();
+                      let l32 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len33 = l32;
+                      let bytes33 = _rt::Vec::from_raw_parts(l31.cast(), len33, len33);
+                      let l34 = i32::from(*base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l38 = i32::from(*base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l42 = *base.add(8+8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l43 = *base.add(8+9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len44 = l43;
+
+                      ErrorValue{
+                        variety: _rt::string_lift(bytes33),
+                        message: match l34 {
+                          0 => None,
+                          1 => {
+                            let e = {
+                              let l35 = *base.add(8+3*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                              let l36 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                              let len37 = l36;
+                              let bytes37 = _rt::Vec::from_raw_parts(l35.cast(), len37, len37);
+
+                              _rt::string_lift(bytes37)
+                            };
+                            Some(e)
+                          }
+                          _ => _rt::invalid_enum_discriminant(),
+                        },
+                        location: match l38 {
+                          0 => None,
+                          1 => {
+                            let e = {
+                              let l39 = *base.add(8+6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                              let l40 = *base.add(8+7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                              let len41 = l40;
+                              let bytes41 = _rt::Vec::from_raw_parts(l39.cast(), len41, len41);
+
+                              _rt::string_lift(bytes41)
+                            };
+                            Some(e)
+                          }
+                          _ => _rt::invalid_enum_discriminant(),
+                        },
+                        details: _rt::Vec::from_raw_parts(l42.cast(), len44, len44),
+                      }
+                    };
+                    Value::Error(e46)
+                  }
+                  n => {
+                    debug_assert_eq!(n, 8, "invalid enum discriminant");
+                    let e46 = {
+                      let l45 = *base.add(8).cast::<i64>();
+
+                      l45 as u64
+                    };
+                    Value::Process(e46)
+                  }
+                };
+
+                v46
+              };
+              result47.push(e47);
+            }
+            _rt::cabi_dealloc(base47, len47 * (8+10*::core::mem::size_of::<*const u8>()), 8);
+            let l48 = *ptr4.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+            let l49 = *ptr4.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+            let len50 = l49;
+            let l51 = *ptr4.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+            let l52 = *ptr4.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+            let len53 = l52;
+            let l54 = *ptr4.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+            let l55 = *ptr4.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+            let base59 = l54;
+            let len59 = l55;
+            let mut result59 = _rt::Vec::with_capacity(len59);
+            for i in 0..len59 {
+              let base = base59.add(i * (2*::core::mem::size_of::<*const u8>()));
+              let e59 = {
+                let l56 = *base.add(0).cast::<*mut u8>();
+                let l57 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len58 = l57;
+                let bytes58 = _rt::Vec::from_raw_parts(l56.cast(), len58, len58);
+
+                _rt::string_lift(bytes58)
+              };
+              result59.push(e59);
+            }
+            _rt::cabi_dealloc(base59, len59 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+            let l60 = *ptr4.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+            let l61 = *ptr4.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+            let base73 = l60;
+            let len73 = l61;
+            let mut result73 = _rt::Vec::with_capacity(len73);
+            for i in 0..len73 {
+              let base = base73.add(i * (8+6*::core::mem::size_of::<*const u8>()));
+              let e73 = {
+                let l62 = *base.add(0).cast::<*mut u8>();
+                let l63 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len64 = l63;
+                let bytes64 = _rt::Vec::from_raw_parts(l62.cast(), len64, len64);
+                let l65 = *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l66 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len67 = l66;
+                let bytes67 = _rt::Vec::from_raw_parts(l65.cast(), len67, len67);
+                let l68 = *base.add(4*::core::mem::size_of::<*const u8>()).cast::<i32>();
+                let l69 = *base.add(4+4*::core::mem::size_of::<*const u8>()).cast::<i32>();
+                let l70 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l71 = *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len72 = l71;
+                let bytes72 = _rt::Vec::from_raw_parts(l70.cast(), len72, len72);
+
+                Diagnostic{
+                  message: _rt::string_lift(bytes64),
+                  location: _rt::string_lift(bytes67),
+                  line: l68 as u32,
+                  column: l69 as u32,
+                  snippet: _rt::string_lift(bytes72),
+                }
+              };
+              result73.push(e73);
+            }
+            _rt::cabi_dealloc(base73, len73 * (8+6*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+            let result74 = Evaluation{
+              values: result47,
+              results: _rt::Vec::from_raw_parts(l48.cast(), len50, len50),
+              spawned: _rt::Vec::from_raw_parts(l51.cast(), len53, len53),
+              logs: result59,
+              diagnostics: result73,
+            };
+            result74
+          }
+        }
+      }
+      impl Session {
+        #[allow(unused_unsafe, clippy::all)]
+        /// Forgets every definition, leaving only the standard library.
+        pub fn reset(&self,) -> (){
+          unsafe {
+
+            #[cfg(target_arch = "wasm32")]
+            #[link(wasm_import_module = "component:nana/nana")]
+            unsafe extern "C" {
+              #[link_name = "[method]session.reset"]
+              fn wit_import0(_: i32, );
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            unsafe extern "C" fn wit_import0(_: i32, ){ unreachable!() }
+            unsafe { wit_import0((self).handle() as i32) };
+          }
+        }
+      }
+      impl Session {
+        #[allow(unused_unsafe, clippy::all)]
+        pub fn bindings(&self,) -> _rt::Vec::<_rt::String>{
+          unsafe {
+
+            #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+            #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+            struct RetArea([::core::mem::MaybeUninit::<u8>; 2*::core::mem::size_of::<*const u8>()]);
+            let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 2*::core::mem::size_of::<*const u8>()]);
+            let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+            #[cfg(target_arch = "wasm32")]
+            #[link(wasm_import_module = "component:nana/nana")]
+            unsafe extern "C" {
+              #[link_name = "[method]session.bindings"]
+              fn wit_import1(_: i32, _: *mut u8, );
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            unsafe extern "C" fn wit_import1(_: i32, _: *mut u8, ){ unreachable!() }
+            unsafe { wit_import1((self).handle() as i32, ptr0) };
+            let l2 = *ptr0.add(0).cast::<*mut u8>();
+            let l3 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+            let base7 = l2;
+            let len7 = l3;
+            let mut result7 = _rt::Vec::with_capacity(len7);
+            for i in 0..len7 {
+              let base = base7.add(i * (2*::core::mem::size_of::<*const u8>()));
+              let e7 = {
+                let l4 = *base.add(0).cast::<*mut u8>();
+                let l5 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len6 = l5;
+                let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+
+                _rt::string_lift(bytes6)
+              };
+              result7.push(e7);
+            }
+            _rt::cabi_dealloc(base7, len7 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+            let result8 = result7;
+            result8
+          }
+        }
+      }
+
     }
+
+  }
 }
-#[allow(dead_code)]
-pub mod exports {
-    #[allow(dead_code)]
-    pub mod component {
-        #[allow(dead_code)]
-        pub mod nana {
-            #[allow(dead_code, clippy::all)]
-            pub mod nana {
-                #[used]
-                #[doc(hidden)]
-                static __FORCE_SECTION_REF: fn() = super::super::super::super::__link_custom_section_describing_imports;
-                use super::super::super::super::_rt;
-                #[doc(hidden)]
-                #[allow(non_snake_case)]
-                pub unsafe fn _export_evaluate_cabi<T: Guest>(
-                    arg0: *mut u8,
-                    arg1: usize,
-                ) -> *mut u8 {
-                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let len0 = arg1;
-                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
-                    let result1 = T::evaluate(_rt::string_lift(bytes0));
-                    let ptr2 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
-                    let vec3 = (result1.into_bytes()).into_boxed_slice();
-                    let ptr3 = vec3.as_ptr().cast::<u8>();
-                    let len3 = vec3.len();
-                    ::core::mem::forget(vec3);
-                    *ptr2.add(4).cast::<usize>() = len3;
-                    *ptr2.add(0).cast::<*mut u8>() = ptr3.cast_mut();
-                    ptr2
-                }
-                #[doc(hidden)]
-                #[allow(non_snake_case)]
-                pub unsafe fn __post_return_evaluate<T: Guest>(arg0: *mut u8) {
-                    let l0 = *arg0.add(0).cast::<*mut u8>();
-                    let l1 = *arg0.add(4).cast::<usize>();
-                    _rt::cabi_dealloc(l0, l1, 1);
-                }
-                pub trait Guest {
-                    fn evaluate(name: _rt::String) -> _rt::String;
-                }
-                #[doc(hidden)]
-                macro_rules! __export_component_nana_nana_cabi {
-                    ($ty:ident with_types_in $($path_to_types:tt)*) => {
-                        const _ : () = { #[export_name = "component:nana/nana#evaluate"]
-                        unsafe extern "C" fn export_evaluate(arg0 : * mut u8, arg1 :
-                        usize,) -> * mut u8 { $($path_to_types)*::
-                        _export_evaluate_cabi::<$ty > (arg0, arg1) } #[export_name =
-                        "cabi_post_component:nana/nana#evaluate"] unsafe extern "C" fn
-                        _post_return_evaluate(arg0 : * mut u8,) { $($path_to_types)*::
-                        __post_return_evaluate::<$ty > (arg0) } };
-                    };
-                }
-                #[doc(hidden)]
-                pub(crate) use __export_component_nana_nana_cabi;
-                #[repr(align(4))]
-                struct _RetArea([::core::mem::MaybeUninit<u8>; 8]);
-                static mut _RET_AREA: _RetArea = _RetArea(
-                    [::core::mem::MaybeUninit::uninit(); 8],
-                );
-            }
-        }
-    }
-    #[allow(dead_code)]
-    pub mod wasi {
-        #[allow(dead_code)]
-        pub mod cli {
-            #[allow(dead_code, clippy::all)]
-            pub mod run {
-                #[used]
-                #[doc(hidden)]
-                static __FORCE_SECTION_REF: fn() = super::super::super::super::__link_custom_section_describing_imports;
-                use super::super::super::super::_rt;
-                #[doc(hidden)]
-                #[allow(non_snake_case)]
-                pub unsafe fn _export_run_cabi<T: Guest>() -> i32 {
-                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let result0 = T::run();
-                    let result1 = match result0 {
-                        Ok(_) => 0i32,
-                        Err(_) => 1i32,
-                    };
-                    result1
-                }
-                pub trait Guest {
-                    /// Run the program.
-                    fn run() -> Result<(), ()>;
-                }
-                #[doc(hidden)]
-                macro_rules! __export_wasi_cli_run_0_2_2_cabi {
-                    ($ty:ident with_types_in $($path_to_types:tt)*) => {
-                        const _ : () = { #[export_name = "wasi:cli/run@0.2.2#run"] unsafe
-                        extern "C" fn export_run() -> i32 { $($path_to_types)*::
-                        _export_run_cabi::<$ty > () } };
-                    };
-                }
-                #[doc(hidden)]
-                pub(crate) use __export_wasi_cli_run_0_2_2_cabi;
+#[allow(dead_code, clippy::all)]
+pub mod wasi {
+  pub mod clocks {
+    /// WASI Monotonic Clock is a clock API intended to let users measure elapsed
+    /// time.
+    ///
+    /// It is intended to be portable at least between Unix-family platforms and
+    /// Windows.
+    ///
+    /// A monotonic clock is a clock which has an unspecified initial value, and
+    /// successive reads of the clock will produce non-decreasing values.
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod monotonic_clock {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::_rt;
+      pub type Pollable = super::super::super::wasi::io::poll::Pollable;
+      /// An instant in time, in nanoseconds. An instant is relative to an
+      /// unspecified initial value, and can only be compared to instances from
+      /// the same monotonic-clock.
+      pub type Instant = u64;
+      /// A duration of time, in nanoseconds.
+      pub type Duration = u64;
+      #[allow(unused_unsafe, clippy::all)]
+      /// Read the current value of the clock.
+      ///
+      /// The clock is monotonic, therefore calling this function repeatedly will
+      /// produce a sequence of non-decreasing values.
+      pub fn now() -> Instant{
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:clocks/monotonic-clock@0.2.2")]
+          unsafe extern "C" {
+            #[link_name = "now"]
+            fn wit_import0() -> i64;
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0() -> i64{ unreachable!() }
+          let ret = unsafe { wit_import0() };
+          ret as u64
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Query the resolution of the clock. Returns the duration of time
+      /// corresponding to a clock tick.
+      pub fn resolution() -> Duration{
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:clocks/monotonic-clock@0.2.2")]
+          unsafe extern "C" {
+            #[link_name = "resolution"]
+            fn wit_import0() -> i64;
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0() -> i64{ unreachable!() }
+          let ret = unsafe { wit_import0() };
+          ret as u64
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Create a `pollable` which will resolve once the specified instant
+      /// has occurred.
+      pub fn subscribe_instant(when: Instant,) -> Pollable{
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:clocks/monotonic-clock@0.2.2")]
+          unsafe extern "C" {
+            #[link_name = "subscribe-instant"]
+            fn wit_import0(_: i64, ) -> i32;
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0(_: i64, ) -> i32{ unreachable!() }
+          let ret = unsafe { wit_import0(_rt::as_i64(when)) };
+          unsafe { super::super::super::wasi::io::poll::Pollable::from_handle(ret as u32) }
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Create a `pollable` that will resolve after the specified duration has
+      /// elapsed from the time this function is invoked.
+      pub fn subscribe_duration(when: Duration,) -> Pollable{
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:clocks/monotonic-clock@0.2.2")]
+          unsafe extern "C" {
+            #[link_name = "subscribe-duration"]
+            fn wit_import0(_: i64, ) -> i32;
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0(_: i64, ) -> i32{ unreachable!() }
+          let ret = unsafe { wit_import0(_rt::as_i64(when)) };
+          unsafe { super::super::super::wasi::io::poll::Pollable::from_handle(ret as u32) }
+        }
+      }
+
+    }
+
+  }
+  pub mod io {
+    /// A poll API intended to let users wait for I/O events on multiple handles
+    /// at once.
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod poll {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::_rt;
+      /// `pollable` represents a single I/O event which may be ready, or not.
+
+      #[derive(Debug)]
+      #[repr(transparent)]
+      pub struct Pollable{
+        handle: _rt::Resource<Pollable>,
+      }
+
+      impl Pollable{
+        #[doc(hidden)]
+        pub unsafe fn from_handle(handle: u32) -> Self {
+          Self {
+            handle: unsafe { _rt::Resource::from_handle(handle) },
+          }
+        }
+
+        #[doc(hidden)]
+        pub fn take_handle(&self) -> u32 {
+          _rt::Resource::take_handle(&self.handle)
+        }
+
+        #[doc(hidden)]
+        pub fn handle(&self) -> u32 {
+          _rt::Resource::handle(&self.handle)
+        }
+      }
+      
+
+      unsafe impl _rt::WasmResource for Pollable{
+        #[inline]
+        unsafe fn drop(_handle: u32) {
+          #[cfg(not(target_arch = "wasm32"))]
+          unreachable!();
+
+          #[cfg(target_arch = "wasm32")]
+          {
+            #[link(wasm_import_module = "wasi:io/poll@0.2.2")]
+            unsafe extern "C" {
+              #[link_name = "[resource-drop]pollable"]
+              fn drop(_: u32);
+            }
+
+            unsafe { drop(_handle) };
+          }
+        }
+      }
+      
+      impl Pollable {
+        #[allow(unused_unsafe, clippy::all)]
+        /// Return the readiness of a pollable. This function never blocks.
+        ///
+        /// Returns `true` when the pollable is ready, and `false` otherwise.
+        pub fn ready(&self,) -> bool{
+          unsafe {
+
+            #[cfg(target_arch = "wasm32")]
+            #[link(wasm_import_module = "wasi:io/poll@0.2.2")]
+            unsafe extern "C" {
+              #[link_name = "[method]pollable.ready"]
+              fn wit_import0(_: i32, ) -> i32;
             }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            unsafe extern "C" fn wit_import0(_: i32, ) -> i32{ unreachable!() }
+            let ret = unsafe { wit_import0((self).handle() as i32) };
+            _rt::bool_lift(ret as u8)
+          }
         }
+      }
+      impl Pollable {
+        #[allow(unused_unsafe, clippy::all)]
+        /// `block` returns immediately if the pollable is ready, and otherwise
+        /// blocks until ready.
+        ///
+        /// This function is equivalent to calling `poll.poll` on a list
+        /// containing only this pollable.
+        pub fn block(&self,) -> (){
+          unsafe {
+
+            #[cfg(target_arch = "wasm32")]
+            #[link(wasm_import_module = "wasi:io/poll@0.2.2")]
+            unsafe extern "C" {
+              #[link_name = "[method]pollable.block"]
+              fn wit_import0(_: i32, );
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            unsafe extern "C" fn wit_import0(_: i32, ){ unreachable!() }
+            unsafe { wit_import0((self).handle() as i32) };
+          }
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Poll for completion on a set of pollables.
+      ///
+      /// This function takes a list of pollables, which identify I/O sources of
+      /// interest, and waits until one or more of the events is ready for I/O.
+      ///
+      /// The result `list<u32>` contains one or more indices of handles in the
+      /// argument list that is ready for I/O.
+      ///
+      /// This function traps if either:
+      /// - the list is empty, or:
+      /// - the list contains more elements than can be indexed with a `u32` value.
+      ///
+      /// A timeout can be implemented by adding a pollable from the
+      /// wasi-clocks API to the list.
+      ///
+      /// This function does not return a `result`; polling in itself does not
+      /// do any I/O so it doesn't fail. If any of the I/O sources identified by
+      /// the pollables has an error, it is indicated by marking the source as
+      /// being ready for I/O.
+      pub fn poll(in_: &[&Pollable],) -> _rt::Vec::<u32>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 2*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 2*::core::mem::size_of::<*const u8>()]);
+          let vec0 = in_;
+          let len0 = vec0.len();
+          let layout0 = _rt::alloc::Layout::from_size_align_unchecked(vec0.len() * 4, 4);
+          let result0 = if layout0.size() != 0 {
+            let ptr = _rt::alloc::alloc(layout0).cast::<u8>();
+            if ptr.is_null()
+            {
+              _rt::alloc::handle_alloc_error(layout0);
+            }
+            ptr
+          }else {
+            ::core::ptr::null_mut()
+          };
+          for (i, e) in vec0.into_iter().enumerate() {
+            let base = result0.add(i * 4);
+            {
+              *base.add(0).cast::<i32>() = (e).handle() as i32;
+            }
+          }
+          let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:io/poll@0.2.2")]
+          unsafe extern "C" {
+            #[link_name = "poll"]
+            fn wit_import2(_: *mut u8, _: usize, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8, ){ unreachable!() }
+          unsafe { wit_import2(result0, len0, ptr1) };
+          let l3 = *ptr1.add(0).cast::<*mut u8>();
+          let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let len5 = l4;
+          let result6 = _rt::Vec::from_raw_parts(l3.cast(), len5, len5);
+          if layout0.size() != 0 {
+            _rt::alloc::dealloc(result0.cast(), layout0);
+          }
+          result6
+        }
+      }
+
     }
+
+  }
 }
-mod _rt {
-    pub use alloc_crate::string::String;
-    pub use alloc_crate::vec::Vec;
-    pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
-        if cfg!(debug_assertions) {
-            String::from_utf8(bytes).unwrap()
-        } else {
-            String::from_utf8_unchecked(bytes)
+#[allow(dead_code, clippy::all)]
+pub mod exports {
+  pub mod component {
+    pub mod nana {
+
+      #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+      pub mod nana {
+        #[used]
+        #[doc(hidden)]
+        static __FORCE_SECTION_REF: fn() =
+        super::super::super::super::__link_custom_section_describing_imports;
+        
+        use super::super::super::super::_rt;
+        /// WIT types can't contain themselves, so the values in an evaluation are
+        /// kept in one flat list. Lists and tagged tuples refer to their items by
+        /// their index in that list.
+        pub type ValueIndex = u32;
+        #[derive(Clone)]
+        pub struct TaggedTuple {
+          pub tag: ValueIndex,
+          pub values: _rt::Vec::<ValueIndex>,
+        }
+        impl ::core::fmt::Debug for TaggedTuple {
+          fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            f.debug_struct("TaggedTuple").field("tag", &self.tag).field("values", &self.values).finish()
+          }
+        }
+        /// An :error(:variety "message" ... :at("file:line:col")) tuple
+        #[derive(Clone)]
+        pub struct ErrorValue {
+          pub variety: _rt::String,
+          pub message: Option<_rt::String>,
+          pub location: Option<_rt::String>,
+          /// Anything else the error carried
+          pub details: _rt::Vec::<ValueIndex>,
+        }
+        impl ::core::fmt::Debug for ErrorValue {
+          fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            f.debug_struct("ErrorValue").field("variety", &self.variety).field("message", &self.message).field("location", &self.location).field("details", &self.details).finish()
+          }
+        }
+        #[derive(Clone)]
+        pub enum Value {
+          /// Sent as decimal digits because Nana's numbers can be larger than
+          /// any WIT integer type
+          Number(_rt::String),
+          String(_rt::String),
+          Keyword(_rt::String),
+          Symbol(_rt::String),
+          List(_rt::Vec::<ValueIndex>),
+          TaggedTuple(TaggedTuple),
+          /// Functions and macros can't leave the interpreter, so only their
+          /// printed form is sent
+          FunctionRef(_rt::String),
+          Error(ErrorValue),
+          /// A handle to a spawned process, identified by its id
+          Process(u64),
+        }
+        impl ::core::fmt::Debug for Value {
+          fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            match self {
+              Value::Number(e) => {
+                f.debug_tuple("Value::Number").field(e).finish()
+              }
+              Value::String(e) => {
+                f.debug_tuple("Value::String").field(e).finish()
+              }
+              Value::Keyword(e) => {
+                f.debug_tuple("Value::Keyword").field(e).finish()
+              }
+              Value::Symbol(e) => {
+                f.debug_tuple("Value::Symbol").field(e).finish()
+              }
+              Value::List(e) => {
+                f.debug_tuple("Value::List").field(e).finish()
+              }
+              Value::TaggedTuple(e) => {
+                f.debug_tuple("Value::TaggedTuple").field(e).finish()
+              }
+              Value::FunctionRef(e) => {
+                f.debug_tuple("Value::FunctionRef").field(e).finish()
+              }
+              Value::Error(e) => {
+                f.debug_tuple("Value::Error").field(e).finish()
+              }
+              Value::Process(e) => {
+                f.debug_tuple("Value::Process").field(e).finish()
+              }
+            }
+          }
+        }
+        /// Something that stopped the code from being parsed. Code with
+        /// diagnostics isn't run.
+        #[derive(Clone)]
+        pub struct Diagnostic {
+          pub message: _rt::String,
+          pub location: _rt::String,
+          pub line: u32,
+          pub column: u32,
+          pub snippet: _rt::String,
+        }
+        impl ::core::fmt::Debug for Diagnostic {
+          fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            f.debug_struct("Diagnostic").field("message", &self.message).field("location", &self.location).field("line", &self.line).field("column", &self.column).field("snippet", &self.snippet).finish()
+          }
+        }
+        #[derive(Clone)]
+        pub struct Evaluation {
+          pub values: _rt::Vec::<Value>,
+          /// The result of each top level form, in order
+          pub results: _rt::Vec::<ValueIndex>,
+          /// The results of spawned processes, in order of completion
+          pub spawned: _rt::Vec::<ValueIndex>,
+          pub logs: _rt::Vec::<_rt::String>,
+          pub diagnostics: _rt::Vec::<Diagnostic>,
+        }
+        impl ::core::fmt::Debug for Evaluation {
+          fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            f.debug_struct("Evaluation").field("values", &self.values).field("results", &self.results).field("spawned", &self.spawned).field("logs", &self.logs).field("diagnostics", &self.diagnostics).finish()
+          }
+        }
+        /// Stops code that would otherwise run forever. Every step a process takes
+        /// counts towards max-steps.
+        #[repr(C)]
+        #[derive(Clone, Copy)]
+        pub struct Limits {
+          pub max_steps: Option<u64>,
+          pub time_limit_ms: Option<u64>,
+        }
+        impl ::core::fmt::Debug for Limits {
+          fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            f.debug_struct("Limits").field("max-steps", &self.max_steps).field("time-limit-ms", &self.time_limit_ms).finish()
+          }
+        }
+        /// An interpreter that keeps its definitions between calls to evaluate, so
+        /// hosts like a REPL can build up a program one cell at a time.
+
+        #[derive(Debug)]
+        #[repr(transparent)]
+        pub struct Session{
+          handle: _rt::Resource<Session>,
+        }
+
+        type _SessionRep<T> = Option<T>;
+
+        impl Session{
+          /// Creates a new resource from the specified representation.
+          ///
+          /// This function will create a new resource handle by moving `val` onto
+          /// the heap and then passing that heap pointer to the component model to
+          /// create a handle. The owned handle is then returned as `Session`.
+          pub fn new<T: GuestSession>(val: T) -> Self {
+            Self::type_guard::<T>();
+            let val: _SessionRep<T> = Some(val);
+            let ptr: *mut _SessionRep<T> =
+            _rt::Box::into_raw(_rt::Box::new(val));
+            unsafe {
+              Self::from_handle(T::_resource_new(ptr.cast()))
+            }
+          }
+
+          /// Gets access to the underlying `T` which represents this resource.
+          pub fn get<T: GuestSession>(&self) -> &T {
+            let ptr = unsafe { &*self.as_ptr::<T>() };
+            ptr.as_ref().unwrap()
+          }
+
+          /// Gets mutable access to the underlying `T` which represents this
+          /// resource.
+          pub fn get_mut<T: GuestSession>(&mut self) -> &mut T {
+            let ptr = unsafe { &mut *self.as_ptr::<T>() };
+            ptr.as_mut().unwrap()
+          }
+
+          /// Consumes this resource and returns the underlying `T`.
+          pub fn into_inner<T: GuestSession>(self) -> T {
+            let ptr = unsafe { &mut *self.as_ptr::<T>() };
+            ptr.take().unwrap()
+          }
+
+          #[doc(hidden)]
+          pub unsafe fn from_handle(handle: u32) -> Self {
+            Self {
+              handle: unsafe { _rt::Resource::from_handle(handle) },
+            }
+          }
+
+          #[doc(hidden)]
+          pub fn take_handle(&self) -> u32 {
+            _rt::Resource::take_handle(&self.handle)
+          }
+
+          #[doc(hidden)]
+          pub fn handle(&self) -> u32 {
+            _rt::Resource::handle(&self.handle)
+          }
+
+          // It's theoretically possible to implement the `GuestSession` trait twice
+          // so guard against using it with two different types here.
+          #[doc(hidden)]
+          fn type_guard<T: 'static>() {
+            use core::any::TypeId;
+            static mut LAST_TYPE: Option<TypeId> = None;
+            unsafe {
+              assert!(!cfg!(target_feature = "atomics"));
+              let id = TypeId::of::<T>();
+              match LAST_TYPE {
+                Some(ty) => assert!(ty == id, "cannot use two types with this resource type"),
+                None => LAST_TYPE = Some(id),
+              }
+            }
+          }
+
+          #[doc(hidden)]
+          pub unsafe fn dtor<T: 'static>(handle: *mut u8) {
+            Self::type_guard::<T>();
+            let _ = unsafe { _rt::Box::from_raw(handle as *mut _SessionRep<T>) };
+          }
+
+          fn as_ptr<T: GuestSession>(&self) -> *mut _SessionRep<T> {
+            Session::type_guard::<T>();
+            T::_resource_rep(self.handle()).cast()
+          }
+        }
+
+        /// A borrowed version of [`Session`] which represents a borrowed value
+        /// with the lifetime `'a`.
+        #[derive(Debug)]
+        #[repr(transparent)]
+        pub struct SessionBorrow<'a> {
+          rep: *mut u8,
+          _marker: core::marker::PhantomData<&'a Session>,
+        }
+
+        impl<'a> SessionBorrow<'a>{
+          #[doc(hidden)]
+          pub unsafe fn lift(rep: usize) -> Self {
+            Self {
+              rep: rep as *mut u8,
+              _marker: core::marker::PhantomData,
+            }
+          }
+
+          /// Gets access to the underlying `T` in this resource.
+          pub fn get<T: GuestSession>(&self) -> &T {
+            let ptr = unsafe { &mut *self.as_ptr::<T>() };
+            ptr.as_ref().unwrap()
+          }
+
+          // NB: mutable access is not allowed due to the component model allowing
+          // multiple borrows of the same resource.
+
+          fn as_ptr<T: 'static>(&self) -> *mut _SessionRep<T> {
+            Session::type_guard::<T>();
+            self.rep.cast()
+          }
         }
+        
+
+        unsafe impl _rt::WasmResource for Session{
+          #[inline]
+          unsafe fn drop(_handle: u32) {
+            #[cfg(not(target_arch = "wasm32"))]
+            unreachable!();
+
+            #[cfg(target_arch = "wasm32")]
+            {
+              #[link(wasm_import_module = "[export]component:nana/nana")]
+              unsafe extern "C" {
+                #[link_name = "[resource-drop]session"]
+                fn drop(_: u32);
+              }
+
+              unsafe { drop(_handle) };
+            }
+          }
+        }
+        
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        pub unsafe fn _export_evaluate_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 {#[cfg(target_arch="wasm32")]
+        _rt::run_ctors_once();let len0 = arg1;
+        let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+        let result1 = T::evaluate(_rt::string_lift(bytes0));
+        let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+        let Evaluation{ values:values3, results:results3, spawned:spawned3, logs:logs3, diagnostics:diagnostics3, } = result1;
+        let vec17 = values3;
+        let len17 = vec17.len();
+        let layout17 = _rt::alloc::Layout::from_size_align_unchecked(vec17.len() * (8+10*::core::mem::size_of::<*const u8>()), 8);
+        let result17 = if layout17.size() != 0 {
+          let ptr = _rt::alloc::alloc(layout17).cast::<u8>();
+          if ptr.is_null()
+          {
+            _rt::alloc::handle_alloc_error(layout17);
+          }
+          ptr
+        }else {
+          ::core::ptr::null_mut()
+        };
+        for (i, e) in vec17.into_iter().enumerate() {
+          let base = result17.add(i * (8+10*::core::mem::size_of::<*const u8>()));
+          {
+            match e {
+              Value::Number(e) => {
+                *base.add(0).cast::<u8>() = (0i32) as u8;
+                let vec4 = (e.into_bytes()).into_boxed_slice();
+                let ptr4 = vec4.as_ptr().cast::<u8>();
+                let len4 = vec4.len();
+                ::core::mem::forget(vec4);
+                *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+                *base.add(8).cast::<*mut u8>() = ptr4.cast_mut();
+              },
+              Value::String(e) => {
+                *base.add(0).cast::<u8>() = (1i32) as u8;
+                let vec5 = (e.into_bytes()).into_boxed_slice();
+                let ptr5 = vec5.as_ptr().cast::<u8>();
+                let len5 = vec5.len();
+                ::core::mem::forget(vec5);
+                *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len5;
+                *base.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+              },
+              Value::Keyword(e) => {
+                *base.add(0).cast::<u8>() = (2i32) as u8;
+                let vec6 = (e.into_bytes()).into_boxed_slice();
+                let ptr6 = vec6.as_ptr().cast::<u8>();
+                let len6 = vec6.len();
+                ::core::mem::forget(vec6);
+                *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len6;
+                *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+              },
+              Value::Symbol(e) => {
+                *base.add(0).cast::<u8>() = (3i32) as u8;
+                let vec7 = (e.into_bytes()).into_boxed_slice();
+                let ptr7 = vec7.as_ptr().cast::<u8>();
+                let len7 = vec7.len();
+                ::core::mem::forget(vec7);
+                *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len7;
+                *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+              },
+              Value::List(e) => {
+                *base.add(0).cast::<u8>() = (4i32) as u8;
+                let vec8 = (e).into_boxed_slice();
+                let ptr8 = vec8.as_ptr().cast::<u8>();
+                let len8 = vec8.len();
+                ::core::mem::forget(vec8);
+                *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len8;
+                *base.add(8).cast::<*mut u8>() = ptr8.cast_mut();
+              },
+              Value::TaggedTuple(e) => {
+                *base.add(0).cast::<u8>() = (5i32) as u8;
+                let TaggedTuple{ tag:tag9, values:values9, } = e;
+                *base.add(8).cast::<i32>() = _rt::as_i32(tag9);
+                let vec10 = (values9).into_boxed_slice();
+                let ptr10 = vec10.as_ptr().cast::<u8>();
+                let len10 = vec10.len();
+                ::core::mem::forget(vec10);
+                *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len10;
+                *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr10.cast_mut();
+              },
+              Value::FunctionRef(e) => {
+                *base.add(0).cast::<u8>() = (6i32) as u8;
+                let vec11 = (e.into_bytes()).into_boxed_slice();
+                let ptr11 = vec11.as_ptr().cast::<u8>();
+                let len11 = vec11.len();
+                ::core::mem::forget(vec11);
+                *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len11;
+                *base.add(8).cast::<*mut u8>() = ptr11.cast_mut();
+              },
+              Value::Error(e) => {
+                *base.add(0).cast::<u8>() = (7i32) as u8;
+                let ErrorValue{ variety:variety12, message:message12, location:location12, details:details12, } = e;
+                let vec13 = (variety12.into_bytes()).into_boxed_slice();
+                let ptr13 = vec13.as_ptr().cast::<u8>();
+                let len13 = vec13.len();
+                ::core::mem::forget(vec13);
+                *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len13;
+                *base.add(8).cast::<*mut u8>() = ptr13.cast_mut();
+                match message12 {
+                  Some(e) => {
+                    *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>() = (1i32) as u8;
+                    let vec14 = (e.into_bytes()).into_boxed_slice();
+                    let ptr14 = vec14.as_ptr().cast::<u8>();
+                    let len14 = vec14.len();
+                    ::core::mem::forget(vec14);
+                    *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<usize>() = len14;
+                    *base.add(8+3*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr14.cast_mut();
+                  },
+                  None => {
+                    {
+                      *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>() = (0i32) as u8;
+                    }
+                  },
+                };match location12 {
+                  Some(e) => {
+                    *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>() = (1i32) as u8;
+                    let vec15 = (e.into_bytes()).into_boxed_slice();
+                    let ptr15 = vec15.as_ptr().cast::<u8>();
+                    let len15 = vec15.len();
+                    ::core::mem::forget(vec15);
+                    *base.add(8+7*::core::mem::size_of::<*const u8>()).cast::<usize>() = len15;
+                    *base.add(8+6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr15.cast_mut();
+                  },
+                  None => {
+                    {
+                      *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>() = (0i32) as u8;
+                    }
+                  },
+                };let vec16 = (details12).into_boxed_slice();
+                let ptr16 = vec16.as_ptr().cast::<u8>();
+                let len16 = vec16.len();
+                ::core::mem::forget(vec16);
+                *base.add(8+9*::core::mem::size_of::<*const u8>()).cast::<usize>() = len16;
+                *base.add(8+8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr16.cast_mut();
+              },
+              Value::Process(e) => {
+                *base.add(0).cast::<u8>() = (8i32) as u8;
+                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+              },
+            }
+          }
+        }
+        *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len17;
+        *ptr2.add(0).cast::<*mut u8>() = result17;
+        let vec18 = (results3).into_boxed_slice();
+        let ptr18 = vec18.as_ptr().cast::<u8>();
+        let len18 = vec18.len();
+        ::core::mem::forget(vec18);
+        *ptr2.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len18;
+        *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr18.cast_mut();
+        let vec19 = (spawned3).into_boxed_slice();
+        let ptr19 = vec19.as_ptr().cast::<u8>();
+        let len19 = vec19.len();
+        ::core::mem::forget(vec19);
+        *ptr2.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>() = len19;
+        *ptr2.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr19.cast_mut();
+        let vec21 = logs3;
+        let len21 = vec21.len();
+        let layout21 = _rt::alloc::Layout::from_size_align_unchecked(vec21.len() * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+        let result21 = if layout21.size() != 0 {
+          let ptr = _rt::alloc::alloc(layout21).cast::<u8>();
+          if ptr.is_null()
+          {
+            _rt::alloc::handle_alloc_error(layout21);
+          }
+          ptr
+        }else {
+          ::core::ptr::null_mut()
+        };
+        for (i, e) in vec21.into_iter().enumerate() {
+          let base = result21.add(i * (2*::core::mem::size_of::<*const u8>()));
+          {
+            let vec20 = (e.into_bytes()).into_boxed_slice();
+            let ptr20 = vec20.as_ptr().cast::<u8>();
+            let len20 = vec20.len();
+            ::core::mem::forget(vec20);
+            *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len20;
+            *base.add(0).cast::<*mut u8>() = ptr20.cast_mut();
+          }
+        }
+        *ptr2.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>() = len21;
+        *ptr2.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = result21;
+        let vec26 = diagnostics3;
+        let len26 = vec26.len();
+        let layout26 = _rt::alloc::Layout::from_size_align_unchecked(vec26.len() * (8+6*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+        let result26 = if layout26.size() != 0 {
+          let ptr = _rt::alloc::alloc(layout26).cast::<u8>();
+          if ptr.is_null()
+          {
+            _rt::alloc::handle_alloc_error(layout26);
+          }
+          ptr
+        }else {
+          ::core::ptr::null_mut()
+        };
+        for (i, e) in vec26.into_iter().enumerate() {
+          let base = result26.add(i * (8+6*::core::mem::size_of::<*const u8>()));
+          {
+            let Diagnostic{ message:message22, location:location22, line:line22, column:column22, snippet:snippet22, } = e;
+            let vec23 = (message22.into_bytes()).into_boxed_slice();
+            let ptr23 = vec23.as_ptr().cast::<u8>();
+            let len23 = vec23.len();
+            ::core::mem::forget(vec23);
+            *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len23;
+            *base.add(0).cast::<*mut u8>() = ptr23.cast_mut();
+            let vec24 = (location22.into_bytes()).into_boxed_slice();
+            let ptr24 = vec24.as_ptr().cast::<u8>();
+            let len24 = vec24.len();
+            ::core::mem::forget(vec24);
+            *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len24;
+            *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr24.cast_mut();
+            *base.add(4*::core::mem::size_of::<*const u8>()).cast::<i32>() = _rt::as_i32(line22);
+            *base.add(4+4*::core::mem::size_of::<*const u8>()).cast::<i32>() = _rt::as_i32(column22);
+            let vec25 = (snippet22.into_bytes()).into_boxed_slice();
+            let ptr25 = vec25.as_ptr().cast::<u8>();
+            let len25 = vec25.len();
+            ::core::mem::forget(vec25);
+            *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<usize>() = len25;
+            *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr25.cast_mut();
+          }
+        }
+        *ptr2.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>() = len26;
+        *ptr2.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = result26;
+        ptr2
+      }
+      #[doc(hidden)]
+      #[allow(non_snake_case)]
+      pub unsafe fn __post_return_evaluate<T: Guest>(arg0: *mut u8,) {
+        let l0 = *arg0.add(0).cast::<*mut u8>();
+        let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+        let base30 = l0;
+        let len30 = l1;
+        for i in 0..len30 {
+          let base = base30.add(i * (8+10*::core::mem::size_of::<*const u8>()));
+          {
+            let l2 = i32::from(*base.add(0).cast::<u8>());
+            match l2 {
+              0 => {
+                let l3 = *base.add(8).cast::<*mut u8>();
+                let l4 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                _rt::cabi_dealloc(l3, l4, 1);
+              },
+              1 => {
+                let l5 = *base.add(8).cast::<*mut u8>();
+                let l6 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                _rt::cabi_dealloc(l5, l6, 1);
+              },
+              2 => {
+                let l7 = *base.add(8).cast::<*mut u8>();
+                let l8 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                _rt::cabi_dealloc(l7, l8, 1);
+              },
+              3 => {
+                let l9 = *base.add(8).cast::<*mut u8>();
+                let l10 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                _rt::cabi_dealloc(l9, l10, 1);
+              },
+              4 => {
+                let l11 = *base.add(8).cast::<*mut u8>();
+                let l12 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let base13 = l11;
+                let len13 = l12;
+                _rt::cabi_dealloc(base13, len13 * 4, 4);
+              },
+              5 => {
+                let l14 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l15 = *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let base16 = l14;
+                let len16 = l15;
+                _rt::cabi_dealloc(base16, len16 * 4, 4);
+              },
+              6 => {
+                let l17 = *base.add(8).cast::<*mut u8>();
+                let l18 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                _rt::cabi_dealloc(l17, l18, 1);
+              },
+              7 => {
+                let l19 = *base.add(8).cast::<*mut u8>();
+                let l20 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                _rt::cabi_dealloc(l19, l20, 1);
+                let l21 = i32::from(*base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                match l21 {
+                  0 => (),
+                  _ => {
+                    let l22 = *base.add(8+3*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                    let l23 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    _rt::cabi_dealloc(l22, l23, 1);
+                  },
+                }
+                let l24 = i32::from(*base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                match l24 {
+                  0 => (),
+                  _ => {
+                    let l25 = *base.add(8+6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                    let l26 = *base.add(8+7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    _rt::cabi_dealloc(l25, l26, 1);
+                  },
+                }
+                let l27 = *base.add(8+8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l28 = *base.add(8+9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let base29 = l27;
+                let len29 = l28;
+                _rt::cabi_dealloc(base29, len29 * 4, 4);
+              },
+              _ => (),
+            }
+          }
+        }
+        _rt::cabi_dealloc(base30, len30 * (8+10*::core::mem::size_of::<*const u8>()), 8);
+        let l31 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+        let l32 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+        let base33 = l31;
+        let len33 = l32;
+        _rt::cabi_dealloc(base33, len33 * 4, 4);
+        let l34 = *arg0.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+        let l35 = *arg0.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+        let base36 = l34;
+        let len36 = l35;
+        _rt::cabi_dealloc(base36, len36 * 4, 4);
+        let l37 = *arg0.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+        let l38 = *arg0.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+        let base41 = l37;
+        let len41 = l38;
+        for i in 0..len41 {
+          let base = base41.add(i * (2*::core::mem::size_of::<*const u8>()));
+          {
+            let l39 = *base.add(0).cast::<*mut u8>();
+            let l40 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+            _rt::cabi_dealloc(l39, l40, 1);
+          }
+        }
+        _rt::cabi_dealloc(base41, len41 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+        let l42 = *arg0.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+        let l43 = *arg0.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+        let base50 = l42;
+        let len50 = l43;
+        for i in 0..len50 {
+          let base = base50.add(i * (8+6*::core::mem::size_of::<*const u8>()));
+          {
+            let l44 = *base.add(0).cast::<*mut u8>();
+            let l45 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+            _rt::cabi_dealloc(l44, l45, 1);
+            let l46 = *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+            let l47 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+            _rt::cabi_dealloc(l46, l47, 1);
+            let l48 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+            let l49 = *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+            _rt::cabi_dealloc(l48, l49, 1);
+          }
+        }
+        _rt::cabi_dealloc(base50, len50 * (8+6*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+      }
+      #[doc(hidden)]
+      #[allow(non_snake_case)]
+      pub unsafe fn _export_evaluate_with_limits_cabi<T: Guest>(arg0: *mut u8,arg1: usize,arg2: i32,arg3: i64,arg4: i32,arg5: i64,) -> *mut u8 {#[cfg(target_arch="wasm32")]
+      _rt::run_ctors_once();let len0 = arg1;
+      let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+      let result1 = T::evaluate_with_limits(_rt::string_lift(bytes0), Limits{
+        max_steps: match arg2 {
+          0 => None,
+          1 => {
+            let e = arg3 as u64;
+            Some(e)
+          }
+          _ => _rt::invalid_enum_discriminant(),
+        },
+        time_limit_ms: match arg4 {
+          0 => None,
+          1 => {
+            let e = arg5 as u64;
+            Some(e)
+          }
+          _ => _rt::invalid_enum_discriminant(),
+        },
+      });
+      let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+      let Evaluation{ values:values3, results:results3, spawned:spawned3, logs:logs3, diagnostics:diagnostics3, } = result1;
+      let vec17 = values3;
+      let len17 = vec17.len();
+      let layout17 = _rt::alloc::Layout::from_size_align_unchecked(vec17.len() * (8+10*::core::mem::size_of::<*const u8>()), 8);
+      let result17 = if layout17.size() != 0 {
+        let ptr = _rt::alloc::alloc(layout17).cast::<u8>();
+        if ptr.is_null()
+        {
+          _rt::alloc::handle_alloc_error(layout17);
+        }
+        ptr
+      }else {
+        ::core::ptr::null_mut()
+      };
+      for (i, e) in vec17.into_iter().enumerate() {
+        let base = result17.add(i * (8+10*::core::mem::size_of::<*const u8>()));
+        {
+          match e {
+            Value::Number(e) => {
+              *base.add(0).cast::<u8>() = (0i32) as u8;
+              let vec4 = (e.into_bytes()).into_boxed_slice();
+              let ptr4 = vec4.as_ptr().cast::<u8>();
+              let len4 = vec4.len();
+              ::core::mem::forget(vec4);
+              *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+              *base.add(8).cast::<*mut u8>() = ptr4.cast_mut();
+            },
+            Value::String(e) => {
+              *base.add(0).cast::<u8>() = (1i32) as u8;
+              let vec5 = (e.into_bytes()).into_boxed_slice();
+              let ptr5 = vec5.as_ptr().cast::<u8>();
+              let len5 = vec5.len();
+              ::core::mem::forget(vec5);
+              *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len5;
+              *base.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+            },
+            Value::Keyword(e) => {
+              *base.add(0).cast::<u8>() = (2i32) as u8;
+              let vec6 = (e.into_bytes()).into_boxed_slice();
+              let ptr6 = vec6.as_ptr().cast::<u8>();
+              let len6 = vec6.len();
+              ::core::mem::forget(vec6);
+              *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len6;
+              *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+            },
+            Value::Symbol(e) => {
+              *base.add(0).cast::<u8>() = (3i32) as u8;
+              let vec7 = (e.into_bytes()).into_boxed_slice();
+              let ptr7 = vec7.as_ptr().cast::<u8>();
+              let len7 = vec7.len();
+              ::core::mem::forget(vec7);
+              *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len7;
+              *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+            },
+            Value::List(e) => {
+              *base.add(0).cast::<u8>() = (4i32) as u8;
+              let vec8 = (e).into_boxed_slice();
+              let ptr8 = vec8.as_ptr().cast::<u8>();
+              let len8 = vec8.len();
+              ::core::mem::forget(vec8);
+              *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len8;
+              *base.add(8).cast::<*mut u8>() = ptr8.cast_mut();
+            },
+            Value::TaggedTuple(e) => {
+              *base.add(0).cast::<u8>() = (5i32) as u8;
+              let TaggedTuple{ tag:tag9, values:values9, } = e;
+              *base.add(8).cast::<i32>() = _rt::as_i32(tag9);
+              let vec10 = (values9).into_boxed_slice();
+              let ptr10 = vec10.as_ptr().cast::<u8>();
+              let len10 = vec10.len();
+              ::core::mem::forget(vec10);
+              *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len10;
+              *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr10.cast_mut();
+            },
+            Value::FunctionRef(e) => {
+              *base.add(0).cast::<u8>() = (6i32) as u8;
+              let vec11 = (e.into_bytes()).into_boxed_slice();
+              let ptr11 = vec11.as_ptr().cast::<u8>();
+              let len11 = vec11.len();
+              ::core::mem::forget(vec11);
+              *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len11;
+              *base.add(8).cast::<*mut u8>() = ptr11.cast_mut();
+            },
+            Value::Error(e) => {
+              *base.add(0).cast::<u8>() = (7i32) as u8;
+              let ErrorValue{ variety:variety12, message:message12, location:location12, details:details12, } = e;
+              let vec13 = (variety12.into_bytes()).into_boxed_slice();
+              let ptr13 = vec13.as_ptr().cast::<u8>();
+              let len13 = vec13.len();
+              ::core::mem::forget(vec13);
+              *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len13;
+              *base.add(8).cast::<*mut u8>() = ptr13.cast_mut();
+              match message12 {
+                Some(e) => {
+                  *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>() = (1i32) as u8;
+                  let vec14 = (e.into_bytes()).into_boxed_slice();
+                  let ptr14 = vec14.as_ptr().cast::<u8>();
+                  let len14 = vec14.len();
+                  ::core::mem::forget(vec14);
+                  *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<usize>() = len14;
+                  *base.add(8+3*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr14.cast_mut();
+                },
+                None => {
+                  {
+                    *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>() = (0i32) as u8;
+                  }
+                },
+              };match location12 {
+                Some(e) => {
+                  *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>() = (1i32) as u8;
+                  let vec15 = (e.into_bytes()).into_boxed_slice();
+                  let ptr15 = vec15.as_ptr().cast::<u8>();
+                  let len15 = vec15.len();
+                  ::core::mem::forget(vec15);
+                  *base.add(8+7*::core::mem::size_of::<*const u8>()).cast::<usize>() = len15;
+                  *base.add(8+6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr15.cast_mut();
+                },
+                None => {
+                  {
+                    *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>() = (0i32) as u8;
+                  }
+                },
+              };let vec16 = (details12).into_boxed_slice();
+              let ptr16 = vec16.as_ptr().cast::<u8>();
+              let len16 = vec16.len();
+              ::core::mem::forget(vec16);
+              *base.add(8+9*::core::mem::size_of::<*const u8>()).cast::<usize>() = len16;
+              *base.add(8+8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr16.cast_mut();
+            },
+            Value::Process(e) => {
+              *base.add(0).cast::<u8>() = (8i32) as u8;
+              *base.add(8).cast::<i64>() = _rt::as_i64(e);
+            },
+          }
+        }
+      }
+      *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len17;
+      *ptr2.add(0).cast::<*mut u8>() = result17;
+      let vec18 = (results3).into_boxed_slice();
+      let ptr18 = vec18.as_ptr().cast::<u8>();
+      let len18 = vec18.len();
+      ::core::mem::forget(vec18);
+      *ptr2.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len18;
+      *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr18.cast_mut();
+      let vec19 = (spawned3).into_boxed_slice();
+      let ptr19 = vec19.as_ptr().cast::<u8>();
+      let len19 = vec19.len();
+      ::core::mem::forget(vec19);
+      *ptr2.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>() = len19;
+      *ptr2.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr19.cast_mut();
+      let vec21 = logs3;
+      let len21 = vec21.len();
+      let layout21 = _rt::alloc::Layout::from_size_align_unchecked(vec21.len() * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+      let result21 = if layout21.size() != 0 {
+        let ptr = _rt::alloc::alloc(layout21).cast::<u8>();
+        if ptr.is_null()
+        {
+          _rt::alloc::handle_alloc_error(layout21);
+        }
+        ptr
+      }else {
+        ::core::ptr::null_mut()
+      };
+      for (i, e) in vec21.into_iter().enumerate() {
+        let base = result21.add(i * (2*::core::mem::size_of::<*const u8>()));
+        {
+          let vec20 = (e.into_bytes()).into_boxed_slice();
+          let ptr20 = vec20.as_ptr().cast::<u8>();
+          let len20 = vec20.len();
+          ::core::mem::forget(vec20);
+          *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len20;
+          *base.add(0).cast::<*mut u8>() = ptr20.cast_mut();
+        }
+      }
+      *ptr2.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>() = len21;
+      *ptr2.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = result21;
+      let vec26 = diagnostics3;
+      let len26 = vec26.len();
+      let layout26 = _rt::alloc::Layout::from_size_align_unchecked(vec26.len() * (8+6*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+      let result26 = if layout26.size() != 0 {
+        let ptr = _rt::alloc::alloc(layout26).cast::<u8>();
+        if ptr.is_null()
+        {
+          _rt::alloc::handle_alloc_error(layout26);
+        }
+        ptr
+      }else {
+        ::core::ptr::null_mut()
+      };
+      for (i, e) in vec26.into_iter().enumerate() {
+        let base = result26.add(i * (8+6*::core::mem::size_of::<*const u8>()));
+        {
+          let Diagnostic{ message:message22, location:location22, line:line22, column:column22, snippet:snippet22, } = e;
+          let vec23 = (message22.into_bytes()).into_boxed_slice();
+          let ptr23 = vec23.as_ptr().cast::<u8>();
+          let len23 = vec23.len();
+          ::core::mem::forget(vec23);
+          *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len23;
+          *base.add(0).cast::<*mut u8>() = ptr23.cast_mut();
+          let vec24 = (location22.into_bytes()).into_boxed_slice();
+          let ptr24 = vec24.as_ptr().cast::<u8>();
+          let len24 = vec24.len();
+          ::core::mem::forget(vec24);
+          *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len24;
+          *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr24.cast_mut();
+          *base.add(4*::core::mem::size_of::<*const u8>()).cast::<i32>() = _rt::as_i32(line22);
+          *base.add(4+4*::core::mem::size_of::<*const u8>()).cast::<i32>() = _rt::as_i32(column22);
+          let vec25 = (snippet22.into_bytes()).into_boxed_slice();
+          let ptr25 = vec25.as_ptr().cast::<u8>();
+          let len25 = vec25.len();
+          ::core::mem::forget(vec25);
+          *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<usize>() = len25;
+          *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr25.cast_mut();
+        }
+      }
+      *ptr2.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>() = len26;
+      *ptr2.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = result26;
+      ptr2
+    }
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    pub unsafe fn __post_return_evaluate_with_limits<T: Guest>(arg0: *mut u8,) {
+      let l0 = *arg0.add(0).cast::<*mut u8>();
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+      let base30 = l0;
+      let len30 = l1;
+      for i in 0..len30 {
+        let base = base30.add(i * (8+10*::core::mem::size_of::<*const u8>()));
+        {
+          let l2 = i32::from(*base.add(0).cast::<u8>());
+          match l2 {
+            0 => {
+              let l3 = *base.add(8).cast::<*mut u8>();
+              let l4 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              _rt::cabi_dealloc(l3, l4, 1);
+            },
+            1 => {
+              let l5 = *base.add(8).cast::<*mut u8>();
+              let l6 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              _rt::cabi_dealloc(l5, l6, 1);
+            },
+            2 => {
+              let l7 = *base.add(8).cast::<*mut u8>();
+              let l8 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              _rt::cabi_dealloc(l7, l8, 1);
+            },
+            3 => {
+              let l9 = *base.add(8).cast::<*mut u8>();
+              let l10 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              _rt::cabi_dealloc(l9, l10, 1);
+            },
+            4 => {
+              let l11 = *base.add(8).cast::<*mut u8>();
+              let l12 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let base13 = l11;
+              let len13 = l12;
+              _rt::cabi_dealloc(base13, len13 * 4, 4);
+            },
+            5 => {
+              let l14 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l15 = *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let base16 = l14;
+              let len16 = l15;
+              _rt::cabi_dealloc(base16, len16 * 4, 4);
+            },
+            6 => {
+              let l17 = *base.add(8).cast::<*mut u8>();
+              let l18 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              _rt::cabi_dealloc(l17, l18, 1);
+            },
+            7 => {
+              let l19 = *base.add(8).cast::<*mut u8>();
+              let l20 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              _rt::cabi_dealloc(l19, l20, 1);
+              let l21 = i32::from(*base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              match l21 {
+                0 => (),
+                _ => {
+                  let l22 = *base.add(8+3*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l23 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  _rt::cabi_dealloc(l22, l23, 1);
+                },
+              }
+              let l24 = i32::from(*base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              match l24 {
+                0 => (),
+                _ => {
+                  let l25 = *base.add(8+6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l26 = *base.add(8+7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  _rt::cabi_dealloc(l25, l26, 1);
+                },
+              }
+              let l27 = *base.add(8+8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l28 = *base.add(8+9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let base29 = l27;
+              let len29 = l28;
+              _rt::cabi_dealloc(base29, len29 * 4, 4);
+            },
+            _ => (),
+          }
+        }
+      }
+      _rt::cabi_dealloc(base30, len30 * (8+10*::core::mem::size_of::<*const u8>()), 8);
+      let l31 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l32 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      let base33 = l31;
+      let len33 = l32;
+      _rt::cabi_dealloc(base33, len33 * 4, 4);
+      let l34 = *arg0.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l35 = *arg0.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      let base36 = l34;
+      let len36 = l35;
+      _rt::cabi_dealloc(base36, len36 * 4, 4);
+      let l37 = *arg0.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l38 = *arg0.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      let base41 = l37;
+      let len41 = l38;
+      for i in 0..len41 {
+        let base = base41.add(i * (2*::core::mem::size_of::<*const u8>()));
+        {
+          let l39 = *base.add(0).cast::<*mut u8>();
+          let l40 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l39, l40, 1);
+        }
+      }
+      _rt::cabi_dealloc(base41, len41 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+      let l42 = *arg0.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l43 = *arg0.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      let base50 = l42;
+      let len50 = l43;
+      for i in 0..len50 {
+        let base = base50.add(i * (8+6*::core::mem::size_of::<*const u8>()));
+        {
+          let l44 = *base.add(0).cast::<*mut u8>();
+          let l45 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l44, l45, 1);
+          let l46 = *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l47 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l46, l47, 1);
+          let l48 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l49 = *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l48, l49, 1);
+        }
+      }
+      _rt::cabi_dealloc(base50, len50 * (8+6*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+    }
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    pub unsafe fn _export_constructor_session_cabi<T: GuestSession>() -> i32 {#[cfg(target_arch="wasm32")]
+    _rt::run_ctors_once();let result0 = Session::new(T::new());
+    (result0).take_handle() as i32
+  }
+  #[doc(hidden)]
+  #[allow(non_snake_case)]
+  pub unsafe fn _export_method_session_evaluate_cabi<T: GuestSession>(arg0: *mut u8,arg1: *mut u8,arg2: usize,) -> *mut u8 {#[cfg(target_arch="wasm32")]
+  _rt::run_ctors_once();let len0 = arg2;
+  let bytes0 = _rt::Vec::from_raw_parts(arg1.cast(), len0, len0);
+  let result1 = T::evaluate(unsafe { SessionBorrow::lift(arg0 as u32 as usize) }.get(), _rt::string_lift(bytes0));
+  let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+  let Evaluation{ values:values3, results:results3, spawned:spawned3, logs:logs3, diagnostics:diagnostics3, } = result1;
+  let vec17 = values3;
+  let len17 = vec17.len();
+  let layout17 = _rt::alloc::Layout::from_size_align_unchecked(vec17.len() * (8+10*::core::mem::size_of::<*const u8>()), 8);
+  let result17 = if layout17.size() != 0 {
+    let ptr = _rt::alloc::alloc(layout17).cast::<u8>();
+    if ptr.is_null()
+    {
+      _rt::alloc::handle_alloc_error(layout17);
+    }
+    ptr
+  }else {
+    ::core::ptr::null_mut()
+  };
+  for (i, e) in vec17.into_iter().enumerate() {
+    let base = result17.add(i * (8+10*::core::mem::size_of::<*const u8>()));
+    {
+      match e {
+        Value::Number(e) => {
+          *base.add(0).cast::<u8>() = (0i32) as u8;
+          let vec4 = (e.into_bytes()).into_boxed_slice();
+          let ptr4 = vec4.as_ptr().cast::<u8>();
+          let len4 = vec4.len();
+          ::core::mem::forget(vec4);
+          *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+          *base.add(8).cast::<*mut u8>() = ptr4.cast_mut();
+        },
+        Value::String(e) => {
+          *base.add(0).cast::<u8>() = (1i32) as u8;
+          let vec5 = (e.into_bytes()).into_boxed_slice();
+          let ptr5 = vec5.as_ptr().cast::<u8>();
+          let len5 = vec5.len();
+          ::core::mem::forget(vec5);
+          *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len5;
+          *base.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+        },
+        Value::Keyword(e) => {
+          *base.add(0).cast::<u8>() = (2i32) as u8;
+          let vec6 = (e.into_bytes()).into_boxed_slice();
+          let ptr6 = vec6.as_ptr().cast::<u8>();
+          let len6 = vec6.len();
+          ::core::mem::forget(vec6);
+          *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len6;
+          *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+        },
+        Value::Symbol(e) => {
+          *base.add(0).cast::<u8>() = (3i32) as u8;
+          let vec7 = (e.into_bytes()).into_boxed_slice();
+          let ptr7 = vec7.as_ptr().cast::<u8>();
+          let len7 = vec7.len();
+          ::core::mem::forget(vec7);
+          *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len7;
+          *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+        },
+        Value::List(e) => {
+          *base.add(0).cast::<u8>() = (4i32) as u8;
+          let vec8 = (e).into_boxed_slice();
+          let ptr8 = vec8.as_ptr().cast::<u8>();
+          let len8 = vec8.len();
+          ::core::mem::forget(vec8);
+          *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len8;
+          *base.add(8).cast::<*mut u8>() = ptr8.cast_mut();
+        },
+        Value::TaggedTuple(e) => {
+          *base.add(0).cast::<u8>() = (5i32) as u8;
+          let TaggedTuple{ tag:tag9, values:values9, } = e;
+          *base.add(8).cast::<i32>() = _rt::as_i32(tag9);
+          let vec10 = (values9).into_boxed_slice();
+          let ptr10 = vec10.as_ptr().cast::<u8>();
+          let len10 = vec10.len();
+          ::core::mem::forget(vec10);
+          *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len10;
+          *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr10.cast_mut();
+        },
+        Value::FunctionRef(e) => {
+          *base.add(0).cast::<u8>() = (6i32) as u8;
+          let vec11 = (e.into_bytes()).into_boxed_slice();
+          let ptr11 = vec11.as_ptr().cast::<u8>();
+          let len11 = vec11.len();
+          ::core::mem::forget(vec11);
+          *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len11;
+          *base.add(8).cast::<*mut u8>() = ptr11.cast_mut();
+        },
+        Value::Error(e) => {
+          *base.add(0).cast::<u8>() = (7i32) as u8;
+          let ErrorValue{ variety:variety12, message:message12, location:location12, details:details12, } = e;
+          let vec13 = (variety12.into_bytes()).into_boxed_slice();
+          let ptr13 = vec13.as_ptr().cast::<u8>();
+          let len13 = vec13.len();
+          ::core::mem::forget(vec13);
+          *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len13;
+          *base.add(8).cast::<*mut u8>() = ptr13.cast_mut();
+          match message12 {
+            Some(e) => {
+              *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>() = (1i32) as u8;
+              let vec14 = (e.into_bytes()).into_boxed_slice();
+              let ptr14 = vec14.as_ptr().cast::<u8>();
+              let len14 = vec14.len();
+              ::core::mem::forget(vec14);
+              *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<usize>() = len14;
+              *base.add(8+3*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr14.cast_mut();
+            },
+            None => {
+              {
+                *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>() = (0i32) as u8;
+              }
+            },
+          };match location12 {
+            Some(e) => {
+              *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>() = (1i32) as u8;
+              let vec15 = (e.into_bytes()).into_boxed_slice();
+              let ptr15 = vec15.as_ptr().cast::<u8>();
+              let len15 = vec15.len();
+              ::core::mem::forget(vec15);
+              *base.add(8+7*::core::mem::size_of::<*const u8>()).cast::<usize>() = len15;
+              *base.add(8+6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr15.cast_mut();
+            },
+            None => {
+              {
+                *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>() = (0i32) as u8;
+              }
+            },
+          };let vec16 = (details12).into_boxed_slice();
+          let ptr16 = vec16.as_ptr().cast::<u8>();
+          let len16 = vec16.len();
+          ::core::mem::forget(vec16);
+          *base.add(8+9*::core::mem::size_of::<*const u8>()).cast::<usize>() = len16;
+          *base.add(8+8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr16.cast_mut();
+        },
+        Value::Process(e) => {
+          *base.add(0).cast::<u8>() = (8i32) as u8;
+          *base.add(8).cast::<i64>() = _rt::as_i64(e);
+        },
+      }
+    }
+  }
+  *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len17;
+  *ptr2.add(0).cast::<*mut u8>() = result17;
+  let vec18 = (results3).into_boxed_slice();
+  let ptr18 = vec18.as_ptr().cast::<u8>();
+  let len18 = vec18.len();
+  ::core::mem::forget(vec18);
+  *ptr2.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len18;
+  *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr18.cast_mut();
+  let vec19 = (spawned3).into_boxed_slice();
+  let ptr19 = vec19.as_ptr().cast::<u8>();
+  let len19 = vec19.len();
+  ::core::mem::forget(vec19);
+  *ptr2.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>() = len19;
+  *ptr2.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr19.cast_mut();
+  let vec21 = logs3;
+  let len21 = vec21.len();
+  let layout21 = _rt::alloc::Layout::from_size_align_unchecked(vec21.len() * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+  let result21 = if layout21.size() != 0 {
+    let ptr = _rt::alloc::alloc(layout21).cast::<u8>();
+    if ptr.is_null()
+    {
+      _rt::alloc::handle_alloc_error(layout21);
+    }
+    ptr
+  }else {
+    ::core::ptr::null_mut()
+  };
+  for (i, e) in vec21.into_iter().enumerate() {
+    let base = result21.add(i * (2*::core::mem::size_of::<*const u8>()));
+    {
+      let vec20 = (e.into_bytes()).into_boxed_slice();
+      let ptr20 = vec20.as_ptr().cast::<u8>();
+      let len20 = vec20.len();
+      ::core::mem::forget(vec20);
+      *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len20;
+      *base.add(0).cast::<*mut u8>() = ptr20.cast_mut();
+    }
+  }
+  *ptr2.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>() = len21;
+  *ptr2.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = result21;
+  let vec26 = diagnostics3;
+  let len26 = vec26.len();
+  let layout26 = _rt::alloc::Layout::from_size_align_unchecked(vec26.len() * (8+6*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+  let result26 = if layout26.size() != 0 {
+    let ptr = _rt::alloc::alloc(layout26).cast::<u8>();
+    if ptr.is_null()
+    {
+      _rt::alloc::handle_alloc_error(layout26);
+    }
+    ptr
+  }else {
+    ::core::ptr::null_mut()
+  };
+  for (i, e) in vec26.into_iter().enumerate() {
+    let base = result26.add(i * (8+6*::core::mem::size_of::<*const u8>()));
+    {
+      let Diagnostic{ message:message22, location:location22, line:line22, column:column22, snippet:snippet22, } = e;
+      let vec23 = (message22.into_bytes()).into_boxed_slice();
+      let ptr23 = vec23.as_ptr().cast::<u8>();
+      let len23 = vec23.len();
+      ::core::mem::forget(vec23);
+      *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len23;
+      *base.add(0).cast::<*mut u8>() = ptr23.cast_mut();
+      let vec24 = (location22.into_bytes()).into_boxed_slice();
+      let ptr24 = vec24.as_ptr().cast::<u8>();
+      let len24 = vec24.len();
+      ::core::mem::forget(vec24);
+      *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len24;
+      *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr24.cast_mut();
+      *base.add(4*::core::mem::size_of::<*const u8>()).cast::<i32>() = _rt::as_i32(line22);
+      *base.add(4+4*::core::mem::size_of::<*const u8>()).cast::<i32>() = _rt::as_i32(column22);
+      let vec25 = (snippet22.into_bytes()).into_boxed_slice();
+      let ptr25 = vec25.as_ptr().cast::<u8>();
+      let len25 = vec25.len();
+      ::core::mem::forget(vec25);
+      *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<usize>() = len25;
+      *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr25.cast_mut();
+    }
+  }
+  *ptr2.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>() = len26;
+  *ptr2.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = result26;
+  ptr2
+}
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_method_session_evaluate<T: GuestSession>(arg0: *mut u8,) {
+  let l0 = *arg0.add(0).cast::<*mut u8>();
+  let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+  let base30 = l0;
+  let len30 = l1;
+  for i in 0..len30 {
+    let base = base30.add(i * (8+10*::core::mem::size_of::<*const u8>()));
+    {
+      let l2 = i32::from(*base.add(0).cast::<u8>());
+      match l2 {
+        0 => {
+          let l3 = *base.add(8).cast::<*mut u8>();
+          let l4 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l3, l4, 1);
+        },
+        1 => {
+          let l5 = *base.add(8).cast::<*mut u8>();
+          let l6 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l5, l6, 1);
+        },
+        2 => {
+          let l7 = *base.add(8).cast::<*mut u8>();
+          let l8 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l7, l8, 1);
+        },
+        3 => {
+          let l9 = *base.add(8).cast::<*mut u8>();
+          let l10 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l9, l10, 1);
+        },
+        4 => {
+          let l11 = *base.add(8).cast::<*mut u8>();
+          let l12 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base13 = l11;
+          let len13 = l12;
+          _rt::cabi_dealloc(base13, len13 * 4, 4);
+        },
+        5 => {
+          let l14 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l15 = *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base16 = l14;
+          let len16 = l15;
+          _rt::cabi_dealloc(base16, len16 * 4, 4);
+        },
+        6 => {
+          let l17 = *base.add(8).cast::<*mut u8>();
+          let l18 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l17, l18, 1);
+        },
+        7 => {
+          let l19 = *base.add(8).cast::<*mut u8>();
+          let l20 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l19, l20, 1);
+          let l21 = i32::from(*base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+          match l21 {
+            0 => (),
+            _ => {
+              let l22 = *base.add(8+3*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l23 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              _rt::cabi_dealloc(l22, l23, 1);
+            },
+          }
+          let l24 = i32::from(*base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>());
+          match l24 {
+            0 => (),
+            _ => {
+              let l25 = *base.add(8+6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l26 = *base.add(8+7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              _rt::cabi_dealloc(l25, l26, 1);
+            },
+          }
+          let l27 = *base.add(8+8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l28 = *base.add(8+9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base29 = l27;
+          let len29 = l28;
+          _rt::cabi_dealloc(base29, len29 * 4, 4);
+        },
+        _ => (),
+      }
+    }
+  }
+  _rt::cabi_dealloc(base30, len30 * (8+10*::core::mem::size_of::<*const u8>()), 8);
+  let l31 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+  let l32 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+  let base33 = l31;
+  let len33 = l32;
+  _rt::cabi_dealloc(base33, len33 * 4, 4);
+  let l34 = *arg0.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+  let l35 = *arg0.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+  let base36 = l34;
+  let len36 = l35;
+  _rt::cabi_dealloc(base36, len36 * 4, 4);
+  let l37 = *arg0.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+  let l38 = *arg0.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+  let base41 = l37;
+  let len41 = l38;
+  for i in 0..len41 {
+    let base = base41.add(i * (2*::core::mem::size_of::<*const u8>()));
+    {
+      let l39 = *base.add(0).cast::<*mut u8>();
+      let l40 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l39, l40, 1);
+    }
+  }
+  _rt::cabi_dealloc(base41, len41 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+  let l42 = *arg0.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+  let l43 = *arg0.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+  let base50 = l42;
+  let len50 = l43;
+  for i in 0..len50 {
+    let base = base50.add(i * (8+6*::core::mem::size_of::<*const u8>()));
+    {
+      let l44 = *base.add(0).cast::<*mut u8>();
+      let l45 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l44, l45, 1);
+      let l46 = *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l47 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l46, l47, 1);
+      let l48 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l49 = *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l48, l49, 1);
+    }
+  }
+  _rt::cabi_dealloc(base50, len50 * (8+6*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+}
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn _export_method_session_evaluate_with_limits_cabi<T: GuestSession>(arg0: *mut u8,arg1: *mut u8,arg2: usize,arg3: i32,arg4: i64,arg5: i32,arg6: i64,) -> *mut u8 {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let len0 = arg2;
+let bytes0 = _rt::Vec::from_raw_parts(arg1.cast(), len0, len0);
+let result1 = T::evaluate_with_limits(unsafe { SessionBorrow::lift(arg0 as u32 as usize) }.get(), _rt::string_lift(bytes0), Limits{
+  max_steps: match arg3 {
+    0 => None,
+    1 => {
+      let e = arg4 as u64;
+      Some(e)
+    }
+    _ => _rt::invalid_enum_discriminant(),
+  },
+  time_limit_ms: match arg5 {
+    0 => None,
+    1 => {
+      let e = arg6 as u64;
+      Some(e)
+    }
+    _ => _rt::invalid_enum_discriminant(),
+  },
+});
+let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+let Evaluation{ values:values3, results:results3, spawned:spawned3, logs:logs3, diagnostics:diagnostics3, } = result1;
+let vec17 = values3;
+let len17 = vec17.len();
+let layout17 = _rt::alloc::Layout::from_size_align_unchecked(vec17.len() * (8+10*::core::mem::size_of::<*const u8>()), 8);
+let result17 = if layout17.size() != 0 {
+  let ptr = _rt::alloc::alloc(layout17).cast::<u8>();
+  if ptr.is_null()
+  {
+    _rt::alloc::handle_alloc_error(layout17);
+  }
+  ptr
+}else {
+  ::core::ptr::null_mut()
+};
+for (i, e) in vec17.into_iter().enumerate() {
+  let base = result17.add(i * (8+10*::core::mem::size_of::<*const u8>()));
+  {
+    match e {
+      Value::Number(e) => {
+        *base.add(0).cast::<u8>() = (0i32) as u8;
+        let vec4 = (e.into_bytes()).into_boxed_slice();
+        let ptr4 = vec4.as_ptr().cast::<u8>();
+        let len4 = vec4.len();
+        ::core::mem::forget(vec4);
+        *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+        *base.add(8).cast::<*mut u8>() = ptr4.cast_mut();
+      },
+      Value::String(e) => {
+        *base.add(0).cast::<u8>() = (1i32) as u8;
+        let vec5 = (e.into_bytes()).into_boxed_slice();
+        let ptr5 = vec5.as_ptr().cast::<u8>();
+        let len5 = vec5.len();
+        ::core::mem::forget(vec5);
+        *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len5;
+        *base.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+      },
+      Value::Keyword(e) => {
+        *base.add(0).cast::<u8>() = (2i32) as u8;
+        let vec6 = (e.into_bytes()).into_boxed_slice();
+        let ptr6 = vec6.as_ptr().cast::<u8>();
+        let len6 = vec6.len();
+        ::core::mem::forget(vec6);
+        *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len6;
+        *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+      },
+      Value::Symbol(e) => {
+        *base.add(0).cast::<u8>() = (3i32) as u8;
+        let vec7 = (e.into_bytes()).into_boxed_slice();
+        let ptr7 = vec7.as_ptr().cast::<u8>();
+        let len7 = vec7.len();
+        ::core::mem::forget(vec7);
+        *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len7;
+        *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+      },
+      Value::List(e) => {
+        *base.add(0).cast::<u8>() = (4i32) as u8;
+        let vec8 = (e).into_boxed_slice();
+        let ptr8 = vec8.as_ptr().cast::<u8>();
+        let len8 = vec8.len();
+        ::core::mem::forget(vec8);
+        *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len8;
+        *base.add(8).cast::<*mut u8>() = ptr8.cast_mut();
+      },
+      Value::TaggedTuple(e) => {
+        *base.add(0).cast::<u8>() = (5i32) as u8;
+        let TaggedTuple{ tag:tag9, values:values9, } = e;
+        *base.add(8).cast::<i32>() = _rt::as_i32(tag9);
+        let vec10 = (values9).into_boxed_slice();
+        let ptr10 = vec10.as_ptr().cast::<u8>();
+        let len10 = vec10.len();
+        ::core::mem::forget(vec10);
+        *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len10;
+        *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr10.cast_mut();
+      },
+      Value::FunctionRef(e) => {
+        *base.add(0).cast::<u8>() = (6i32) as u8;
+        let vec11 = (e.into_bytes()).into_boxed_slice();
+        let ptr11 = vec11.as_ptr().cast::<u8>();
+        let len11 = vec11.len();
+        ::core::mem::forget(vec11);
+        *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len11;
+        *base.add(8).cast::<*mut u8>() = ptr11.cast_mut();
+      },
+      Value::Error(e) => {
+        *base.add(0).cast::<u8>() = (7i32) as u8;
+        let ErrorValue{ variety:variety12, message:message12, location:location12, details:details12, } = e;
+        let vec13 = (variety12.into_bytes()).into_boxed_slice();
+        let ptr13 = vec13.as_ptr().cast::<u8>();
+        let len13 = vec13.len();
+        ::core::mem::forget(vec13);
+        *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len13;
+        *base.add(8).cast::<*mut u8>() = ptr13.cast_mut();
+        match message12 {
+          Some(e) => {
+            *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>() = (1i32) as u8;
+            let vec14 = (e.into_bytes()).into_boxed_slice();
+            let ptr14 = vec14.as_ptr().cast::<u8>();
+            let len14 = vec14.len();
+            ::core::mem::forget(vec14);
+            *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<usize>() = len14;
+            *base.add(8+3*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr14.cast_mut();
+          },
+          None => {
+            {
+              *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>() = (0i32) as u8;
+            }
+          },
+        };match location12 {
+          Some(e) => {
+            *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>() = (1i32) as u8;
+            let vec15 = (e.into_bytes()).into_boxed_slice();
+            let ptr15 = vec15.as_ptr().cast::<u8>();
+            let len15 = vec15.len();
+            ::core::mem::forget(vec15);
+            *base.add(8+7*::core::mem::size_of::<*const u8>()).cast::<usize>() = len15;
+            *base.add(8+6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr15.cast_mut();
+          },
+          None => {
+            {
+              *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>() = (0i32) as u8;
+            }
+          },
+        };let vec16 = (details12).into_boxed_slice();
+        let ptr16 = vec16.as_ptr().cast::<u8>();
+        let len16 = vec16.len();
+        ::core::mem::forget(vec16);
+        *base.add(8+9*::core::mem::size_of::<*const u8>()).cast::<usize>() = len16;
+        *base.add(8+8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr16.cast_mut();
+      },
+      Value::Process(e) => {
+        *base.add(0).cast::<u8>() = (8i32) as u8;
+        *base.add(8).cast::<i64>() = _rt::as_i64(e);
+      },
+    }
+  }
+}
+*ptr2.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len17;
+*ptr2.add(0).cast::<*mut u8>() = result17;
+let vec18 = (results3).into_boxed_slice();
+let ptr18 = vec18.as_ptr().cast::<u8>();
+let len18 = vec18.len();
+::core::mem::forget(vec18);
+*ptr2.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len18;
+*ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr18.cast_mut();
+let vec19 = (spawned3).into_boxed_slice();
+let ptr19 = vec19.as_ptr().cast::<u8>();
+let len19 = vec19.len();
+::core::mem::forget(vec19);
+*ptr2.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>() = len19;
+*ptr2.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr19.cast_mut();
+let vec21 = logs3;
+let len21 = vec21.len();
+let layout21 = _rt::alloc::Layout::from_size_align_unchecked(vec21.len() * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+let result21 = if layout21.size() != 0 {
+  let ptr = _rt::alloc::alloc(layout21).cast::<u8>();
+  if ptr.is_null()
+  {
+    _rt::alloc::handle_alloc_error(layout21);
+  }
+  ptr
+}else {
+  ::core::ptr::null_mut()
+};
+for (i, e) in vec21.into_iter().enumerate() {
+  let base = result21.add(i * (2*::core::mem::size_of::<*const u8>()));
+  {
+    let vec20 = (e.into_bytes()).into_boxed_slice();
+    let ptr20 = vec20.as_ptr().cast::<u8>();
+    let len20 = vec20.len();
+    ::core::mem::forget(vec20);
+    *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len20;
+    *base.add(0).cast::<*mut u8>() = ptr20.cast_mut();
+  }
+}
+*ptr2.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>() = len21;
+*ptr2.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = result21;
+let vec26 = diagnostics3;
+let len26 = vec26.len();
+let layout26 = _rt::alloc::Layout::from_size_align_unchecked(vec26.len() * (8+6*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+let result26 = if layout26.size() != 0 {
+  let ptr = _rt::alloc::alloc(layout26).cast::<u8>();
+  if ptr.is_null()
+  {
+    _rt::alloc::handle_alloc_error(layout26);
+  }
+  ptr
+}else {
+  ::core::ptr::null_mut()
+};
+for (i, e) in vec26.into_iter().enumerate() {
+  let base = result26.add(i * (8+6*::core::mem::size_of::<*const u8>()));
+  {
+    let Diagnostic{ message:message22, location:location22, line:line22, column:column22, snippet:snippet22, } = e;
+    let vec23 = (message22.into_bytes()).into_boxed_slice();
+    let ptr23 = vec23.as_ptr().cast::<u8>();
+    let len23 = vec23.len();
+    ::core::mem::forget(vec23);
+    *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len23;
+    *base.add(0).cast::<*mut u8>() = ptr23.cast_mut();
+    let vec24 = (location22.into_bytes()).into_boxed_slice();
+    let ptr24 = vec24.as_ptr().cast::<u8>();
+    let len24 = vec24.len();
+    ::core::mem::forget(vec24);
+    *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len24;
+    *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr24.cast_mut();
+    *base.add(4*::core::mem::size_of::<*const u8>()).cast::<i32>() = _rt::as_i32(line22);
+    *base.add(4+4*::core::mem::size_of::<*const u8>()).cast::<i32>() = _rt::as_i32(column22);
+    let vec25 = (snippet22.into_bytes()).into_boxed_slice();
+    let ptr25 = vec25.as_ptr().cast::<u8>();
+    let len25 = vec25.len();
+    ::core::mem::forget(vec25);
+    *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<usize>() = len25;
+    *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr25.cast_mut();
+  }
+}
+*ptr2.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>() = len26;
+*ptr2.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = result26;
+ptr2
+}
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_method_session_evaluate_with_limits<T: GuestSession>(arg0: *mut u8,) {
+  let l0 = *arg0.add(0).cast::<*mut u8>();
+  let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+  let base30 = l0;
+  let len30 = l1;
+  for i in 0..len30 {
+    let base = base30.add(i * (8+10*::core::mem::size_of::<*const u8>()));
+    {
+      let l2 = i32::from(*base.add(0).cast::<u8>());
+      match l2 {
+        0 => {
+          let l3 = *base.add(8).cast::<*mut u8>();
+          let l4 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l3, l4, 1);
+        },
+        1 => {
+          let l5 = *base.add(8).cast::<*mut u8>();
+          let l6 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l5, l6, 1);
+        },
+        2 => {
+          let l7 = *base.add(8).cast::<*mut u8>();
+          let l8 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l7, l8, 1);
+        },
+        3 => {
+          let l9 = *base.add(8).cast::<*mut u8>();
+          let l10 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l9, l10, 1);
+        },
+        4 => {
+          let l11 = *base.add(8).cast::<*mut u8>();
+          let l12 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base13 = l11;
+          let len13 = l12;
+          _rt::cabi_dealloc(base13, len13 * 4, 4);
+        },
+        5 => {
+          let l14 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l15 = *base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base16 = l14;
+          let len16 = l15;
+          _rt::cabi_dealloc(base16, len16 * 4, 4);
+        },
+        6 => {
+          let l17 = *base.add(8).cast::<*mut u8>();
+          let l18 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l17, l18, 1);
+        },
+        7 => {
+          let l19 = *base.add(8).cast::<*mut u8>();
+          let l20 = *base.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l19, l20, 1);
+          let l21 = i32::from(*base.add(8+2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+          match l21 {
+            0 => (),
+            _ => {
+              let l22 = *base.add(8+3*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l23 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              _rt::cabi_dealloc(l22, l23, 1);
+            },
+          }
+          let l24 = i32::from(*base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<u8>());
+          match l24 {
+            0 => (),
+            _ => {
+              let l25 = *base.add(8+6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l26 = *base.add(8+7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              _rt::cabi_dealloc(l25, l26, 1);
+            },
+          }
+          let l27 = *base.add(8+8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l28 = *base.add(8+9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base29 = l27;
+          let len29 = l28;
+          _rt::cabi_dealloc(base29, len29 * 4, 4);
+        },
+        _ => (),
+      }
+    }
+  }
+  _rt::cabi_dealloc(base30, len30 * (8+10*::core::mem::size_of::<*const u8>()), 8);
+  let l31 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+  let l32 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+  let base33 = l31;
+  let len33 = l32;
+  _rt::cabi_dealloc(base33, len33 * 4, 4);
+  let l34 = *arg0.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+  let l35 = *arg0.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+  let base36 = l34;
+  let len36 = l35;
+  _rt::cabi_dealloc(base36, len36 * 4, 4);
+  let l37 = *arg0.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+  let l38 = *arg0.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+  let base41 = l37;
+  let len41 = l38;
+  for i in 0..len41 {
+    let base = base41.add(i * (2*::core::mem::size_of::<*const u8>()));
+    {
+      let l39 = *base.add(0).cast::<*mut u8>();
+      let l40 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l39, l40, 1);
+    }
+  }
+  _rt::cabi_dealloc(base41, len41 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+  let l42 = *arg0.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+  let l43 = *arg0.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+  let base50 = l42;
+  let len50 = l43;
+  for i in 0..len50 {
+    let base = base50.add(i * (8+6*::core::mem::size_of::<*const u8>()));
+    {
+      let l44 = *base.add(0).cast::<*mut u8>();
+      let l45 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l44, l45, 1);
+      let l46 = *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l47 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l46, l47, 1);
+      let l48 = *base.add(8+4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l49 = *base.add(8+5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l48, l49, 1);
+    }
+  }
+  _rt::cabi_dealloc(base50, len50 * (8+6*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+}
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn _export_method_session_reset_cabi<T: GuestSession>(arg0: *mut u8,) {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();T::reset(unsafe { SessionBorrow::lift(arg0 as u32 as usize) }.get());
+}
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn _export_method_session_bindings_cabi<T: GuestSession>(arg0: *mut u8,) -> *mut u8 {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result0 = T::bindings(unsafe { SessionBorrow::lift(arg0 as u32 as usize) }.get());
+let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+let vec3 = result0;
+let len3 = vec3.len();
+let layout3 = _rt::alloc::Layout::from_size_align_unchecked(vec3.len() * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+let result3 = if layout3.size() != 0 {
+  let ptr = _rt::alloc::alloc(layout3).cast::<u8>();
+  if ptr.is_null()
+  {
+    _rt::alloc::handle_alloc_error(layout3);
+  }
+  ptr
+}else {
+  ::core::ptr::null_mut()
+};
+for (i, e) in vec3.into_iter().enumerate() {
+  let base = result3.add(i * (2*::core::mem::size_of::<*const u8>()));
+  {
+    let vec2 = (e.into_bytes()).into_boxed_slice();
+    let ptr2 = vec2.as_ptr().cast::<u8>();
+    let len2 = vec2.len();
+    ::core::mem::forget(vec2);
+    *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len2;
+    *base.add(0).cast::<*mut u8>() = ptr2.cast_mut();
+  }
+}
+*ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+*ptr1.add(0).cast::<*mut u8>() = result3;
+ptr1
+}
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_method_session_bindings<T: GuestSession>(arg0: *mut u8,) {
+  let l0 = *arg0.add(0).cast::<*mut u8>();
+  let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+  let base4 = l0;
+  let len4 = l1;
+  for i in 0..len4 {
+    let base = base4.add(i * (2*::core::mem::size_of::<*const u8>()));
+    {
+      let l2 = *base.add(0).cast::<*mut u8>();
+      let l3 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l2, l3, 1);
     }
+  }
+  _rt::cabi_dealloc(base4, len4 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+}
+pub trait Guest {
+  type Session: GuestSession;
+  fn evaluate(code: _rt::String,) -> Evaluation;
+  /// Like evaluate, but once a limit is reached the form that was running
+  /// gets an :error(:out-of-fuel ...) as its result, and nothing after it runs.
+  fn evaluate_with_limits(code: _rt::String,limits: Limits,) -> Evaluation;
+}
+pub trait GuestSession: 'static {
+
+  #[doc(hidden)]
+  unsafe fn _resource_new(val: *mut u8) -> u32
+  where Self: Sized
+  {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+      let _ = val;
+      unreachable!();
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+      #[link(wasm_import_module = "[export]component:nana/nana")]
+      unsafe extern "C" {
+        #[link_name = "[resource-new]session"]
+        fn new(_: *mut u8) -> u32;
+      }
+      unsafe { new(val) }
+    }
+  }
+
+  #[doc(hidden)]
+  fn _resource_rep(handle: u32) -> *mut u8
+  where Self: Sized
+  {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+      let _ = handle;
+      unreachable!();
+    }
+
     #[cfg(target_arch = "wasm32")]
-    pub fn run_ctors_once() {
-        wit_bindgen_rt::run_ctors_once();
+    {
+      #[link(wasm_import_module = "[export]component:nana/nana")]
+      unsafe extern "C" {
+        #[link_name = "[resource-rep]session"]
+        fn rep(_: u32) -> *mut u8;
+      }
+      unsafe {
+        rep(handle)
+      }
+    }
+  }
+
+  
+  fn new() -> Self;
+  /// Code that fails to parse defines nothing.
+  fn evaluate(&self,code: _rt::String,) -> Evaluation;
+  fn evaluate_with_limits(&self,code: _rt::String,limits: Limits,) -> Evaluation;
+  /// Forgets every definition, leaving only the standard library.
+  fn reset(&self,) -> ();
+  fn bindings(&self,) -> _rt::Vec::<_rt::String>;
+}
+#[doc(hidden)]
+
+macro_rules! __export_component_nana_nana_cabi{
+  ($ty:ident with_types_in $($path_to_types:tt)*) => (const _: () = {
+
+    #[unsafe(export_name = "component:nana/nana#evaluate")]
+    unsafe extern "C" fn export_evaluate(arg0: *mut u8,arg1: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_evaluate_cabi::<$ty>(arg0, arg1) }
+    }
+    #[unsafe(export_name = "cabi_post_component:nana/nana#evaluate")]
+    unsafe extern "C" fn _post_return_evaluate(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_evaluate::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "component:nana/nana#evaluate-with-limits")]
+    unsafe extern "C" fn export_evaluate_with_limits(arg0: *mut u8,arg1: usize,arg2: i32,arg3: i64,arg4: i32,arg5: i64,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_evaluate_with_limits_cabi::<$ty>(arg0, arg1, arg2, arg3, arg4, arg5) }
+    }
+    #[unsafe(export_name = "cabi_post_component:nana/nana#evaluate-with-limits")]
+    unsafe extern "C" fn _post_return_evaluate_with_limits(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_evaluate_with_limits::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "component:nana/nana#[constructor]session")]
+    unsafe extern "C" fn export_constructor_session() -> i32 {
+      unsafe { $($path_to_types)*::_export_constructor_session_cabi::<<$ty as $($path_to_types)*::Guest>::Session>() }
+    }
+    #[unsafe(export_name = "component:nana/nana#[method]session.evaluate")]
+    unsafe extern "C" fn export_method_session_evaluate(arg0: *mut u8,arg1: *mut u8,arg2: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_method_session_evaluate_cabi::<<$ty as $($path_to_types)*::Guest>::Session>(arg0, arg1, arg2) }
+    }
+    #[unsafe(export_name = "cabi_post_component:nana/nana#[method]session.evaluate")]
+    unsafe extern "C" fn _post_return_method_session_evaluate(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_method_session_evaluate::<<$ty as $($path_to_types)*::Guest>::Session>(arg0) }
+    }
+    #[unsafe(export_name = "component:nana/nana#[method]session.evaluate-with-limits")]
+    unsafe extern "C" fn export_method_session_evaluate_with_limits(arg0: *mut u8,arg1: *mut u8,arg2: usize,arg3: i32,arg4: i64,arg5: i32,arg6: i64,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_method_session_evaluate_with_limits_cabi::<<$ty as $($path_to_types)*::Guest>::Session>(arg0, arg1, arg2, arg3, arg4, arg5, arg6) }
+    }
+    #[unsafe(export_name = "cabi_post_component:nana/nana#[method]session.evaluate-with-limits")]
+    unsafe extern "C" fn _post_return_method_session_evaluate_with_limits(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_method_session_evaluate_with_limits::<<$ty as $($path_to_types)*::Guest>::Session>(arg0) }
+    }
+    #[unsafe(export_name = "component:nana/nana#[method]session.reset")]
+    unsafe extern "C" fn export_method_session_reset(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::_export_method_session_reset_cabi::<<$ty as $($path_to_types)*::Guest>::Session>(arg0) }
+    }
+    #[unsafe(export_name = "component:nana/nana#[method]session.bindings")]
+    unsafe extern "C" fn export_method_session_bindings(arg0: *mut u8,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_method_session_bindings_cabi::<<$ty as $($path_to_types)*::Guest>::Session>(arg0) }
+    }
+    #[unsafe(export_name = "cabi_post_component:nana/nana#[method]session.bindings")]
+    unsafe extern "C" fn _post_return_method_session_bindings(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_method_session_bindings::<<$ty as $($path_to_types)*::Guest>::Session>(arg0) }
+    }
+
+    const _: () = {
+      #[doc(hidden)]
+      #[unsafe(export_name = "component:nana/nana#[dtor]session")]
+      #[allow(non_snake_case)]
+      unsafe extern "C" fn dtor(rep: *mut u8) {
+        unsafe {
+          $($path_to_types)*::Session::dtor::<
+          <$ty as $($path_to_types)*::Guest>::Session
+          >(rep)
+        }
+      }
+    };
+    
+  };);
+}
+#[doc(hidden)]
+pub(crate) use __export_component_nana_nana_cabi;
+
+#[cfg_attr(target_pointer_width="64", repr(align(8)))]
+#[cfg_attr(target_pointer_width="32", repr(align(4)))]
+struct _RetArea([::core::mem::MaybeUninit::<u8>; 10*::core::mem::size_of::<*const u8>()]);
+static mut _RET_AREA: _RetArea = _RetArea([::core::mem::MaybeUninit::uninit(); 10*::core::mem::size_of::<*const u8>()]);
+
+}
+
+}
+}
+pub mod wasi {
+  pub mod cli {
+
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod run {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::super::_rt;
+      #[doc(hidden)]
+      #[allow(non_snake_case)]
+      pub unsafe fn _export_run_cabi<T: Guest>() -> i32 {#[cfg(target_arch="wasm32")]
+      _rt::run_ctors_once();let result0 = T::run();
+      let result1 = match result0 {
+        Ok(_) => { 0i32 },
+        Err(_) => { 1i32 },
+      };result1
+    }
+    pub trait Guest {
+      /// Run the program.
+      fn run() -> Result<(),()>;
     }
-    pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
-        if size == 0 {
-            return;
+    #[doc(hidden)]
+
+    macro_rules! __export_wasi_cli_run_0_2_2_cabi{
+      ($ty:ident with_types_in $($path_to_types:tt)*) => (const _: () = {
+
+        #[unsafe(export_name = "wasi:cli/run@0.2.2#run")]
+        unsafe extern "C" fn export_run() -> i32 {
+          unsafe { $($path_to_types)*::_export_run_cabi::<$ty>() }
         }
-        let layout = alloc::Layout::from_size_align_unchecked(size, align);
-        alloc::dealloc(ptr, layout);
+      };);
     }
-    extern crate alloc as alloc_crate;
-    pub use alloc_crate::alloc;
+    #[doc(hidden)]
+    pub(crate) use __export_wasi_cli_run_0_2_2_cabi;
+
+  }
+
 }
-/// Generates `#[no_mangle]` functions to export the specified type as the
-/// root implementation of all generated traits.
+}
+}
+mod _rt {
+  #![allow(dead_code, clippy::all)]
+
+
+  use core::fmt;
+  use core::marker;
+  use core::sync::atomic::{AtomicU32, Ordering::Relaxed};
+
+  /// A type which represents a component model resource, either imported or
+  /// exported into this component.
+  ///
+  /// This is a low-level wrapper which handles the lifetime of the resource
+  /// (namely this has a destructor). The `T` provided defines the component model
+  /// intrinsics that this wrapper uses.
+  ///
+  /// One of the chief purposes of this type is to provide `Deref` implementations
+  /// to access the underlying data when it is owned.
+  ///
+  /// This type is primarily used in generated code for exported and imported
+  /// resources.
+  #[repr(transparent)]
+  pub struct Resource<T: WasmResource> {
+    // NB: This would ideally be `u32` but it is not. The fact that this has
+    // interior mutability is not exposed in the API of this type except for the
+    // `take_handle` method which is supposed to in theory be private.
+    //
+    // This represents, almost all the time, a valid handle value. When it's
+    // invalid it's stored as `u32::MAX`.
+    handle: AtomicU32,
+    _marker: marker::PhantomData<T>,
+  }
+
+  /// A trait which all wasm resources implement, namely providing the ability to
+  /// drop a resource.
+  ///
+  /// This generally is implemented by generated code, not user-facing code.
+  #[allow(clippy::missing_safety_doc)]
+  pub unsafe trait WasmResource {
+    /// Invokes the `[resource-drop]...` intrinsic.
+    unsafe fn drop(handle: u32);
+  }
+
+  impl<T: WasmResource> Resource<T> {
+    #[doc(hidden)]
+    pub unsafe fn from_handle(handle: u32) -> Self {
+      debug_assert!(handle != u32::MAX);
+      Self {
+        handle: AtomicU32::new(handle),
+        _marker: marker::PhantomData,
+      }
+    }
+
+    /// Takes ownership of the handle owned by `resource`.
+    ///
+    /// Note that this ideally would be `into_handle` taking `Resource<T>` by
+    /// ownership. The code generator does not enable that in all situations,
+    /// unfortunately, so this is provided instead.
+    ///
+    /// Also note that `take_handle` is in theory only ever called on values
+    /// owned by a generated function. For example a generated function might
+    /// take `Resource<T>` as an argument but then call `take_handle` on a
+    /// reference to that argument. In that sense the dynamic nature of
+    /// `take_handle` should only be exposed internally to generated code, not
+    /// to user code.
+    #[doc(hidden)]
+    pub fn take_handle(resource: &Resource<T>) -> u32 {
+      resource.handle.swap(u32::MAX, Relaxed)
+    }
+
+    #[doc(hidden)]
+    pub fn handle(resource: &Resource<T>) -> u32 {
+      resource.handle.load(Relaxed)
+    }
+  }
+
+  impl<T: WasmResource> fmt::Debug for Resource<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      f.debug_struct("Resource")
+      .field("handle", &self.handle)
+      .finish()
+    }
+  }
+
+  impl<T: WasmResource> Drop for Resource<T> {
+    fn drop(&mut self) {
+      unsafe {
+        match self.handle.load(Relaxed) {
+          // If this handle was "taken" then don't do anything in the
+          // destructor.
+          u32::MAX => {}
+
+          // ... but otherwise do actually destroy it with the imported
+          // component model intrinsic as defined through `T`.
+          other => T::drop(other),
+        }
+      }
+    }
+  }
+  pub unsafe fn bool_lift(val: u8) -> bool {
+    if cfg!(debug_assertions) {
+      match val {
+        0 => false,
+        1 => true,
+        _ => panic!("invalid bool discriminant"),
+      }
+    } else {
+      val != 0
+    }
+  }
+  pub use alloc_crate::vec::Vec;
+  pub use alloc_crate::alloc;
+
+  pub fn as_i64<T: AsI64>(t: T) -> i64 {
+    t.as_i64()
+  }
+
+  pub trait AsI64 {
+    fn as_i64(self) -> i64;
+  }
+
+  impl<'a, T: Copy + AsI64> AsI64 for &'a T {
+    fn as_i64(self) -> i64 {
+      (*self).as_i64()
+    }
+  }
+  
+  impl AsI64 for i64 {
+    #[inline]
+    fn as_i64(self) -> i64 {
+      self as i64
+    }
+  }
+  
+  impl AsI64 for u64 {
+    #[inline]
+    fn as_i64(self) -> i64 {
+      self as i64
+    }
+  }
+  pub use alloc_crate::string::String;
+  pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
+    if cfg!(debug_assertions) {
+      String::from_utf8(bytes).unwrap()
+    } else {
+      String::from_utf8_unchecked(bytes)
+    }
+  }
+  pub unsafe fn invalid_enum_discriminant<T>() -> T {
+    if cfg!(debug_assertions) {
+      panic!("invalid enum discriminant")
+    } else {
+      unsafe { core::hint::unreachable_unchecked() }
+    }
+  }
+  pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
+    if size == 0 {
+      return;
+    }
+    let layout = alloc::Layout::from_size_align_unchecked(size, align);
+    alloc::dealloc(ptr, layout);
+  }
+  
+  #[cfg(target_arch = "wasm32")]
+  pub fn run_ctors_once() {
+    wit_bindgen_rt::run_ctors_once();
+  }
+  pub use alloc_crate::boxed::Box;
+
+  pub fn as_i32<T: AsI32>(t: T) -> i32 {
+    t.as_i32()
+  }
+
+  pub trait AsI32 {
+    fn as_i32(self) -> i32;
+  }
+
+  impl<'a, T: Copy + AsI32> AsI32 for &'a T {
+    fn as_i32(self) -> i32 {
+      (*self).as_i32()
+    }
+  }
+  
+  impl AsI32 for i32 {
+    #[inline]
+    fn as_i32(self) -> i32 {
+      self as i32
+    }
+  }
+  
+  impl AsI32 for u32 {
+    #[inline]
+    fn as_i32(self) -> i32 {
+      self as i32
+    }
+  }
+  
+  impl AsI32 for i16 {
+    #[inline]
+    fn as_i32(self) -> i32 {
+      self as i32
+    }
+  }
+  
+  impl AsI32 for u16 {
+    #[inline]
+    fn as_i32(self) -> i32 {
+      self as i32
+    }
+  }
+  
+  impl AsI32 for i8 {
+    #[inline]
+    fn as_i32(self) -> i32 {
+      self as i32
+    }
+  }
+  
+  impl AsI32 for u8 {
+    #[inline]
+    fn as_i32(self) -> i32 {
+      self as i32
+    }
+  }
+  
+  impl AsI32 for char {
+    #[inline]
+    fn as_i32(self) -> i32 {
+      self as i32
+    }
+  }
+  
+  impl AsI32 for usize {
+    #[inline]
+    fn as_i32(self) -> i32 {
+      self as i32
+    }
+  }
+  extern crate alloc as alloc_crate;
+}
+
+/// Generates `#[unsafe(no_mangle)]` functions to export the specified type as
+/// the root implementation of all generated traits.
 ///
 /// For more information see the documentation of `wit_bindgen::generate!`.
 ///
@@ -183,33 +3785,71 @@ mod _rt {
 /// ```
 #[allow(unused_macros)]
 #[doc(hidden)]
+
 macro_rules! __export_command_impl {
-    ($ty:ident) => {
-        self::export!($ty with_types_in self);
-    };
-    ($ty:ident with_types_in $($path_to_types_root:tt)*) => {
-        $($path_to_types_root)*::
-        exports::wasi::cli::run::__export_wasi_cli_run_0_2_2_cabi!($ty with_types_in
-        $($path_to_types_root)*:: exports::wasi::cli::run); $($path_to_types_root)*::
-        exports::component::nana::nana::__export_component_nana_nana_cabi!($ty
-        with_types_in $($path_to_types_root)*:: exports::component::nana::nana);
-    };
+  ($ty:ident) => (self::export!($ty with_types_in self););
+  ($ty:ident with_types_in $($path_to_types_root:tt)*) => (
+  $($path_to_types_root)*::exports::wasi::cli::run::__export_wasi_cli_run_0_2_2_cabi!($ty with_types_in $($path_to_types_root)*::exports::wasi::cli::run);
+  $($path_to_types_root)*::exports::component::nana::nana::__export_component_nana_nana_cabi!($ty with_types_in $($path_to_types_root)*::exports::component::nana::nana);
+  )
 }
 #[doc(inline)]
 pub(crate) use __export_command_impl as export;
+
 #[cfg(target_arch = "wasm32")]
-#[link_section = "component-type:wit-bindgen:0.31.0:component:nana:command:encoded world"]
+#[unsafe(link_section = "component-type:wit-bindgen:0.41.0:component:nana:command:encoded world")]
 #[doc(hidden)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 304] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xb2\x01\x01A\x02\x01\
-A\x06\x01B\x02\x01@\x01\x04names\0s\x04\0\x08evaluate\x01\0\x03\x01\x13component\
-:nana/nana\x05\0\x01B\x03\x01j\0\0\x01@\0\0\0\x04\0\x03run\x01\x01\x04\x01\x12wa\
-si:cli/run@0.2.2\x05\x01\x01B\x02\x01@\x01\x04names\0s\x04\0\x08evaluate\x01\0\x04\
-\x01\x13component:nana/nana\x05\x02\x04\x01\x16component:nana/command\x04\0\x0b\x0d\
-\x01\0\x07command\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-compone\
-nt\x070.216.0\x10wit-bindgen-rust\x060.31.0";
+#[allow(clippy::octal_escapes)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 2033] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xf3\x0e\x01A\x02\x01\
+A\x0b\x01B\x0a\x04\0\x08pollable\x03\x01\x01h\0\x01@\x01\x04self\x01\0\x7f\x04\0\
+\x16[method]pollable.ready\x01\x02\x01@\x01\x04self\x01\x01\0\x04\0\x16[method]p\
+ollable.block\x01\x03\x01p\x01\x01py\x01@\x01\x02in\x04\0\x05\x04\0\x04poll\x01\x06\
+\x03\0\x12wasi:io/poll@0.2.2\x05\0\x02\x03\0\0\x08pollable\x01B\x0f\x02\x03\x02\x01\
+\x01\x04\0\x08pollable\x03\0\0\x01w\x04\0\x07instant\x03\0\x02\x01w\x04\0\x08dur\
+ation\x03\0\x04\x01@\0\0\x03\x04\0\x03now\x01\x06\x01@\0\0\x05\x04\0\x0aresoluti\
+on\x01\x07\x01i\x01\x01@\x01\x04when\x03\0\x08\x04\0\x11subscribe-instant\x01\x09\
+\x01@\x01\x04when\x05\0\x08\x04\0\x12subscribe-duration\x01\x0a\x03\0!wasi:clock\
+s/monotonic-clock@0.2.2\x05\x02\x01B%\x01y\x04\0\x0bvalue-index\x03\0\0\x01p\x01\
+\x01r\x02\x03tag\x01\x06values\x02\x04\0\x0ctagged-tuple\x03\0\x03\x01ks\x01r\x04\
+\x07varietys\x07message\x05\x08location\x05\x07details\x02\x04\0\x0berror-value\x03\
+\0\x06\x01q\x09\x06number\x01s\0\x06string\x01s\0\x07keyword\x01s\0\x06symbol\x01\
+s\0\x04list\x01\x02\0\x0ctagged-tuple\x01\x04\0\x0cfunction-ref\x01s\0\x05error\x01\
+\x07\0\x07process\x01w\0\x04\0\x05value\x03\0\x08\x01r\x05\x07messages\x08locati\
+ons\x04liney\x06columny\x07snippets\x04\0\x0adiagnostic\x03\0\x0a\x01p\x09\x01ps\
+\x01p\x0b\x01r\x05\x06values\x0c\x07results\x02\x07spawned\x02\x04logs\x0d\x0bdi\
+agnostics\x0e\x04\0\x0aevaluation\x03\0\x0f\x01kw\x01r\x02\x09max-steps\x11\x0dt\
+ime-limit-ms\x11\x04\0\x06limits\x03\0\x12\x04\0\x07session\x03\x01\x01i\x14\x01\
+@\0\0\x15\x04\0\x14[constructor]session\x01\x16\x01h\x14\x01@\x02\x04self\x17\x04\
+codes\0\x10\x04\0\x18[method]session.evaluate\x01\x18\x01@\x03\x04self\x17\x04co\
+des\x06limits\x13\0\x10\x04\0$[method]session.evaluate-with-limits\x01\x19\x01@\x01\
+\x04self\x17\x01\0\x04\0\x15[method]session.reset\x01\x1a\x01@\x01\x04self\x17\0\
+\x0d\x04\0\x18[method]session.bindings\x01\x1b\x01@\x01\x04codes\0\x10\x04\0\x08\
+evaluate\x01\x1c\x01@\x02\x04codes\x06limits\x13\0\x10\x04\0\x14evaluate-with-li\
+mits\x01\x1d\x03\0\x13component:nana/nana\x05\x03\x01B\x03\x01j\0\0\x01@\0\0\0\x04\
+\0\x03run\x01\x01\x04\0\x12wasi:cli/run@0.2.2\x05\x04\x01B%\x01y\x04\0\x0bvalue-\
+index\x03\0\0\x01p\x01\x01r\x02\x03tag\x01\x06values\x02\x04\0\x0ctagged-tuple\x03\
+\0\x03\x01ks\x01r\x04\x07varietys\x07message\x05\x08location\x05\x07details\x02\x04\
+\0\x0berror-value\x03\0\x06\x01q\x09\x06number\x01s\0\x06string\x01s\0\x07keywor\
+d\x01s\0\x06symbol\x01s\0\x04list\x01\x02\0\x0ctagged-tuple\x01\x04\0\x0cfunctio\
+n-ref\x01s\0\x05error\x01\x07\0\x07process\x01w\0\x04\0\x05value\x03\0\x08\x01r\x05\
+\x07messages\x08locations\x04liney\x06columny\x07snippets\x04\0\x0adiagnostic\x03\
+\0\x0a\x01p\x09\x01ps\x01p\x0b\x01r\x05\x06values\x0c\x07results\x02\x07spawned\x02\
+\x04logs\x0d\x0bdiagnostics\x0e\x04\0\x0aevaluation\x03\0\x0f\x01kw\x01r\x02\x09\
+max-steps\x11\x0dtime-limit-ms\x11\x04\0\x06limits\x03\0\x12\x04\0\x07session\x03\
+\x01\x01i\x14\x01@\0\0\x15\x04\0\x14[constructor]session\x01\x16\x01h\x14\x01@\x02\
+\x04self\x17\x04codes\0\x10\x04\0\x18[method]session.evaluate\x01\x18\x01@\x03\x04\
+self\x17\x04codes\x06limits\x13\0\x10\x04\0$[method]session.evaluate-with-limits\
+\x01\x19\x01@\x01\x04self\x17\x01\0\x04\0\x15[method]session.reset\x01\x1a\x01@\x01\
+\x04self\x17\0\x0d\x04\0\x18[method]session.bindings\x01\x1b\x01@\x01\x04codes\0\
+\x10\x04\0\x08evaluate\x01\x1c\x01@\x02\x04codes\x06limits\x13\0\x10\x04\0\x14ev\
+aluate-with-limits\x01\x1d\x04\0\x13component:nana/nana\x05\x05\x04\0\x16compone\
+nt:nana/command\x04\0\x0b\x0d\x01\0\x07command\x03\0\0\0G\x09producers\x01\x0cpr\
+ocessed-by\x02\x0dwit-component\x070.227.1\x10wit-bindgen-rust\x060.41.0";
+
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
-    wit_bindgen_rt::maybe_link_cabi_realloc();
+  wit_bindgen_rt::maybe_link_cabi_realloc();
 }
+
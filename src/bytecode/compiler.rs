@@ -0,0 +1,153 @@
+use im::Vector;
+
+use super::{Case, Chunk, Instruction, MatchTable};
+use crate::errors::{error_with_message, with_location};
+use crate::expressions::RuntimeExpression::{
+    self, Hole, Keyword, List, Local, MacroCall, Number, String as NString, Symbol, TaggedTuple,
+};
+use crate::process::Process::Complete;
+use crate::span::Span;
+
+// Compiles a function body. Every expression is run but only the last one's
+// value is returned.
+pub fn compile_body(expressions: &Vector<RuntimeExpression>) -> Chunk {
+    let mut compiler = Compiler::default();
+    for (index, expression) in expressions.iter().enumerate() {
+        if index > 0 {
+            compiler.emit(Instruction::Pop);
+        }
+        compiler.compile(expression, index == expressions.len() - 1);
+    }
+    compiler.emit(Instruction::Return);
+    compiler.chunk
+}
+
+// Compiles a single top level form
+pub fn compile(expression: &RuntimeExpression) -> Chunk {
+    compile_body(&im::vector![expression.clone()])
+}
+
+#[derive(Default)]
+struct Compiler {
+    chunk: Chunk,
+}
+
+impl Compiler {
+    fn emit(&mut self, instruction: Instruction) -> usize {
+        self.chunk.code.push(instruction);
+        self.chunk.code.len() - 1
+    }
+
+    fn add_constant(&mut self, value: RuntimeExpression) -> usize {
+        self.chunk.constants.push(value);
+        self.chunk.constants.len() - 1
+    }
+
+    fn constant(&mut self, value: RuntimeExpression) {
+        let index = self.add_constant(value);
+        self.emit(Instruction::Constant(index));
+    }
+
+    fn span(&mut self, span: &Span) -> usize {
+        self.chunk.spans.push(span.clone());
+        self.chunk.spans.len() - 1
+    }
+
+    // Left to eval, which knows how to run everything
+    fn eval(&mut self, expression: &RuntimeExpression) {
+        let index = self.add_constant(expression.clone());
+        self.emit(Instruction::Eval(index));
+    }
+
+    // Points a jump emitted earlier at the next instruction
+    fn patch(&mut self, at: usize) {
+        let target = self.chunk.code.len();
+        self.chunk.code[at] = match self.chunk.code[at] {
+            Instruction::Jump(_) => Instruction::Jump(target),
+            Instruction::Callee(tag, span, _) => Instruction::Callee(tag, span, target),
+            instruction => instruction,
+        };
+    }
+
+    // In tail position an expression's value is the value of the whole body
+    fn compile(&mut self, expression: &RuntimeExpression, tail: bool) {
+        match expression {
+            Number(_) | NString(_) | Keyword(_) => self.constant(expression.clone()),
+            Symbol(..) => {
+                let index = self.add_constant(expression.clone());
+                self.emit(Instruction::LoadName(index));
+            }
+            Local(_, slot, _) => {
+                let index = self.add_constant(expression.clone());
+                self.emit(Instruction::LoadLocal(*slot, index));
+            }
+            List(values) => {
+                values.iter().for_each(|value| self.compile(value, false));
+                self.emit(Instruction::List(values.len()));
+            }
+            TaggedTuple(tag, args, span) => match **tag {
+                Symbol(..) | Local(..) => {
+                    let tag = self.add_constant((**tag).clone());
+                    let span = self.span(span);
+                    let callee = self.emit(Instruction::Callee(tag, span, 0));
+                    args.iter().for_each(|arg| self.compile(arg, false));
+                    match tail {
+                        true => self.emit(Instruction::TailCall(args.len(), span)),
+                        false => self.emit(Instruction::Call(args.len(), span)),
+                    };
+                    self.patch(callee);
+                }
+                Keyword(_) => {
+                    let tag = self.add_constant((**tag).clone());
+                    args.iter().for_each(|arg| self.compile(arg, false));
+                    let span = self.span(span);
+                    self.emit(Instruction::Tuple(tag, args.len(), span));
+                }
+                _ => self.eval(expression),
+            },
+            MacroCall(name, args, _) if name == "Match" && args.len() == 2 => match &args[1] {
+                List(cases) if cases.len() % 2 == 0 => self.compile_match(&args[0], cases, tail),
+                _ => self.eval(expression),
+            },
+            Hole(span) => {
+                let error =
+                    error_with_message("invalid-hole", "Holes can only be used in patterns");
+                match with_location(error, span) {
+                    Complete(error) => self.constant(error),
+                    _ => self.eval(expression),
+                }
+            }
+            _ => self.eval(expression),
+        }
+    }
+
+    fn compile_match(
+        &mut self,
+        value: &RuntimeExpression,
+        cases: &Vector<RuntimeExpression>,
+        tail: bool,
+    ) {
+        self.compile(value, false);
+        let table = self.chunk.matches.len();
+        self.chunk.matches.push(MatchTable {
+            cases: vec![],
+            end: 0,
+        });
+        self.emit(Instruction::Match(table));
+
+        let mut jumps_to_end = vec![];
+        for pair in cases.iter().collect::<Vec<_>>().chunks(2) {
+            let case = Case {
+                pattern: pair[0].clone(),
+                body: self.chunk.code.len(),
+            };
+            self.chunk.matches[table].cases.push(case);
+            self.compile(pair[1], tail);
+            self.emit(Instruction::EndScope);
+            jumps_to_end.push(self.emit(Instruction::Jump(0)));
+        }
+
+        self.chunk.matches[table].end = self.chunk.code.len();
+        jumps_to_end.into_iter().for_each(|jump| self.patch(jump));
+    }
+}
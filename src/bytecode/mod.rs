@@ -0,0 +1,71 @@
+// An alternative to the tree walking evaluator in eval. Expressions are
+// compiled to a flat list of instructions that a VM runs with an explicit
+// stack, instead of allocating a closure for every step.
+//
+// Only the common forms are compiled: values, variables, calls, lists, tuples
+// and Match. Anything else, including macro calls, is handed to eval, so both
+// backends always give the same results.
+
+use crate::environment::Slot;
+use crate::expressions::RuntimeExpression;
+use crate::span::Span;
+
+pub mod compiler;
+pub mod vm;
+
+#[cfg(test)]
+pub mod vm_test;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    // Pushes a constant
+    Constant(usize),
+    // Pushes the value of the symbol held in a constant
+    LoadName(usize),
+    LoadLocal(Slot, usize),
+    // Pushes the function named by the tag in a constant, or an error and
+    // then jumps past the call's arguments if there's no such function
+    Callee(usize, usize, usize),
+    // Pops the given number of arguments and then the function to call them
+    // with. The second value is the span of the call.
+    Call(usize, usize),
+    // A call whose result is the result of the whole function, which reuses
+    // the caller's frame so loops written as recursion run in constant space
+    TailCall(usize, usize),
+    List(usize),
+    // Pops values into a tuple tagged with the keyword in a constant
+    Tuple(usize, usize, usize),
+    // Evaluates the expression in a constant with eval
+    Eval(usize),
+    // Pops a value and jumps to the first case in a match table that it
+    // matches, with the case's bindings in a new scope
+    Match(usize),
+    // Leaves the scope of a Match case
+    EndScope,
+    Jump(usize),
+    Pop,
+    Return,
+}
+
+// One case of a Match: its pattern and where its body starts
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case {
+    pub pattern: RuntimeExpression,
+    pub body: usize,
+}
+
+// Where to go if nothing matches, which will push :error(:no-match-found)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchTable {
+    pub cases: Vec<Case>,
+    pub end: usize,
+}
+
+// The compiled code of a function body or top level form
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Chunk {
+    pub code: Vec<Instruction>,
+    pub constants: Vec<RuntimeExpression>,
+    pub spans: Vec<Span>,
+    pub matches: Vec<MatchTable>,
+}
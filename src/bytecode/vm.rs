@@ -0,0 +1,263 @@
+use std::sync::Arc;
+
+use im::Vector;
+
+use super::compiler::compile;
+use super::{Chunk, Instruction};
+use crate::errors::{arity_error, error, not_found_error, with_location};
+use crate::eval::{apply, describe, eval};
+use crate::expressions::Environment;
+use crate::expressions::RuntimeExpression::{self, Function, Local, Symbol, TaggedTuple};
+use crate::process::Process::{self, Complete, Running, Spawn, Waiting};
+use crate::process::Stepable;
+use crate::scheduler;
+use crate::standard_library::core::does_match;
+
+// How many instructions run before the VM lets other processes have a turn
+pub const INSTRUCTIONS_PER_STEP: usize = 1000;
+
+// Runs an expression on the VM. The process yields every
+// INSTRUCTIONS_PER_STEP instructions, just as eval's processes yield at every
+// step, so spawned processes still take turns.
+pub fn run(expression: &RuntimeExpression, env: Environment) -> Process<RuntimeExpression> {
+    Vm::new(Arc::new(compile(expression)), env).run(INSTRUCTIONS_PER_STEP)
+}
+
+#[derive(Clone)]
+struct Frame {
+    chunk: Arc<Chunk>,
+    ip: usize,
+    env: Environment,
+    // The environments to go back to as each Match case ends
+    scopes: Vec<Environment>,
+    // Where this frame's values start on the stack
+    base: usize,
+}
+
+// Processes are values that can be stepped more than once, so each step works
+// on its own copy of the VM
+#[derive(Clone)]
+struct Vm {
+    stack: Vec<RuntimeExpression>,
+    frames: Vec<Frame>,
+    // A process started by an instruction, like a builtin call or an Eval,
+    // that has to finish before the VM can carry on
+    waiting_on: Option<Process<RuntimeExpression>>,
+}
+
+impl Stepable<RuntimeExpression> for Vm {
+    fn step(&self) -> Process<RuntimeExpression> {
+        self.clone().run(INSTRUCTIONS_PER_STEP)
+    }
+}
+
+impl Vm {
+    fn new(chunk: Arc<Chunk>, env: Environment) -> Vm {
+        Vm {
+            stack: vec![],
+            frames: vec![Frame {
+                chunk,
+                ip: 0,
+                env,
+                scopes: vec![],
+                base: 0,
+            }],
+            waiting_on: None,
+        }
+    }
+
+    fn frame(&mut self) -> &mut Frame {
+        self.frames.last_mut().unwrap()
+    }
+
+    // A step runs many instructions, so the scheduler is told how many, and
+    // the process's turn ends sooner
+    fn run(self, budget: usize) -> Process<RuntimeExpression> {
+        let (process, executed) = self.run_instructions(budget);
+        scheduler::reduce(executed as u64);
+        process
+    }
+
+    fn run_instructions(mut self, budget: usize) -> (Process<RuntimeExpression>, usize) {
+        for executed in 0..budget {
+            if let Some(process) = self.waiting_on.take() {
+                match process {
+                    Complete(value) => self.stack.push(value),
+                    Running(stepable) => self.waiting_on = Some(stepable.step()),
+                    Spawn(continuation, spawned) => {
+                        self.waiting_on = Some((*continuation).clone());
+                        let process = Spawn(Arc::new(Running(Arc::new(self))), spawned);
+                        return (process, executed);
+                    }
+                    Waiting(wake, continuation) => {
+                        self.waiting_on = Some((*continuation).clone());
+                        return (Waiting(wake, Arc::new(Running(Arc::new(self)))), executed);
+                    }
+                }
+                continue;
+            }
+
+            if let Some(result) = self.execute() {
+                return (Complete(result), executed + 1);
+            }
+        }
+        (Running(Arc::new(self)), budget)
+    }
+
+    // Runs one instruction and returns the result once the outermost frame
+    // has returned
+    fn execute(&mut self) -> Option<RuntimeExpression> {
+        let frame = self.frame();
+        let chunk = frame.chunk.clone();
+        let instruction = chunk.code[frame.ip];
+        frame.ip += 1;
+
+        match instruction {
+            Instruction::Constant(index) => self.stack.push(chunk.constants[index].clone()),
+            Instruction::LoadName(index) | Instruction::LoadLocal(_, index) => {
+                let value = self.lookup(&chunk.constants[index]);
+                self.stack.push(value.unwrap_or_else(|| {
+                    let (name, span) = name_and_span(&chunk.constants[index]);
+                    completed(with_location(
+                        not_found_error(&format!("{} not found", name)),
+                        span,
+                    ))
+                }));
+            }
+            Instruction::Callee(tag, span, after_call) => {
+                match self.lookup(&chunk.constants[tag]) {
+                    Some(function) => self.stack.push(function),
+                    None => {
+                        let (name, _) = name_and_span(&chunk.constants[tag]);
+                        let error = not_found_error(&format!("No function '{}' found", name));
+                        self.stack
+                            .push(completed(with_location(error, &chunk.spans[span])));
+                        self.frame().ip = after_call;
+                    }
+                }
+            }
+            Instruction::Call(count, span) => self.call(count, &chunk.spans[span], false),
+            Instruction::TailCall(count, span) => self.call(count, &chunk.spans[span], true),
+            Instruction::List(count) => {
+                let values = self.pop(count);
+                self.stack.push(RuntimeExpression::List(values));
+            }
+            Instruction::Tuple(tag, count, span) => {
+                let values = self.pop(count);
+                let tag = Arc::new(chunk.constants[tag].clone());
+                self.stack
+                    .push(TaggedTuple(tag, values, chunk.spans[span].clone()));
+            }
+            Instruction::Eval(index) => {
+                let env = self.frame().env.clone();
+                self.waiting_on = Some(eval(chunk.constants[index].clone(), env));
+            }
+            Instruction::Match(table) => {
+                let value = self.stack.pop().unwrap();
+                let table = &chunk.matches[table];
+                let frame = self.frame();
+                let mut bindings = vec![];
+                let case = table.cases.iter().find(|case| {
+                    bindings.clear();
+                    does_match(&case.pattern, &value, &mut bindings)
+                });
+                match case {
+                    Some(case) => {
+                        let (names, values): (Vec<_>, Vec<_>) = bindings.into_iter().unzip();
+                        let env = frame.env.clone().push_frame(names.into(), values);
+                        frame.scopes.push(std::mem::replace(&mut frame.env, env));
+                        frame.ip = case.body;
+                    }
+                    None => {
+                        frame.ip = table.end;
+                        self.stack.push(completed(error("no-match-found")));
+                    }
+                }
+            }
+            Instruction::EndScope => {
+                let frame = self.frame();
+                frame.env = frame.scopes.pop().unwrap();
+            }
+            Instruction::Jump(target) => self.frame().ip = target,
+            Instruction::Pop => {
+                self.stack.pop();
+            }
+            Instruction::Return => {
+                let result = self.stack.pop().unwrap();
+                let frame = self.frames.pop().unwrap();
+                if self.frames.is_empty() {
+                    return Some(result);
+                }
+                self.stack.truncate(frame.base);
+                self.stack.push(result);
+            }
+        }
+        None
+    }
+
+    fn lookup(&mut self, expression: &RuntimeExpression) -> Option<RuntimeExpression> {
+        let env = &self.frame().env;
+        match expression {
+            Local(_, slot, _) => env.get_slot(*slot),
+            Symbol(name, _) => env.get(name),
+            _ => None,
+        }
+    }
+
+    fn pop(&mut self, count: usize) -> Vector<RuntimeExpression> {
+        let at = self.stack.len() - count;
+        self.stack.drain(at..).collect()
+    }
+
+    // Functions written in Nana run on the VM. Anything else is applied the
+    // same way eval applies it.
+    fn call(&mut self, count: usize, span: &crate::span::Span, tail: bool) {
+        let args = self.pop(count);
+        let function = self.stack.pop().unwrap();
+
+        match function {
+            Function(ref name, ref params, _, _) if !params.arity().accepts(args.len()) => {
+                let error = arity_error(&describe(name, &function), params.arity(), args.len());
+                self.stack.push(completed(with_location(error, span)));
+            }
+            Function(_, params, env, body) => {
+                let frame = Frame {
+                    chunk: body.compiled(),
+                    ip: 0,
+                    env: env.push_frame(params.names(), params.bind(args)),
+                    scopes: vec![],
+                    base: self.stack.len(),
+                };
+                if tail {
+                    let caller = self.frames.pop().unwrap();
+                    self.stack.truncate(caller.base);
+                    self.frames.push(Frame {
+                        base: caller.base,
+                        ..frame
+                    });
+                } else {
+                    self.frames.push(frame);
+                }
+            }
+            function => match with_location(apply(function, args), span) {
+                Complete(value) => self.stack.push(value),
+                process => self.waiting_on = Some(process),
+            },
+        }
+    }
+}
+
+fn name_and_span(expression: &RuntimeExpression) -> (&str, &crate::span::Span) {
+    match expression {
+        Symbol(name, span) | Local(name, _, span) => (name, span),
+        _ => unreachable!("Only symbols are looked up"),
+    }
+}
+
+// Errors are made as processes that have already completed
+fn completed(process: Process<RuntimeExpression>) -> RuntimeExpression {
+    match process {
+        Complete(value) => value,
+        _ => unreachable!("Errors complete immediately"),
+    }
+}
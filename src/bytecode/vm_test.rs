@@ -0,0 +1,126 @@
+use crate::bytecode::vm::{run, INSTRUCTIONS_PER_STEP};
+use crate::eval::Backend;
+use crate::expressions::{print, RuntimeExpression};
+use crate::interpreter::Interpreter;
+use crate::parsers::diagnostics::parse_program;
+use crate::parsers::macros::build_macros;
+use crate::parsers::nana::{input, input_from_file, program};
+use crate::process::Process::{Complete, Running};
+use crate::standard_library::core::standard_library;
+
+// Every result and spawned result, printed so functions can be compared
+fn run_on(backend: Backend, file: &str) -> Vec<String> {
+    let code = std::fs::read_to_string(file).unwrap();
+    let mut interpreter = Interpreter::new();
+    interpreter.set_backend(backend);
+    let (results, spawned) = interpreter
+        .run(parse_program(input_from_file(&code, file)))
+        .unwrap();
+    results
+        .into_iter()
+        .chain(spawned.into_iter().flatten())
+        .map(print)
+        .collect()
+}
+
+#[test]
+fn test_backends_agree_on_the_examples() {
+    for file in [
+        "examples/learn_x_in_y_minutes.nana",
+        "examples/mailboxes.nana",
+        "examples/shadowing.nana",
+        "examples/spawn.nana",
+        "examples/standard_library.nana",
+        "examples/supervisor.nana",
+        "examples/vararg_macros.nana",
+    ] {
+        assert_eq!(
+            run_on(Backend::TreeWalking, file),
+            run_on(Backend::Bytecode, file),
+            "{}",
+            file
+        );
+    }
+}
+
+#[test]
+fn test_backends_agree_on_errors() {
+    let code = r#"
+    Func pair [a b]
+      [a b]
+
+    pair(1)
+    missing(1)
+    [pair(1 2) nothing]
+    add(1 :two)
+    Match 3 [1 :one]
+    Fn [a] Match a [[b] b]
+    :tagged(1 pair(2 3))"#;
+
+    let results = |backend| {
+        let mut interpreter = Interpreter::new();
+        interpreter.set_backend(backend);
+        interpreter
+            .eval_str(code)
+            .unwrap()
+            .into_iter()
+            .map(print)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(results(Backend::TreeWalking), results(Backend::Bytecode));
+}
+
+#[test]
+fn test_tail_calls_run_in_constant_space() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_backend(Backend::Bytecode);
+    let results = interpreter
+        .eval_str(
+            "
+            Func count [n]
+              Match n
+                [0 :done
+                 _ count(decrement(n))]
+            count(100000)",
+        )
+        .unwrap();
+    assert_eq!(print(results.last().unwrap().clone()), ":done");
+}
+
+#[test]
+fn test_the_vm_yields_so_other_processes_get_a_turn() {
+    let mut interpreter = Interpreter::new();
+    interpreter
+        .eval_str(
+            "
+            Func count [n]
+              Match n
+                [0 :done
+                 _ count(decrement(n))]",
+        )
+        .unwrap();
+    let env = interpreter.environment().clone();
+
+    let (_, expressions) = program(input(&format!("count({})", INSTRUCTIONS_PER_STEP))).unwrap();
+    let (call, _) = build_macros(&expressions, &env);
+    let process = run(&call.unwrap(), env);
+
+    assert!(matches!(process, Running(_)));
+    assert_eq!(
+        process.run_once_until_complete(),
+        RuntimeExpression::Keyword("done".to_owned())
+    );
+}
+
+#[test]
+fn test_top_level_values_compile_to_constants() {
+    let env = standard_library();
+    let (_, expressions) = program(input("[1 :two \"three\"]")).unwrap();
+    let (list, _) = build_macros(&expressions, &env);
+    let process = run(&list.unwrap(), env);
+
+    let Complete(value) = process else {
+        panic!("A short form should finish in one step");
+    };
+    assert_eq!(print(value), "[1 :two \"three\"]");
+}
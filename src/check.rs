@@ -0,0 +1,111 @@
+use im::{vector, Vector};
+
+use crate::errors::is_error;
+use crate::eval::{eval, macro_expand};
+use crate::expressions::RuntimeExpression::{
+    self, List, Local, Macro, MacroCall, String as NString, Symbol, TaggedTuple,
+};
+use crate::expressions::{is_comment, Environment, LexicalExpression};
+use crate::parsers::macros::build_macros;
+
+// Finds the mistakes in a program that can be spotted without running it:
+// macro calls to macros that don't exist or that are missing arguments, and
+// tuples with invalid tags. Syntax errors are found earlier by parse_program.
+//
+// Macros have to be known before calls to them can be built, so definitions of
+// new macros are expanded and evaluated along the way. Nothing else is run.
+pub fn check_program(
+    expressions: Vector<LexicalExpression>,
+    env: Environment,
+) -> Vector<RuntimeExpression> {
+    let mut work: Vector<LexicalExpression> =
+        expressions.into_iter().filter(|e| !is_comment(e)).collect();
+    let mut env = env;
+    let mut errors = vector![];
+
+    while !work.is_empty() {
+        let (form, remaining_work) = build_macros(&work, &env);
+        work = remaining_work;
+
+        if let Some(form) = form {
+            collect_errors(&form, &mut errors);
+            env = define_macro(form, env);
+        }
+    }
+
+    errors
+}
+
+fn collect_errors(expression: &RuntimeExpression, errors: &mut Vector<RuntimeExpression>) {
+    match expression {
+        error if is_error(error) => errors.push_back(error.clone()),
+        TaggedTuple(tag, values, _) => {
+            collect_errors(tag, errors);
+            values
+                .iter()
+                .for_each(|value| collect_errors(value, errors));
+        }
+        List(values) | MacroCall(_, values, _) => {
+            values
+                .iter()
+                .for_each(|value| collect_errors(value, errors));
+        }
+        _ => {}
+    }
+}
+
+fn is_call_to(expression: &RuntimeExpression, macro_name: &str) -> bool {
+    matches!(expression, MacroCall(name, _, _) if name == macro_name)
+}
+
+// Unquoting a name just splices in what it's bound to, but unquoting anything
+// else would run it
+fn only_unquotes_names(expression: &RuntimeExpression) -> bool {
+    match expression {
+        MacroCall(name, args, _) if name == "Unquote" => {
+            args.iter().all(|arg| matches!(arg, Symbol(..) | Local(..)))
+        }
+        TaggedTuple(tag, values, _) => {
+            only_unquotes_names(tag) && values.iter().all(only_unquotes_names)
+        }
+        List(values) | MacroCall(_, values, _) => values.iter().all(only_unquotes_names),
+        _ => true,
+    }
+}
+
+fn is_quoting_macro(expression: &RuntimeExpression) -> bool {
+    matches!(expression, Macro(_, _, _, body)
+        if body.iter().all(|e| is_call_to(e, "Quote") && only_unquotes_names(e)))
+}
+
+// If form defines a macro, possibly by way of other macros like Defmacro, the
+// returned environment includes it.
+fn define_macro(form: RuntimeExpression, env: Environment) -> Environment {
+    match form {
+        MacroCall(name, mut args, _) if name == "Def" && args.len() == 2 => {
+            let value = args.pop_back().unwrap();
+            match (args.pop_front().unwrap(), value) {
+                (Symbol(name, _) | NString(name), value) if is_call_to(&value, "Macro") => {
+                    match eval(value, env.clone()).run_once_until_complete() {
+                        a_macro @ Macro(..) => env.add(name, a_macro),
+                        _ => env,
+                    }
+                }
+                _ => env,
+            }
+        }
+        // Only macros that just quote some code, and unquote nothing but
+        // their arguments, are expanded. Other macros run code as they
+        // expand, which check promises not to do.
+        MacroCall(name, args, _) => match env.get(&name) {
+            Some(a_macro) if is_quoting_macro(&a_macro) => {
+                match macro_expand(a_macro, args, env.clone()).run_once_until_complete() {
+                    expanded @ MacroCall(..) => define_macro(expanded, env),
+                    _ => env,
+                }
+            }
+            _ => env,
+        },
+        _ => env,
+    }
+}
@@ -0,0 +1,41 @@
+use crate::check::check_program;
+use crate::errors::error_variety;
+use crate::parsers::diagnostics::parse_program;
+use crate::parsers::nana::input;
+use crate::standard_library::core::standard_library;
+
+fn check(code: &str) -> Vec<String> {
+    let expressions = parse_program(input(code)).unwrap();
+    check_program(expressions, standard_library())
+        .iter()
+        .map(|error| error_variety(error).unwrap().to_owned())
+        .collect()
+}
+
+#[test]
+fn test_correct_programs_have_no_errors() {
+    assert!(check("Func add-one [n] add(n 1)\nadd-one(1)").is_empty());
+}
+
+#[test]
+fn test_unknown_and_incomplete_macros_are_found() {
+    assert_eq!(check("[Nope 1]"), vec!["not-found"]);
+    assert_eq!(check("Func add-one [n]"), vec!["missing-macro-arguments"]);
+}
+
+#[test]
+fn test_macros_defined_by_the_program_are_known() {
+    let code = "Defmacro \"Twice\" [form] Quote [Unquote form Unquote form]\nTwice 1";
+    assert!(check(code).is_empty());
+
+    let code = "Def \"Ignore\" Macro [form] :ignored\nIgnore 1";
+    assert!(check(code).is_empty());
+}
+
+#[test]
+fn test_code_is_not_run() {
+    assert!(check("log(missing)\npanic(:at-check-time)").is_empty());
+
+    let code = "Defmacro \"Loud\" [] Quote Def Unquote panic(:at-check-time) 1\nLoud";
+    assert!(check(code).is_empty());
+}
@@ -0,0 +1,146 @@
+use std::fs;
+
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+use crate::check::check_program;
+use crate::expressions::print;
+use crate::parsers::diagnostics::parse_program;
+use crate::parsers::nana::input_from_file;
+use crate::run::run_program;
+use crate::session::Session;
+use crate::standard_library::core::standard_library;
+
+const USAGE: &str = "Usage:
+  nana run <file> [args...]   Run a program. Its arguments are bound to argv
+  nana repl                   Start an interactive session
+  nana check <file>...        Look for mistakes without running anything";
+
+// Runs the nana command with the arguments that followed the program's name
+// and returns the exit code.
+pub fn main(args: Vec<String>) -> i32 {
+    match args.split_first() {
+        Some((command, rest)) if command == "run" && !rest.is_empty() => run(&rest[0], &rest[1..]),
+        Some((command, rest)) if command == "check" && !rest.is_empty() => {
+            rest.iter().map(|file| check(file)).max().unwrap_or(0)
+        }
+        Some((command, rest)) if command == "repl" && rest.is_empty() => repl(),
+        _ => {
+            eprintln!("{}", USAGE);
+            2
+        }
+    }
+}
+
+fn read_file(file: &str) -> Option<String> {
+    fs::read_to_string(file)
+        .map_err(|error| eprintln!("Couldn't read {}: {}", file, error))
+        .ok()
+}
+
+fn run(file: &str, args: &[String]) -> i32 {
+    match read_file(file) {
+        Some(code) if run_program(file, &code, args) => 0,
+        _ => 1,
+    }
+}
+
+fn check(file: &str) -> i32 {
+    let Some(code) = read_file(file) else {
+        return 1;
+    };
+    let expressions = match parse_program(input_from_file(&code, file)) {
+        Ok(expressions) => expressions,
+        Err(diagnostics) => {
+            diagnostics.iter().for_each(|d| eprintln!("{}", d));
+            return 1;
+        }
+    };
+
+    let errors = check_program(expressions, standard_library());
+    errors
+        .iter()
+        .for_each(|error| eprintln!("{}", print(error.clone())));
+
+    if errors.is_empty() {
+        0
+    } else {
+        1
+    }
+}
+
+fn history_file() -> Option<String> {
+    std::env::var("HOME")
+        .ok()
+        .map(|home| format!("{}/.nana_history", home))
+}
+
+// Each entry is read until it holds complete forms, so a form can be spread
+// over several lines just like in a file.
+fn repl() -> i32 {
+    let mut editor = match DefaultEditor::new() {
+        Ok(editor) => editor,
+        Err(error) => {
+            eprintln!("Couldn't start the repl: {}", error);
+            return 1;
+        }
+    };
+    let history = history_file();
+    if let Some(history) = &history {
+        let _ = editor.load_history(history);
+    }
+
+    let session = Session::new();
+    let mut entry = String::new();
+
+    loop {
+        let prompt = if entry.is_empty() { "nana> " } else { "  ... " };
+        match editor.readline(prompt) {
+            Ok(line) => {
+                entry.push_str(&line);
+                entry.push('\n');
+                if entry.trim().is_empty() {
+                    entry.clear();
+                    continue;
+                }
+                if session.needs_more_input(&entry) {
+                    continue;
+                }
+
+                let _ = editor.add_history_entry(entry.trim_end());
+                evaluate_entry(&session, &entry);
+                entry.clear();
+            }
+            // Ctrl-C abandons the current entry, Ctrl-D leaves
+            Err(ReadlineError::Interrupted) => entry.clear(),
+            Err(ReadlineError::Eof) => break,
+            Err(error) => {
+                eprintln!("{}", error);
+                break;
+            }
+        }
+    }
+
+    if let Some(history) = &history {
+        let _ = editor.save_history(history);
+    }
+    0
+}
+
+fn evaluate_entry(session: &Session, entry: &str) {
+    match session.evaluate(entry) {
+        Ok(evaluation) => {
+            evaluation.logs.iter().for_each(|line| println!("{}", line));
+            evaluation
+                .results
+                .into_iter()
+                .for_each(|result| println!("{}", print(result)));
+            evaluation
+                .spawned
+                .into_iter()
+                .flatten()
+                .for_each(|result| println!("spawned: {}", print(result)));
+        }
+        Err(diagnostics) => diagnostics.iter().for_each(|d| eprintln!("{}", d)),
+    }
+}
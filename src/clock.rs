@@ -0,0 +1,100 @@
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[cfg(all(target_arch = "wasm32", feature = "component"))]
+use crate::bindings::wasi::clocks::monotonic_clock;
+
+// Where the scheduler and builtins like sleep get the time from. Times are
+// how long it's been since some fixed point, so they only go forward. Like
+// output sinks, each thread runs with a clock of its own.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Duration;
+    // Returns once the time is at least deadline
+    fn sleep_until(&self, deadline: Duration);
+}
+
+// The host's monotonic clock. The component gets it from wasi:clocks.
+pub struct SystemClock;
+
+#[cfg(all(target_arch = "wasm32", feature = "component"))]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        Duration::from_nanos(monotonic_clock::now())
+    }
+
+    fn sleep_until(&self, deadline: Duration) {
+        let deadline = u64::try_from(deadline.as_nanos()).unwrap_or(u64::MAX);
+        monotonic_clock::subscribe_instant(deadline).block()
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", feature = "component")))]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+        START.get_or_init(std::time::Instant::now).elapsed()
+    }
+
+    fn sleep_until(&self, deadline: Duration) {
+        std::thread::sleep(deadline.saturating_sub(self.now()))
+    }
+}
+
+// Only moves when it's advanced, or when something sleeps until a later time,
+// which it jumps to straight away. Tests use it so timers don't make them slow
+// or flaky.
+#[derive(Clone, Default)]
+pub struct VirtualClock(Arc<Mutex<Duration>>);
+
+impl VirtualClock {
+    pub fn advance(&self, by: Duration) {
+        *self.0.lock().unwrap() += by
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Duration {
+        *self.0.lock().unwrap()
+    }
+
+    fn sleep_until(&self, deadline: Duration) {
+        let mut now = self.0.lock().unwrap();
+        *now = (*now).max(deadline)
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Arc<dyn Clock>> = RefCell::new(Arc::new(SystemClock));
+}
+
+// Restores the previous clock when dropped, so a panicking evaluation doesn't
+// leave its clock installed
+struct Restore(Option<Arc<dyn Clock>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            CURRENT.with(|current| current.replace(previous));
+        }
+    }
+}
+
+// Runs f with clock telling the time instead of the system clock
+pub fn with_clock<T>(clock: Arc<dyn Clock>, f: impl FnOnce() -> T) -> T {
+    let _restore = Restore(Some(CURRENT.with(|current| current.replace(clock))));
+    f()
+}
+
+// The clock the current thread is telling the time with
+pub fn current_clock() -> Arc<dyn Clock> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+pub fn now() -> Duration {
+    current_clock().now()
+}
+
+pub fn sleep_until(deadline: Duration) {
+    current_clock().sleep_until(deadline)
+}
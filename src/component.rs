@@ -0,0 +1,214 @@
+use std::fs;
+use std::io::{self, Read};
+use std::time::Duration;
+
+use im::Vector;
+
+use crate::bindings::exports::component::nana::nana::{
+    Diagnostic as WitDiagnostic, ErrorValue, Evaluation as WitEvaluation, Guest as Nana,
+    GuestSession, Limits, TaggedTuple as WitTaggedTuple, Value, ValueIndex,
+};
+use crate::bindings::exports::wasi::cli::run::Guest as Command;
+use crate::expressions::print;
+use crate::expressions::RuntimeExpression::{
+    self, BuiltinFunction, BuiltinMacro, Definition, Function, Handle, Hole, Keyword, List, Local,
+    Macro, MacroCall, Number, String as NString, Symbol, TaggedTuple,
+};
+use crate::parsers::diagnostics::Diagnostic;
+use crate::process::Fuel;
+use crate::run::run_program;
+use crate::s;
+use crate::session::{Evaluation, Session};
+
+struct Component;
+
+// Runs the file named by the first argument, or the program read from stdin
+// when there are no arguments. Under WASI, std's filesystem and stdio are
+// backed by wasi:filesystem preopens and wasi:cli/stdin, so the host decides
+// which directories a program can be read from.
+impl Command for Component {
+    fn run() -> Result<(), ()> {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        let (file, code, program_args) = match args.split_first() {
+            Some((file, program_args)) => match fs::read_to_string(file) {
+                Ok(code) => (file.clone(), code, program_args),
+                Err(error) => {
+                    eprintln!("Couldn't read {}: {}", file, error);
+                    return Err(());
+                }
+            },
+            None => {
+                let mut code = String::new();
+                if let Err(error) = io::stdin().read_to_string(&mut code) {
+                    eprintln!("Couldn't read stdin: {}", error);
+                    return Err(());
+                }
+                (s!("<stdin>"), code, &[][..])
+            }
+        };
+
+        if run_program(&file, &code, program_args) {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl Nana for Component {
+    type Session = Session;
+
+    fn evaluate(code: String) -> WitEvaluation {
+        to_wit_evaluation(Session::new().evaluate(&code))
+    }
+
+    fn evaluate_with_limits(code: String, limits: Limits) -> WitEvaluation {
+        to_wit_evaluation(Session::new().evaluate_with_fuel(&code, to_fuel(limits)))
+    }
+}
+
+impl GuestSession for Session {
+    fn new() -> Self {
+        Session::new()
+    }
+
+    fn evaluate(&self, code: String) -> WitEvaluation {
+        to_wit_evaluation(Session::evaluate(self, &code))
+    }
+
+    fn evaluate_with_limits(&self, code: String, limits: Limits) -> WitEvaluation {
+        to_wit_evaluation(Session::evaluate_with_fuel(self, &code, to_fuel(limits)))
+    }
+
+    fn reset(&self) {
+        Session::reset(self)
+    }
+
+    fn bindings(&self) -> Vec<String> {
+        Session::bindings(self)
+    }
+}
+
+// The exported symbols' names can't be linked into native libraries, so they
+// only exist when building the component itself
+#[cfg(target_arch = "wasm32")]
+crate::bindings::export!(Component with_types_in crate::bindings);
+
+pub fn to_fuel(limits: Limits) -> Fuel {
+    Fuel {
+        max_steps: limits.max_steps,
+        time_limit: limits.time_limit_ms.map(Duration::from_millis),
+    }
+}
+
+pub fn to_wit_evaluation(evaluation: Result<Evaluation, Vector<Diagnostic>>) -> WitEvaluation {
+    let mut values = Values::default();
+
+    match evaluation {
+        Ok(evaluation) => WitEvaluation {
+            results: values.add_all(evaluation.results),
+            spawned: values.add_all(evaluation.spawned.into_iter().flatten().collect()),
+            values: values.0,
+            logs: evaluation.logs,
+            diagnostics: vec![],
+        },
+        Err(diagnostics) => WitEvaluation {
+            values: vec![],
+            results: vec![],
+            spawned: vec![],
+            logs: vec![],
+            diagnostics: diagnostics.iter().map(to_wit_diagnostic).collect(),
+        },
+    }
+}
+
+fn to_wit_diagnostic(diagnostic: &Diagnostic) -> WitDiagnostic {
+    WitDiagnostic {
+        message: diagnostic.message(),
+        location: diagnostic.span.to_string(),
+        line: diagnostic.span.line,
+        column: diagnostic.span.column as u32,
+        snippet: diagnostic.snippet.clone(),
+    }
+}
+
+// The flat list of values sent with an evaluation. Items are added before the
+// value that holds them, so every index points backwards.
+#[derive(Default)]
+struct Values(Vec<Value>);
+
+impl Values {
+    fn add_all(&mut self, expressions: Vector<RuntimeExpression>) -> Vec<ValueIndex> {
+        expressions
+            .into_iter()
+            .map(|expression| self.add(expression))
+            .collect()
+    }
+
+    fn add(&mut self, expression: RuntimeExpression) -> ValueIndex {
+        let value = match expression {
+            Number(number) => Value::Number(number.to_string()),
+            NString(string) => Value::String(string),
+            Keyword(name) => Value::Keyword(name),
+            Symbol(name, _span) | Local(name, _, _span) => Value::Symbol(name),
+            Hole(_span) => Value::Symbol(s!("_")),
+            List(items) => Value::List(self.add_all(items)),
+            TaggedTuple(tag, values, _span) if *tag == Keyword(s!("error")) => {
+                Value::Error(self.error_value(values))
+            }
+            TaggedTuple(tag, values, _span) => Value::TaggedTuple(WitTaggedTuple {
+                tag: self.add((*tag).clone()),
+                values: self.add_all(values),
+            }),
+            MacroCall(name, args, span) => Value::TaggedTuple(WitTaggedTuple {
+                tag: self.add(Symbol(name, span)),
+                values: self.add_all(args),
+            }),
+            Definition(_name, value) => return self.add((*value).clone()),
+            Handle(handle) => Value::Process(handle.id() as u64),
+            function @ (BuiltinFunction(..) | Function(..) | BuiltinMacro(..) | Macro(..)) => {
+                Value::FunctionRef(print(function))
+            }
+        };
+
+        self.0.push(value);
+        (self.0.len() - 1) as ValueIndex
+    }
+
+    fn error_value(&mut self, mut values: Vector<RuntimeExpression>) -> ErrorValue {
+        let variety = match values.front() {
+            Some(Keyword(variety)) => {
+                let variety = variety.clone();
+                values.pop_front();
+                variety
+            }
+            _ => s!("unknown"),
+        };
+        let message = match values.front() {
+            Some(NString(message)) => {
+                let message = message.clone();
+                values.pop_front();
+                Some(message)
+            }
+            _ => None,
+        };
+        let location = match values.back() {
+            Some(TaggedTuple(tag, at, _span)) if **tag == Keyword(s!("at")) => match at.front() {
+                Some(NString(location)) => {
+                    let location = location.clone();
+                    values.pop_back();
+                    Some(location)
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+
+        ErrorValue {
+            variety,
+            message,
+            location,
+            details: self.add_all(values),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::bindings::exports::component::nana::nana::Value;
+use crate::component::to_wit_evaluation;
+use crate::session::Session;
+
+#[test]
+fn test_values_are_flattened() {
+    let session = Session::new();
+    let evaluation = to_wit_evaluation(session.evaluate("[1 :two(\"three\")]"));
+
+    assert_eq!(evaluation.results.len(), 1);
+    let Value::List(items) = &evaluation.values[evaluation.results[0] as usize] else {
+        panic!("Expected a list")
+    };
+    assert!(matches!(&evaluation.values[items[0] as usize], Value::Number(n) if n == "1"));
+    let Value::TaggedTuple(tuple) = &evaluation.values[items[1] as usize] else {
+        panic!("Expected a tagged tuple")
+    };
+    assert!(matches!(&evaluation.values[tuple.tag as usize], Value::Keyword(k) if k == "two"));
+    assert!(
+        matches!(&evaluation.values[tuple.values[0] as usize], Value::String(s) if s == "three")
+    );
+}
+
+#[test]
+fn test_errors_are_picked_apart() {
+    let session = Session::new();
+    let evaluation = to_wit_evaluation(session.evaluate("\n  missing"));
+
+    let Value::Error(error) = &evaluation.values[evaluation.results[0] as usize] else {
+        panic!("Expected an error")
+    };
+    assert_eq!(error.variety, "not-found");
+    assert!(error.message.is_some());
+    assert_eq!(error.location.as_deref(), Some("2:3"));
+    assert!(error.details.is_empty());
+}
+
+#[test]
+fn test_spawned_results_and_diagnostics_are_separate() {
+    let session = Session::new();
+    let evaluation = to_wit_evaluation(session.evaluate("spawn(Fn [] :child)\n:parent"));
+    assert_eq!(evaluation.results.len(), 2);
+    assert_eq!(evaluation.spawned.len(), 1);
+    assert!(evaluation.diagnostics.is_empty());
+    assert!(matches!(
+        &evaluation.values[evaluation.results[0] as usize],
+        Value::Process(_)
+    ));
+
+    let evaluation = to_wit_evaluation(session.evaluate("[1 2"));
+    assert!(evaluation.results.is_empty());
+    assert_eq!(evaluation.diagnostics.len(), 1);
+    assert_eq!(evaluation.diagnostics[0].line, 1);
+}
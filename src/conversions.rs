@@ -0,0 +1,83 @@
+use crate::expressions::RuntimeExpression::{self, Keyword, List, Number, String as NString};
+
+// Conversions between Nana values and Rust types, for passing values to and
+// from an Interpreter. Converting back fails with the original value when it
+// isn't the type that was asked for.
+
+impl From<u128> for RuntimeExpression {
+    fn from(value: u128) -> Self {
+        Number(value)
+    }
+}
+
+impl From<String> for RuntimeExpression {
+    fn from(value: String) -> Self {
+        NString(value)
+    }
+}
+
+impl From<&str> for RuntimeExpression {
+    fn from(value: &str) -> Self {
+        NString(value.to_owned())
+    }
+}
+
+// Nana has no booleans, by convention :true and :false are used instead
+impl From<bool> for RuntimeExpression {
+    fn from(value: bool) -> Self {
+        Keyword(value.to_string())
+    }
+}
+
+impl<T: Into<RuntimeExpression>> From<Vec<T>> for RuntimeExpression {
+    fn from(values: Vec<T>) -> Self {
+        List(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl TryFrom<RuntimeExpression> for u128 {
+    type Error = RuntimeExpression;
+
+    fn try_from(value: RuntimeExpression) -> Result<Self, Self::Error> {
+        match value {
+            Number(number) => Ok(number),
+            other => Err(other),
+        }
+    }
+}
+
+impl TryFrom<RuntimeExpression> for String {
+    type Error = RuntimeExpression;
+
+    fn try_from(value: RuntimeExpression) -> Result<Self, Self::Error> {
+        match value {
+            NString(string) => Ok(string),
+            other => Err(other),
+        }
+    }
+}
+
+impl TryFrom<RuntimeExpression> for bool {
+    type Error = RuntimeExpression;
+
+    fn try_from(value: RuntimeExpression) -> Result<Self, Self::Error> {
+        match value {
+            Keyword(name) if name == "true" => Ok(true),
+            Keyword(name) if name == "false" => Ok(false),
+            other => Err(other),
+        }
+    }
+}
+
+impl<T: TryFrom<RuntimeExpression, Error = RuntimeExpression>> TryFrom<RuntimeExpression>
+    for Vec<T>
+{
+    type Error = RuntimeExpression;
+
+    fn try_from(value: RuntimeExpression) -> Result<Self, Self::Error> {
+        match value {
+            List(values) => values.into_iter().map(T::try_from).collect(),
+            other => Err(other),
+        }
+    }
+}
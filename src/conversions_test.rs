@@ -0,0 +1,42 @@
+use im::vector;
+
+use crate::expressions::RuntimeExpression::{self, Keyword, List, Number, String as NString};
+use crate::s;
+
+#[test]
+fn test_rust_values_convert_to_nana() {
+    assert_eq!(RuntimeExpression::from(7), Number(7));
+    assert_eq!(RuntimeExpression::from("seven"), NString(s!("seven")));
+    assert_eq!(RuntimeExpression::from(true), Keyword(s!("true")));
+    assert_eq!(
+        RuntimeExpression::from(vec![1, 2]),
+        List(vector![Number(1), Number(2)])
+    );
+}
+
+#[test]
+fn test_nana_values_convert_to_rust() {
+    assert_eq!(u128::try_from(Number(7)), Ok(7));
+    assert_eq!(String::try_from(NString(s!("seven"))), Ok(s!("seven")));
+    assert_eq!(bool::try_from(Keyword(s!("false"))), Ok(false));
+    assert_eq!(
+        Vec::<u128>::try_from(List(vector![Number(1), Number(2)])),
+        Ok(vec![1, 2])
+    );
+}
+
+#[test]
+fn test_failed_conversions_return_the_value() {
+    assert_eq!(
+        u128::try_from(Keyword(s!("seven"))),
+        Err(Keyword(s!("seven")))
+    );
+    assert_eq!(
+        bool::try_from(Keyword(s!("maybe"))),
+        Err(Keyword(s!("maybe")))
+    );
+    assert_eq!(
+        Vec::<u128>::try_from(List(vector![Number(1), Keyword(s!("two"))])),
+        Err(Keyword(s!("two")))
+    );
+}
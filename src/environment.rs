@@ -1,58 +1,159 @@
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 
 use im::HashMap;
 
-use crate::expressions::RuntimeExpression;
+use crate::expressions::{Builtin, RuntimeExpression};
 
+// Where a local variable lives: how many frames out from the innermost one,
+// and its position in that frame
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Slot {
+    pub depth: usize,
+    pub index: usize,
+}
+
+// The locals bound by one function call or Match arm. Code that has been
+// through resolve finds them by slot, anything else by name.
+#[derive(PartialEq, Debug)]
+struct Frame {
+    names: Arc<[String]>,
+    values: Vec<RuntimeExpression>,
+    parent: Option<Arc<Frame>>,
+}
+
+// Top level definitions live in a hash map of globals. Calls and matches push
+// frames on top of it, which are cheap to make and to search.
 #[derive(PartialEq, Debug, Clone)]
 pub struct Environment {
-    map: HashMap<String, OnceLock<RuntimeExpression>>,
+    globals: HashMap<String, OnceLock<RuntimeExpression>>,
+    frame: Option<Arc<Frame>>,
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Environment::new()
+    }
 }
 
 impl Environment {
     pub fn new() -> Environment {
         Environment {
-            map: HashMap::new(),
+            globals: HashMap::new(),
+            frame: None,
         }
     }
 
     pub fn from(from: HashMap<String, RuntimeExpression>) -> Environment {
         Environment {
-            map: from.into_iter().fold(HashMap::new(), |acc, (key, value)| {
+            globals: from.into_iter().fold(HashMap::new(), |acc, (key, value)| {
                 acc.update(key, OnceLock::from(value))
             }),
+            frame: None,
         }
     }
 
     pub fn prepare(self, key: String) -> Environment {
         Environment {
-            map: self.map.update(key, OnceLock::new()),
+            globals: self.globals.update(key, OnceLock::new()),
+            ..self
         }
     }
 
     pub fn provide(&self, key: &str, value: RuntimeExpression) -> Option<()> {
-        self.map.get(key).and_then(|lock| lock.set(value).ok())
+        self.globals.get(key).and_then(|lock| lock.set(value).ok())
     }
 
     pub fn add(self, key: String, value: RuntimeExpression) -> Environment {
         Environment {
-            map: self.map.update(key, OnceLock::from(value)),
+            globals: self.globals.update(key, OnceLock::from(value)),
+            ..self
+        }
+    }
+
+    // Adds a function implemented in Rust under its own name
+    pub fn add_builtin(self, builtin: Builtin) -> Environment {
+        self.add(
+            builtin.name.clone(),
+            RuntimeExpression::BuiltinFunction(builtin),
+        )
+    }
+
+    // Adds a native module, a set of related builtins
+    pub fn add_module(self, module: impl IntoIterator<Item = Builtin>) -> Environment {
+        module.into_iter().fold(self, Environment::add_builtin)
+    }
+
+    // Binds each name to the value at the same position. The new frame
+    // shadows anything outside it with the same name.
+    pub fn push_frame(self, names: Arc<[String]>, values: Vec<RuntimeExpression>) -> Environment {
+        Environment {
+            frame: Some(Arc::new(Frame {
+                names,
+                values,
+                parent: self.frame,
+            })),
+            globals: self.globals,
         }
     }
 
     pub fn get(&self, key: &str) -> Option<RuntimeExpression> {
-        self.map.get(key).map(|lock| lock.get()).flatten().cloned()
+        let mut frame = self.frame.as_deref();
+        while let Some(current) = frame {
+            if let Some(index) = current.names.iter().position(|name| name == key) {
+                return current.values.get(index).cloned();
+            }
+            frame = current.parent.as_deref();
+        }
+        self.globals.get(key).and_then(|lock| lock.get()).cloned()
+    }
+
+    pub fn get_slot(&self, slot: Slot) -> Option<RuntimeExpression> {
+        let mut frame = self.frame.as_deref();
+        for _ in 0..slot.depth {
+            frame = frame.and_then(|frame| frame.parent.as_deref());
+        }
+        frame
+            .and_then(|frame| frame.values.get(slot.index))
+            .cloned()
+    }
+
+    // The names bound by each frame, outermost first, which is what resolve
+    // needs to know to turn names into slots
+    pub fn scopes(&self) -> Vec<Arc<[String]>> {
+        let mut scopes = vec![];
+        let mut frame = self.frame.as_deref();
+        while let Some(current) = frame {
+            scopes.push(current.names.clone());
+            frame = current.parent.as_deref();
+        }
+        scopes.reverse();
+        scopes
     }
 
+    // Combines the globals of both. Where both define a name which one wins
+    // depends on their sizes, so this is only for putting together
+    // environments that don't overlap.
     pub fn union(self, other: Environment) -> Environment {
         Environment {
-            map: self.map.union(other.map),
+            globals: self.globals.union(other.globals),
+            ..self
         }
     }
 
+    // Every global definition followed by every local, innermost first
     pub fn iter<'a>(&'a self) -> impl Iterator<Item = (String, RuntimeExpression)> + 'a {
-        self.map
+        let globals = self
+            .globals
             .iter()
-            .filter_map(|(key, lock)| lock.get().map(|value| (key.clone(), value.clone())))
+            .filter_map(|(key, lock)| lock.get().map(|value| (key.clone(), value.clone())));
+        let locals = std::iter::successors(self.frame.as_deref(), |frame| frame.parent.as_deref())
+            .flat_map(|frame| {
+                frame
+                    .names
+                    .iter()
+                    .cloned()
+                    .zip(frame.values.iter().cloned())
+            });
+        globals.chain(locals)
     }
 }
@@ -1,4 +1,16 @@
-use crate::{environment::Environment, expressions::RuntimeExpression::Number, s};
+use std::sync::{Arc, Mutex};
+
+use im::vector;
+
+use crate::{
+    environment::{Environment, Slot},
+    expressions::{
+        Arity, Builtin,
+        RuntimeExpression::{BuiltinFunction, Number},
+    },
+    process::Process,
+    s,
+};
 
 #[test]
 pub fn test_environment() {
@@ -17,3 +29,50 @@ pub fn test_environment() {
     assert_eq!(env_two.get("two"), Some(Number(2)));
     assert_eq!(env_two.get("three"), None);
 }
+
+#[test]
+pub fn test_native_modules() {
+    let calls = Arc::new(Mutex::new(0));
+    let counted_calls = calls.clone();
+    let module = vec![
+        Builtin::new("count", Arity::Exactly(0), move |_args| {
+            let mut calls = counted_calls.lock().unwrap();
+            *calls += 1;
+            Process::Complete(Number(*calls))
+        }),
+        Builtin::new("ignore", Arity::AtLeast(0), |_args| {
+            Process::Complete(Number(0))
+        }),
+    ];
+
+    let env = Environment::new().add_module(module);
+
+    let Some(BuiltinFunction(count)) = env.get("count") else {
+        panic!("count should be a builtin");
+    };
+    assert_eq!(count.name, "count");
+    assert_eq!(count.arity, Arity::Exactly(0));
+    count.call(vector![]);
+    count.call(vector![]);
+    assert_eq!(*calls.lock().unwrap(), 2);
+    assert!(env.get("ignore").is_some());
+}
+
+#[test]
+pub fn test_frames() {
+    let globals = Environment::new().add(s!("a"), Number(1));
+    let outer = globals.push_frame(
+        Arc::from(vec![s!("a"), s!("b")]),
+        vec![Number(2), Number(3)],
+    );
+    let inner = outer
+        .clone()
+        .push_frame(Arc::from(vec![s!("a")]), vec![Number(4)]);
+
+    assert_eq!(inner.get("a"), Some(Number(4)));
+    assert_eq!(inner.get("b"), Some(Number(3)));
+    assert_eq!(inner.get_slot(Slot { depth: 1, index: 0 }), Some(Number(2)));
+    assert_eq!(inner.get_slot(Slot { depth: 2, index: 0 }), None);
+    assert_eq!(outer.get("a"), Some(Number(2)));
+    assert_eq!(inner.scopes().len(), 2);
+}
@@ -1,17 +1,20 @@
 use std::sync::Arc;
 
-use im::vector;
+use im::{vector, Vector};
 
 use crate::{
+    expressions::Arity,
     expressions::RuntimeExpression::{self, Keyword, String as NString, TaggedTuple},
     process::Process::{self, Complete},
     s,
+    span::Span,
 };
 
 pub fn error(variety: &str) -> Process<RuntimeExpression> {
     Complete(TaggedTuple(
         Arc::new(Keyword(s!("error"))),
         vector![Keyword(s!(variety))],
+        Span::default(),
     ))
 }
 
@@ -19,6 +22,7 @@ pub fn error_with_message(variety: &str, message: &str) -> Process<RuntimeExpres
     Complete(TaggedTuple(
         Arc::new(Keyword(s!("error"))),
         vector![Keyword(s!(variety)), NString(s!(message))],
+        Span::default(),
     ))
 }
 
@@ -29,3 +33,100 @@ pub fn argument_error(message: &str) -> Process<RuntimeExpression> {
 pub fn not_found_error(message: &str) -> Process<RuntimeExpression> {
     error_with_message("not-found", message)
 }
+
+pub fn arithmetic_error(message: &str) -> Process<RuntimeExpression> {
+    error_with_message("arithmetic", message)
+}
+
+// What a run that used up its fuel gives for the form it was running
+pub fn out_of_fuel_error(steps: u64, timed_out: bool) -> RuntimeExpression {
+    let limit = if timed_out { "time" } else { "fuel" };
+    TaggedTuple(
+        Arc::new(Keyword(s!("error"))),
+        vector![
+            Keyword(s!("out-of-fuel")),
+            NString(format!("Ran out of {} after {} steps", limit, steps))
+        ],
+        Span::default(),
+    )
+}
+
+// Why a process that called panic ended, with whatever it was called with
+pub fn panic_error(args: Vector<RuntimeExpression>) -> RuntimeExpression {
+    TaggedTuple(
+        Arc::new(Keyword(s!("error"))),
+        vector![Keyword(s!("panic"))] + args,
+        Span::default(),
+    )
+}
+
+pub fn is_error(expression: &RuntimeExpression) -> bool {
+    matches!(expression, TaggedTuple(tag, _, _) if **tag == Keyword(s!("error")))
+}
+
+// The :variety of an :error(:variety ...) tuple
+pub fn error_variety(expression: &RuntimeExpression) -> Option<&str> {
+    match expression {
+        TaggedTuple(_, values, _) if is_error(expression) => match values.front() {
+            Some(Keyword(variety)) => Some(variety),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn arity_error(name: &str, expected: Arity, actual: usize) -> Process<RuntimeExpression> {
+    let arguments = |n: usize| if n == 1 { "argument" } else { "arguments" };
+    let expected = match expected {
+        Arity::Exactly(n) => format!("{} {}", n, arguments(n)),
+        Arity::AtLeast(n) => format!("at least {} {}", n, arguments(n)),
+        Arity::Between(min, max) => format!("{} to {} arguments", min, max),
+    };
+    error_with_message(
+        "arity",
+        &format!("{} takes {} but was given {}", name, expected, actual),
+    )
+}
+
+fn is_location(expression: &RuntimeExpression) -> bool {
+    match expression {
+        TaggedTuple(tag, _, _) => **tag == Keyword(s!("at")),
+        _ => false,
+    }
+}
+
+fn location(span: &Span) -> RuntimeExpression {
+    TaggedTuple(
+        Arc::new(Keyword(s!("at"))),
+        vector![NString(span.to_string())],
+        Span::default(),
+    )
+}
+
+pub fn located_error(variety: &str, message: &str, span: &Span) -> RuntimeExpression {
+    TaggedTuple(
+        Arc::new(Keyword(s!("error"))),
+        vector![Keyword(s!(variety)), NString(s!(message)), location(span)],
+        Span::default(),
+    )
+}
+
+// Appends :at("file:line:col") to an error that doesn't say where it came from
+// yet. Only processes that have already completed are inspected, wrapping a
+// running process would add a step to every call and break tail calls.
+pub fn with_location(
+    process: Process<RuntimeExpression>,
+    span: &Span,
+) -> Process<RuntimeExpression> {
+    match process {
+        Complete(TaggedTuple(tag, mut values, tuple_span))
+            if span.is_known()
+                && *tag == Keyword(s!("error"))
+                && !values.last().is_some_and(is_location) =>
+        {
+            values.push_back(location(span));
+            Complete(TaggedTuple(tag, values, tuple_span))
+        }
+        process => process,
+    }
+}
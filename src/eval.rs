@@ -2,21 +2,32 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use im::{vector, HashMap, Vector};
+use im::{vector, Vector};
 
-use crate::errors::{error_with_message, not_found_error};
+use crate::errors::{
+    argument_error, arity_error, error_with_message, not_found_error, out_of_fuel_error,
+    with_location,
+};
 use crate::expressions::RuntimeExpression::{
-    self, BuiltinFunction, BuiltinMacro, Definition, Function, Hole, Keyword, List, Macro,
-    MacroCall, Number, String as NString, Symbol, TaggedTuple,
+    self, BuiltinFunction, BuiltinMacro, Definition, Function, Handle, Hole, Keyword, List, Local,
+    Macro, MacroCall, Number, String as NString, Symbol, TaggedTuple,
+};
+use crate::expressions::{
+    is_comment, print, Arity, Environment, LexicalExpression, Params, ProcessHandle,
 };
-use crate::expressions::{is_comment, Environment, LexicalExpression};
+use crate::mailbox::as_process;
 
+use crate::bytecode::vm;
+use crate::parsers::diagnostics::{parse_program, Diagnostic};
 use crate::parsers::macros::build_macros;
-use crate::parsers::nana::program;
-use crate::process::Process::{self, Complete, Running, Spawn};
+use crate::parsers::nana::{input, input_from_file};
+use crate::process::Process::{self, Complete, Running, Spawn, Waiting};
+use crate::process::{RunResult, Stepable};
+use crate::resolve::resolve;
 use crate::s;
+use crate::scheduler::Priority;
 
 pub fn read_code(path: &str) -> String {
     let mut file = File::open(Path::new(path)).unwrap();
@@ -25,22 +36,31 @@ pub fn read_code(path: &str) -> String {
     contents
 }
 
+// How errors refer to a function or macro
+pub fn describe(name: &Option<String>, function: &RuntimeExpression) -> String {
+    match name {
+        Some(name) => name.clone(),
+        None => print(function.clone()),
+    }
+}
+
 pub fn apply(
     function: RuntimeExpression,
     args: Vector<RuntimeExpression>,
 ) -> Process<RuntimeExpression> {
     match function {
-        BuiltinFunction(body) => (body)(args),
-        Function(params, environment, body) => {
-            let new_env = environment.union(Environment::from(
-                params
-                    .iter()
-                    .cloned()
-                    .zip(args.iter().cloned())
-                    .collect::<HashMap<_, _>>(),
-            ));
+        BuiltinFunction(builtin) if !builtin.arity.accepts(args.len()) => {
+            arity_error(&builtin.name, builtin.arity, args.len())
+        }
+        BuiltinFunction(builtin) => builtin.call(args),
+        Function(ref name, ref params, _, _) if !params.arity().accepts(args.len()) => {
+            arity_error(&describe(name, &function), params.arity(), args.len())
+        }
+        Function(_name, params, environment, body) => {
+            let new_env = environment.push_frame(params.names(), params.bind(args));
 
             let eval_body = body
+                .expressions()
                 .iter()
                 .cloned()
                 .map(move |e| {
@@ -53,26 +73,54 @@ pub fn apply(
         }
         _ => error_with_message(
             "invalid-application",
-            "You've called an invalid value as a function",
+            &format!("{} was called but is not a function", print(function)),
         ),
     }
 }
 
+// A macro call can't tell where its arguments end by itself, so a macro with a
+// rest param takes one form for each required param and then a list holding
+// the rest of its forms.
+pub fn macro_form_count(params: &Params) -> usize {
+    params.required().len() + usize::from(params.rest().is_some())
+}
+
+fn is_list(expression: Option<&RuntimeExpression>) -> bool {
+    matches!(expression, Some(List(_)))
+}
+
+fn spread_rest(params: &Params, mut args: Vector<RuntimeExpression>) -> Vector<RuntimeExpression> {
+    match (params.rest(), args.pop_back()) {
+        (Some(_), Some(List(rest))) => args + rest,
+        (_, Some(last)) => {
+            args.push_back(last);
+            args
+        }
+        (_, None) => args,
+    }
+}
+
 pub fn macro_expand(
     macro_expression: RuntimeExpression,
     args: Vector<RuntimeExpression>,
     environment: Environment,
 ) -> Process<RuntimeExpression> {
     match macro_expression {
-        BuiltinMacro(_params, body) => (body)(args, environment),
-        Macro(params, environment, body) => {
-            let new_env = environment.union(Environment::from(
-                params
-                    .iter()
-                    .cloned()
-                    .zip(args.iter().cloned())
-                    .collect::<HashMap<_, _>>(),
-            ));
+        BuiltinMacro(_name, _params, body) => (body)(args, environment),
+        Macro(ref name, ref params, _, _) if macro_form_count(params) != args.len() => arity_error(
+            &describe(name, &macro_expression),
+            Arity::Exactly(macro_form_count(params)),
+            args.len(),
+        ),
+        Macro(ref name, ref params, _, _) if params.rest().is_some() && !is_list(args.last()) => {
+            argument_error(&format!(
+                "{} takes its remaining forms in a list",
+                describe(name, &macro_expression)
+            ))
+        }
+        Macro(_name, params, environment, body) => {
+            let new_env =
+                environment.push_frame(params.names(), params.bind(spread_rest(&params, args)));
 
             let eval_body = body
                 .iter()
@@ -93,46 +141,64 @@ pub fn macro_expand(
 
 pub fn eval(expression: RuntimeExpression, environment: Environment) -> Process<RuntimeExpression> {
     match expression {
-        TaggedTuple(tag, args) => match (*tag).clone() {
+        TaggedTuple(tag, args, span) => match (*tag).clone() {
             // TODO: This should just eval the tag and then apply it to the tuple
-            Symbol(name) => {
-                let maybe_function = environment.get(&name);
-                match maybe_function {
-                    Some(function) => {
-                        let function = function.clone();
-
-                        eval_expressions(&args, &environment).and_then(Arc::new(
-                            move |evaluated_expressions| {
-                                apply(function.clone(), evaluated_expressions)
-                            },
-                        ))
+            tag @ (Symbol(..) | Local(..)) => {
+                let (name, maybe_function) = match tag {
+                    Local(name, slot, _) => (name, environment.get_slot(slot)),
+                    Symbol(name, _) => {
+                        let function = environment.get(&name);
+                        (name, function)
                     }
-                    _ => not_found_error(&format!("No function '{}' found", name)),
+                    _ => unreachable!(),
+                };
+                match maybe_function {
+                    Some(function) => eval_expressions(&args, &environment).and_then(Arc::new(
+                        move |evaluated_expressions| {
+                            with_location(apply(function.clone(), evaluated_expressions), &span)
+                        },
+                    )),
+                    _ => with_location(
+                        not_found_error(&format!("No function '{}' found", name)),
+                        &span,
+                    ),
                 }
             }
             Keyword(_) => eval_expressions(&args, &environment).and_then(Arc::new(
                 move |evaluated_expressions| {
-                    Complete(TaggedTuple(tag.clone(), evaluated_expressions))
+                    Complete(TaggedTuple(
+                        tag.clone(),
+                        evaluated_expressions,
+                        span.clone(),
+                    ))
                 },
             )),
             // TODO: Eval tagged tuples other than Symbols and Keywords
-            _ => todo!(),
+            _ => with_location(
+                error_with_message("invalid-tag", "Only symbols and keywords can tag a tuple"),
+                &span,
+            ),
         },
 
-        MacroCall(name, args) => {
+        MacroCall(name, args, span) => {
             let maybe_macro = environment.get(&name);
             match maybe_macro {
                 Some(a_macro) => {
                     let expanded = macro_expand(a_macro.clone(), args, environment.clone());
                     match a_macro {
-                        Macro(..) => {
-                            expanded.and_then(Arc::new(move |re| eval(re, environment.clone())))
-                        }
-                        BuiltinMacro(..) => expanded,
-                        _ => not_found_error("No macro of that name found"),
+                        // The expansion is new code, so it's resolved against
+                        // the frames it's about to run in
+                        Macro(..) => expanded.and_then(Arc::new(move |re| {
+                            eval(
+                                resolve(re, &environment.scopes(), &environment),
+                                environment.clone(),
+                            )
+                        })),
+                        BuiltinMacro(..) => with_location(expanded, &span),
+                        _ => with_location(not_found_error("No macro of that name found"), &span),
                     }
                 }
-                _ => not_found_error("No macro of that name found"),
+                _ => with_location(not_found_error("No macro of that name found"), &span),
             }
         }
 
@@ -148,21 +214,40 @@ pub fn eval(expression: RuntimeExpression, environment: Environment) -> Process<
             }))
         }
 
-        Symbol(name) => match environment.get(&name) {
+        Symbol(name, span) => match environment.get(&name) {
             Some(value) => Complete(value.clone()),
-            None => not_found_error(&format!("{} not found", &name)),
+            None => with_location(not_found_error(&format!("{} not found", &name)), &span),
+        },
+        Local(name, slot, span) => match environment.get_slot(slot) {
+            Some(value) => Complete(value),
+            None => with_location(not_found_error(&format!("{} not found", &name)), &span),
         },
 
         Keyword(_) => Complete(expression),
         Number(_) => Complete(expression),
         RuntimeExpression::String(_) => Complete(expression),
+        Handle(_) => Complete(expression),
+
+        Hole(span) => with_location(
+            error_with_message("invalid-hole", "Holes can only be used in patterns"),
+            &span,
+        ),
 
-        BuiltinFunction(..) => todo!("When would you actually eval a function?"),
-        Function(..) => todo!("Evalling a function"),
-        BuiltinMacro(..) => todo!("Do we eval macros?"),
-        Macro(..) => todo!("Do we eval macros?"),
-        Hole => todo!("I can't imagine what holes evaluate to"),
-        Definition(..) => todo!("Evalling a definition"),
+        // These are values that only exist at runtime so they can't appear in
+        // parsed code, but Unquote can splice them into a quoted expression.
+        BuiltinFunction(..) | Function(..) => {
+            error_with_message("invalid-eval", "A function can't be evaluated as code")
+        }
+        BuiltinMacro(..) | Macro(..) => {
+            error_with_message("invalid-eval", "A macro can't be evaluated as code")
+        }
+        Definition(name, _) => error_with_message(
+            "invalid-eval",
+            &format!(
+                "The definition of {} can only be evaluated at the top level",
+                name
+            ),
+        ),
     }
 }
 
@@ -171,28 +256,34 @@ pub fn eval(expression: RuntimeExpression, environment: Environment) -> Process<
 pub fn quote(value: RuntimeExpression, env: Environment) -> Process<RuntimeExpression> {
     match value {
         BuiltinFunction(_) => Complete(value),
-        Function(params, env, body) => {
+        Function(name, params, env, body) => {
             let new_env = env.clone();
             let processes = body
+                .expressions()
                 .iter()
                 .cloned()
                 .map(move |re| quote(re, env.clone()))
                 .collect();
             Process::run_in_sequence(processes).and_then(Arc::new(move |new_body| {
-                Complete(Function(params.clone(), new_env.clone(), new_body))
+                Complete(Function(
+                    name.clone(),
+                    params.clone(),
+                    new_env.clone(),
+                    new_body.into(),
+                ))
             }))
         }
-        TaggedTuple(tag, values) => {
+        TaggedTuple(tag, values, span) => {
             let processes = values
                 .iter()
                 .cloned()
                 .map(move |re| quote(re, env.clone()))
                 .collect();
             Process::run_in_sequence(processes).and_then(Arc::new(move |new_values| {
-                Complete(TaggedTuple(tag.clone(), new_values))
+                Complete(TaggedTuple(tag.clone(), new_values, span.clone()))
             }))
         }
-        Hole => Complete(value),
+        Hole(_) => Complete(value),
         List(values) => {
             let processes = values
                 .iter()
@@ -202,8 +293,8 @@ pub fn quote(value: RuntimeExpression, env: Environment) -> Process<RuntimeExpre
             Process::run_in_sequence(processes)
                 .and_then(Arc::new(|new_values| Complete(List(new_values))))
         }
-        BuiltinMacro(_, _) => Complete(value),
-        Macro(params, env, body) => {
+        BuiltinMacro(..) => Complete(value),
+        Macro(name, params, env, body) => {
             let new_env = env.clone();
             let processes = body
                 .iter()
@@ -211,10 +302,15 @@ pub fn quote(value: RuntimeExpression, env: Environment) -> Process<RuntimeExpre
                 .map(move |re| quote(re, env.clone()))
                 .collect();
             Process::run_in_sequence(processes).and_then(Arc::new(move |new_body| {
-                Complete(Macro(params.clone(), new_env.clone(), new_body))
+                Complete(Macro(
+                    name.clone(),
+                    params.clone(),
+                    new_env.clone(),
+                    new_body,
+                ))
             }))
         }
-        MacroCall(name, args) => {
+        MacroCall(name, args, span) => {
             if name == s!("Unquote") {
                 let mut args = args;
                 let value = args.pop_front().unwrap();
@@ -226,14 +322,15 @@ pub fn quote(value: RuntimeExpression, env: Environment) -> Process<RuntimeExpre
                     .map(move |re| quote(re, env.clone()))
                     .collect();
                 Process::run_in_sequence(processes).and_then(Arc::new(move |new_args| {
-                    Complete(MacroCall(name.clone(), new_args))
+                    Complete(MacroCall(name.clone(), new_args, span.clone()))
                 }))
             }
         }
         Number(_) => Complete(value),
         NString(_) => Complete(value),
         Keyword(_) => Complete(value),
-        Symbol(_) => Complete(value),
+        Handle(_) => Complete(value),
+        Symbol(..) | Local(..) => Complete(value),
         Definition(name, value) => {
             let process = quote((*value).clone(), env);
             process.and_then(Arc::new(move |new_value| {
@@ -243,11 +340,43 @@ pub fn quote(value: RuntimeExpression, env: Environment) -> Process<RuntimeExpre
     }
 }
 
+// Which evaluator runs top level forms. Both give the same results, the
+// bytecode VM just gets there faster. Functions called by builtins, such as
+// the one given to spawn, are always run by eval.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Backend {
+    #[default]
+    TreeWalking,
+    Bytecode,
+}
+
+// The results of the top level forms that have completed so far and the
+// definitions they made, so a program that runs out of fuel can still say what
+// it got done
+#[derive(Clone)]
+struct Progress(Arc<Mutex<(Vector<RuntimeExpression>, Environment)>>);
+
+impl Progress {
+    fn new(env: Environment) -> Progress {
+        Progress(Arc::new(Mutex::new((vector![], env))))
+    }
+
+    fn record(&self, results: &Vector<RuntimeExpression>, env: &Environment) {
+        *self.0.lock().unwrap() = (results.clone(), env.clone());
+    }
+
+    fn completed(&self) -> (Vector<RuntimeExpression>, Environment) {
+        self.0.lock().unwrap().clone()
+    }
+}
+
 fn execute_with_definitions_and_process(
     work: Vector<LexicalExpression>,
     env: Environment,
     mut results: Vector<RuntimeExpression>,
     process: Process<RuntimeExpression>,
+    backend: Backend,
+    progress: Progress,
 ) -> Process<(Vector<RuntimeExpression>, Environment)> {
     match process {
         Complete(result) => {
@@ -257,17 +386,24 @@ fn execute_with_definitions_and_process(
             };
 
             results.push_back(new_seed.clone());
+            progress.record(&results, &new_env);
             Running(Arc::new(move || {
-                execute_with_definitions(work.clone(), new_env.clone(), results.clone())
+                execute_with_definitions(
+                    work.clone(),
+                    new_env.clone(),
+                    results.clone(),
+                    backend,
+                    progress.clone(),
+                )
             }))
         }
-        Running(stepable) => Running(Arc::new(move || {
-            execute_with_definitions_and_process(
-                work.clone(),
-                env.clone(),
-                results.clone(),
-                stepable.step(),
-            )
+        Running(stepable) => Running(Arc::new(ProgramStep {
+            work,
+            env,
+            results,
+            stepable,
+            backend,
+            progress,
         })),
         Spawn(continuation, spawned_processes) => Spawn(
             Arc::new(execute_with_definitions_and_process(
@@ -275,6 +411,8 @@ fn execute_with_definitions_and_process(
                 env.clone(),
                 results.clone(),
                 (*continuation).clone(),
+                backend,
+                progress,
             )),
             spawned_processes
                 .iter()
@@ -284,20 +422,64 @@ fn execute_with_definitions_and_process(
                         env.clone(),
                         vector![],
                         (*process).clone(),
+                        backend,
+                        Progress::new(env.clone()),
                     )
                 })
                 .collect::<im::Vector<_>>(),
         ),
+        Waiting(wake, continuation) => Waiting(
+            wake,
+            Arc::new(execute_with_definitions_and_process(
+                work,
+                env,
+                results,
+                (*continuation).clone(),
+                backend,
+                progress,
+            )),
+        ),
+    }
+}
+
+// A step of the form that's running, after which the rest of the program
+// carries on. It's a struct rather than a closure so the form's priority,
+// which a spawned process gets from its pid, still reaches the scheduler.
+struct ProgramStep {
+    work: Vector<LexicalExpression>,
+    env: Environment,
+    results: Vector<RuntimeExpression>,
+    stepable: Arc<dyn Stepable<RuntimeExpression>>,
+    backend: Backend,
+    progress: Progress,
+}
+
+impl Stepable<(Vector<RuntimeExpression>, Environment)> for ProgramStep {
+    fn step(&self) -> Process<(Vector<RuntimeExpression>, Environment)> {
+        execute_with_definitions_and_process(
+            self.work.clone(),
+            self.env.clone(),
+            self.results.clone(),
+            self.stepable.step(),
+            self.backend,
+            self.progress.clone(),
+        )
+    }
+
+    fn priority(&self) -> Priority {
+        self.stepable.priority()
     }
 }
 
 // Runs lexical expressions, basically code prior to macro parsing, and returns
 // a process that will eventually produce the result of each top level
 // expression, as well as an environment containing all new definitions
-pub fn execute_with_definitions(
+fn execute_with_definitions(
     work: Vector<LexicalExpression>,
     env: Environment,
     results: Vector<RuntimeExpression>,
+    backend: Backend,
+    progress: Progress,
 ) -> Process<(Vector<RuntimeExpression>, Environment)> {
     if work.is_empty() {
         Complete((results, env))
@@ -305,8 +487,19 @@ pub fn execute_with_definitions(
         let (head, remaining_work) = build_macros(&work, &env);
         match head {
             Some(first_expression) => {
-                let first_process = eval(first_expression, env.clone());
-                execute_with_definitions_and_process(remaining_work, env, results, first_process)
+                let first_expression = resolve(first_expression, &env.scopes(), &env);
+                let first_process = match backend {
+                    Backend::TreeWalking => eval(first_expression, env.clone()),
+                    Backend::Bytecode => vm::run(&first_expression, env.clone()),
+                };
+                execute_with_definitions_and_process(
+                    remaining_work,
+                    env,
+                    results,
+                    first_process,
+                    backend,
+                    progress,
+                )
             }
             None => Complete((vector![], env)),
         }
@@ -315,11 +508,108 @@ pub fn execute_with_definitions(
 
 // Runs a string of code and returns the result of each top level expression, as
 // well as an environment containing all new definitions
+//
+// If the code can't be parsed nothing is run. Instead the only result is a
+// :error(:syntax ...) for each problem found.
 pub fn execute(code: String, env: Environment) -> Vector<(Vector<RuntimeExpression>, Environment)> {
-    let (_err, expressions) = program(&code).unwrap();
-    let comments_stripped = expressions.into_iter().filter(|e| !is_comment(e)).collect();
-    let process = execute_with_definitions(comments_stripped, env, vector![]);
-    process.run_until_complete()
+    execute_parsed(parse_program(input(&code)), env)
+}
+
+// Like execute, but spans (and so error locations) will name the file the code
+// was read from
+pub fn execute_file_contents(
+    file: &str,
+    code: String,
+    env: Environment,
+) -> Vector<(Vector<RuntimeExpression>, Environment)> {
+    execute_parsed(parse_program(input_from_file(&code, file)), env)
+}
+
+fn execute_parsed(
+    parsed: Result<Vector<LexicalExpression>, Vector<Diagnostic>>,
+    env: Environment,
+) -> Vector<(Vector<RuntimeExpression>, Environment)> {
+    match parsed {
+        Ok(expressions) => program_process(
+            expressions,
+            env.clone(),
+            Backend::default(),
+            Progress::new(env),
+        )
+        .run_until_complete(),
+        Err(diagnostics) => vector![(diagnostics.iter().map(Diagnostic::to_error).collect(), env)],
+    }
+}
+
+// The results of a program's top level forms, and the environment with the
+// definitions they made
+pub(crate) type ProgramResults = (Vector<RuntimeExpression>, Environment);
+
+// Runs parsed code and returns the result of each top level expression and the
+// environment they built, kept apart from the results of any processes they
+// spawned
+//
+// If the fuel runs out, the form that was running gets an :error(:out-of-fuel)
+// as its result, and no later forms are run. If the program had finished and
+// only spawned processes were left, the error goes with their results instead.
+//
+// run decides how the program's processes take their turns, and how much fuel
+// they have.
+pub fn execute_program(
+    expressions: Vector<LexicalExpression>,
+    env: Environment,
+    backend: Backend,
+    run: impl FnOnce(Process<ProgramResults>) -> RunResult<ProgramResults, ProgramResults>,
+) -> (ProgramResults, Vector<Vector<RuntimeExpression>>) {
+    let progress = Progress::new(env.clone());
+    let spawned_results = |spawned: Vector<ProgramResults>| {
+        spawned
+            .into_iter()
+            .map(|(results, _env)| results)
+            .collect::<Vector<_>>()
+    };
+
+    match run(program_process(expressions, env, backend, progress.clone())) {
+        Ok((main, spawned)) => (main, spawned_results(spawned)),
+        Err(out_of_fuel) => {
+            let error = out_of_fuel_error(out_of_fuel.steps, out_of_fuel.timed_out);
+            let (main, spawned) = out_of_fuel.completed;
+            let mut spawned = spawned_results(spawned);
+            match main {
+                Some(main) => {
+                    spawned.push_back(vector![error]);
+                    (main, spawned)
+                }
+                None => {
+                    let (mut results, env) = progress.completed();
+                    results.push_back(error);
+                    ((results, env), spawned)
+                }
+            }
+        }
+    }
+}
+
+fn program_process(
+    expressions: Vector<LexicalExpression>,
+    env: Environment,
+    backend: Backend,
+    progress: Progress,
+) -> Process<(Vector<RuntimeExpression>, Environment)> {
+    let comments_stripped: Vector<_> = expressions.into_iter().filter(|e| !is_comment(e)).collect();
+    // Top level code runs as a process of its own, so it has a pid
+    as_process(
+        ProcessHandle::top_level(),
+        Running(Arc::new(move || {
+            execute_with_definitions(
+                comments_stripped.clone(),
+                env.clone(),
+                vector![],
+                backend,
+                progress.clone(),
+            )
+        })),
+    )
 }
 
 pub fn execute_once(code: String, env: Environment) -> Vector<RuntimeExpression> {
@@ -330,7 +620,7 @@ pub fn execute_once(code: String, env: Environment) -> Vector<RuntimeExpression>
 fn eval_expressions(
     args: &Vector<RuntimeExpression>,
     environment: &Environment,
-) -> Process<Vector<RuntimeExpression>> {
+) -> Process<Vector<RuntimeExpression>, RuntimeExpression> {
     Process::run_in_sequence(
         args.iter()
             .cloned()
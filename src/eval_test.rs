@@ -3,35 +3,40 @@ use std::sync::Arc;
 use im::{hashmap, vector};
 
 use crate::{
-    eval::eval,
+    eval::{eval, execute_file_contents, macro_expand, Backend},
     expressions::{
-        Environment,
+        print, print_many, Arity, Environment,
         RuntimeExpression::{
-            self, BuiltinFunction, BuiltinMacro, Function, List, Macro, MacroCall, Number, Symbol,
+            self, BuiltinMacro, Function, Keyword, List, Macro, MacroCall, Number, Symbol,
             TaggedTuple,
         },
     },
+    interpreter::Interpreter,
+    parsers::{diagnostics::parse_program, nana::input},
     process::Process,
     s,
+    span::Span,
+    standard_library::core::standard_library,
 };
 
 pub fn environment() -> Environment {
     Environment::from(hashmap! {
         s!("life") => Number(42),
         s!("Package") => Macro(
-            vector![s!("name")],
+            None,
+            vector![s!("name")].into(),
             Environment::new(),
             vector![],
         ),
-        s!("foo") => BuiltinFunction(|_args| {
+        s!("foo") => RuntimeExpression::builtin("foo", Arity::Exactly(0), |_args| {
             Process::Complete(RuntimeExpression::String(s!("bar")))
         }),
-        s!("swap") => BuiltinMacro(vector![s!("left"), s!("right")], |args, _env| {
+        s!("swap") => BuiltinMacro(s!("swap"), vector![s!("left"), s!("right")], |args, _env| {
             let first = args.head().unwrap().clone();
             let last = args.last().unwrap().clone();
             Process::Complete(List(vector![last, first]))
         }),
-        s!("ignore") => Macro(vector![s!("expression")], Environment::new(), vector![
+        s!("ignore") => Macro(None, vector![s!("expression")].into(), Environment::new(), vector![
             Number(42)
         ])
     })
@@ -41,17 +46,18 @@ pub fn environment_with_fn() -> Environment {
     Environment::from(hashmap! {
         s!("life") => Number(2),
         s!("list-nums") => Function(
-            vector![s!("n")],
+            None,
+            vector![s!("n")].into(),
             environment(),
             vector![
                 Number(0),
                 List(vector![
                     Number(1),
-                    Symbol(s!("n")),
+                    Symbol(s!("n"), Span::default()),
                     Number(3),
-                    Symbol(s!("life")),
+                    Symbol(s!("life"), Span::default()),
                 ])
-            ]
+            ].into()
         )
     })
 }
@@ -68,20 +74,24 @@ fn test_scalar_literals() {
 
 #[test]
 fn test_value_names() {
-    let result = eval(Symbol(s!("life")), environment()).run_once_until_complete();
+    let result = eval(Symbol(s!("life"), Span::default()), environment()).run_once_until_complete();
     assert_eq!(result, Number(42));
 }
 
 #[test]
 fn test_lists() {
-    let expression = List(vector![Number(1), Symbol(s!("life"))]);
+    let expression = List(vector![Number(1), Symbol(s!("life"), Span::default())]);
     let result = eval(expression, environment()).run_once_until_complete();
     assert_eq!(result, List(vector![Number(1), Number(42)]));
 }
 
 #[test]
 fn test_builtin_function_call() {
-    let expression = TaggedTuple(Arc::new(RuntimeExpression::Symbol(s!("foo"))), vector![]);
+    let expression = TaggedTuple(
+        Arc::new(RuntimeExpression::Symbol(s!("foo"), Span::default())),
+        vector![],
+        Span::default(),
+    );
     let result = eval(expression, environment()).run_once_until_complete();
     assert_eq!(result, RuntimeExpression::String(s!("bar")))
 }
@@ -89,8 +99,9 @@ fn test_builtin_function_call() {
 #[test]
 fn test_user_defined_function_call() {
     let expression = TaggedTuple(
-        Arc::new(RuntimeExpression::Symbol(s!("list-nums"))),
-        vector![Symbol(s!("life"))],
+        Arc::new(RuntimeExpression::Symbol(s!("list-nums"), Span::default())),
+        vector![Symbol(s!("life"), Span::default())],
+        Span::default(),
     );
     let actual = eval(expression, environment_with_fn()).run_once_until_complete();
     let expected = List(vector![Number(1), Number(2), Number(3), Number(42)]);
@@ -99,7 +110,7 @@ fn test_user_defined_function_call() {
 
 #[test]
 fn test_builtin_macro_call() {
-    let expression = MacroCall(s!("swap"), vector![Number(1), Number(2)]);
+    let expression = MacroCall(s!("swap"), vector![Number(1), Number(2)], Span::default());
     let actual = eval(expression, environment()).run_once_until_complete();
     let expected = List(vector![Number(2), Number(1)]);
     assert_eq!(expected, actual);
@@ -107,8 +118,147 @@ fn test_builtin_macro_call() {
 
 #[test]
 fn test_user_defined_macro_call() {
-    let expression = MacroCall(s!("ignore"), vector![Number(1), Number(2)]);
+    let expression = MacroCall(s!("ignore"), vector![Number(1)], Span::default());
     let actual = eval(expression, environment()).run_once_until_complete();
     let expected = Number(42);
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn test_errors_report_their_location() {
+    let actual = execute_file_contents("errors.nana", s!("1\n  missing"), standard_library())
+        .head()
+        .unwrap()
+        .0
+        .clone();
+    let expected = vector![
+        Number(1),
+        TaggedTuple(
+            Arc::new(Keyword(s!("error"))),
+            vector![
+                Keyword(s!("not-found")),
+                RuntimeExpression::String(s!("missing not found")),
+                TaggedTuple(
+                    Arc::new(Keyword(s!("at"))),
+                    vector![RuntimeExpression::String(s!("errors.nana:2:3"))],
+                    Span::default(),
+                ),
+            ],
+            Span::default(),
+        ),
+    ];
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_syntax_errors_are_returned_instead_of_running() {
+    let results = execute_file_contents("broken.nana", s!("1\n[2 3\n4)"), standard_library());
+    let printed = results
+        .iter()
+        .map(|(values, _env)| print_many(values.clone(), "\n"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            ":error(:syntax \"expected ']' to close the list, found ')'\" :at(\"broken.nana:3:2\"))"
+        ],
+        printed
+    );
+}
+
+#[test]
+fn test_bad_forms_return_errors_and_execution_continues() {
+    let code = "Undefined\nFoo(1)\nFn [1] 2\ndivide(1 0)\n_\n\"tag\"(1)\n:still-running\nDef";
+    let results = execute_file_contents("bad.nana", s!(code), standard_library());
+    let printed = print_many(results.head().unwrap().0.clone(), "\n");
+    let expected = [
+        ":error(:not-found \"Macro Undefined was referenced but has not been defined\" :at(\"bad.nana:1:1\"))",
+        ":error(:invalid-tag \"Macro names can't be used to tag a tuple yet\" :at(\"bad.nana:2:1\"))",
+        ":error(:argument \"Fn params must be symbols, then [name default] pairs, then & and a symbol\" :at(\"bad.nana:3:1\"))",
+        ":error(:arithmetic \"divide(1 0) divides by zero\" :at(\"bad.nana:4:1\"))",
+        ":error(:invalid-hole \"Holes can only be used in patterns\" :at(\"bad.nana:5:1\"))",
+        ":error(:invalid-tag \"Only symbols and keywords can tag a tuple\" :at(\"bad.nana:6:1\"))",
+        ":still-running",
+        ":error(:missing-macro-arguments \"Def takes 2 arguments but the program ended after 0\" :at(\"bad.nana:8:1\"))",
+    ]
+    .join("\n");
+    assert_eq!(expected, printed);
+}
+
+#[test]
+fn test_functions_are_named_after_their_definition() {
+    let code = "Func increment [n] add(n 1)\nincrement\nDef inc increment\ninc\nadd\nFn [x] x\nDef life 42\nlife(1)";
+    let results = execute_file_contents("names.nana", s!(code), standard_library());
+    let printed = print_many(results.head().unwrap().0.clone(), "\n");
+    let expected = [
+        "Function increment([n])",
+        "Function increment([n])",
+        "Function increment([n])",
+        "Function increment([n])",
+        "add/2",
+        "Function([x] _)",
+        "42",
+        ":error(:invalid-application \"42 was called but is not a function\" :at(\"names.nana:8:1\"))",
+    ]
+    .join("\n");
+    assert_eq!(expected, printed);
+}
+
+#[test]
+fn test_functions_check_their_arity() {
+    let code = "Func increment [n] add(n 1)\nincrement(1 2)\nincrement()\nDef apply-two Fn [f] f(1 2)\napply-two(Fn [a] a)";
+    let results = execute_file_contents("arity.nana", s!(code), standard_library());
+    let printed = print_many(results.head().unwrap().0.clone().skip(1), "\n");
+    let expected = [
+        ":error(:arity \"increment takes 1 argument but was given 2\" :at(\"arity.nana:2:1\"))",
+        ":error(:arity \"increment takes 1 argument but was given 0\" :at(\"arity.nana:3:1\"))",
+        "Function apply-two([f])",
+        ":error(:arity \"Function([a] _) takes 1 argument but was given 2\" :at(\"arity.nana:4:22\"))",
+    ]
+    .join("\n");
+    assert_eq!(expected, printed);
+}
+
+#[test]
+fn test_macros_check_their_arity() {
+    let env = environment();
+    let ignore = env.get("ignore").unwrap();
+
+    let actual = macro_expand(ignore, vector![], env).run_once_until_complete();
+
+    assert_eq!(
+        print(actual),
+        ":error(:arity \"Macro([expression] _) takes 1 argument but was given 0\")"
+    );
+}
+
+#[test]
+fn test_spawn_can_be_called_anywhere() {
+    let code = [
+        "second(spawn(Fn [] :argument) 1)",
+        "Match [spawn(Fn [] :listed) 2] [[handle n] [await(handle) n]]",
+        "Match spawn(Fn [] :matched) [handle await(handle)]",
+        "Func spawn-in-body [] await(spawn(Fn [] :body))",
+        "spawn-in-body()",
+    ]
+    .join("\n");
+
+    for backend in [Backend::TreeWalking, Backend::Bytecode] {
+        let mut interpreter = Interpreter::new();
+        interpreter.set_backend(backend);
+        interpreter.eval_str("Func second [a b] b").unwrap();
+        let (results, spawned) = interpreter.run(parse_program(input(&code))).unwrap();
+
+        assert_eq!(
+            "1\n[:listed 2]\n:matched\nFunction spawn-in-body([])\n:body",
+            print_many(results, "\n"),
+            "{:?}",
+            backend
+        );
+        assert_eq!(
+            ":argument :listed :matched :body",
+            print_many(spawned.into_iter().flatten().collect(), " "),
+            "{:?}",
+            backend
+        );
+    }
+}
@@ -9,6 +9,7 @@ use crate::expressions::RuntimeExpression::{
 };
 use crate::helpers::strip_functions;
 use crate::s;
+use crate::span::Span;
 use crate::standard_library::core::standard_library;
 
 #[test]
@@ -32,16 +33,16 @@ fn test_learn_x_in_y_minutes() {
 
         // Macros
         Number(1),
-        Symbol(s!("life")),
-        TaggedTuple(Arc::new(Symbol(s!("decrement"))), vector![Symbol(s!("life"))]),
-        TaggedTuple(Arc::new(Symbol(s!("foo"))), vector![Symbol(s!("bar"))]),
+        Symbol(s!("life"), Span::default()),
+        TaggedTuple(Arc::new(Symbol(s!("decrement"), Span::default())), vector![Symbol(s!("life"), Span::default())], Span::default()),
+        TaggedTuple(Arc::new(Symbol(s!("foo"), Span::default())), vector![Symbol(s!("bar"), Span::default())], Span::default()),
 
         // Defmacro
         Number(2),
 
         // Unquote
         Number(42),
-        List(vector![Number(1), Number(42), Number(3), Symbol(s!("foo"))]),
+        List(vector![Number(1), Number(42), Number(3), Symbol(s!("foo"), Span::default())]),
         Keyword(s!("true")),
         NString(s!("It's Tuesday!")),
 
@@ -0,0 +1,16 @@
+use crate::expressions::print;
+use crate::interpreter::Interpreter;
+
+#[test]
+fn test_processes_answer_messages() {
+    let mut interpreter = Interpreter::new();
+    let results = interpreter.eval_file("examples/mailboxes.nana").unwrap();
+
+    assert_eq!(
+        results
+            .last()
+            .map(|result| print(result.clone()))
+            .as_deref(),
+        Some("[0 1 2 :timed-out]")
+    );
+}
@@ -6,3 +6,15 @@ pub mod spawn_test;
 
 #[cfg(test)]
 pub mod time_to_a_billion;
+
+#[cfg(test)]
+pub mod vararg_macros_test;
+
+#[cfg(test)]
+pub mod shadowing_test;
+
+#[cfg(test)]
+pub mod mailboxes_test;
+
+#[cfg(test)]
+pub mod supervision_test;
@@ -0,0 +1,23 @@
+use im::vector;
+
+use crate::eval::{execute_once, read_code};
+use crate::expressions::RuntimeExpression::{Keyword, List, Number};
+use crate::helpers::strip_functions;
+use crate::s;
+use crate::standard_library::core::standard_library;
+
+#[test]
+fn test_innermost_bindings_shadow_outer_ones() {
+    let code = read_code("examples/shadowing.nana");
+    let results = strip_functions(execute_once(code, standard_library()));
+    let expected = vector![
+        Keyword(s!("foo")),
+        List(vector![Number(789), Keyword(s!("foo"))]),
+        Number(1),
+        Number(123),
+        Number(456),
+        Keyword(s!("bar")),
+    ];
+
+    assert_eq!(expected, results);
+}
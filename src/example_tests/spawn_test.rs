@@ -1,29 +1,44 @@
+use std::sync::Arc;
+
 use crate::expressions::RuntimeExpression::{Keyword, List};
 use im::{vector, Vector};
 
 use crate::{
     eval::{execute, read_code},
     helpers::strip_functions,
-    s, standard_library,
+    output::{with_sink, Buffer},
+    s,
+    standard_library::core::standard_library,
 };
 
 #[test]
 fn test_spawn_2_and_loop() {
     let code = read_code("examples/spawn.nana");
-    let results = execute(code, standard_library());
+    let logs = Buffer::default();
+    let results = with_sink(Arc::new(logs.clone()), || execute(code, standard_library()));
     let stripped = results
         .into_iter()
         .map(|(r, _e)| strip_functions(r))
         .collect::<Vector<_>>();
+    let done = |name: &str| List(vector![Keyword(s!(name)), Keyword(s!("done"))]);
     let expected = vector![
-        vector![List(vector![Keyword(s!("b")), Keyword(s!("done"))])],
-        vector![List(vector![Keyword(s!("a")), Keyword(s!("done"))])],
-        vector![
-            Keyword(s!("process-spawned")),
-            Keyword(s!("process-spawned")),
-            List(vector![Keyword(s!("c")), Keyword(s!("done"))]),
-        ]
+        vector![done("b")],
+        vector![done("a")],
+        vector![List(vector![
+            done("c"),
+            done("b"),
+            List(vector![done("a"), done("b")]),
+        ])]
     ];
 
     assert_eq!(expected, stripped);
+
+    // Each process uses up its turn every time round its loop, so they take
+    // turns logging until each is done
+    let expected_logs = [
+        "c 9", "a 9", "b 4", "c 8", "a 8", "b 3", "c 7", "a 7", "b 2", "c 6", "a 6", "b 1", "c 5",
+        "a 5", "b 0", "c 4", "a 4", "c 3", "a 3", "c 2", "a 2", "c 1", "a 1", "c 0", "a 0",
+    ]
+    .map(|line| format!("[:{}]", line));
+    assert_eq!(logs.lines(), expected_logs);
 }
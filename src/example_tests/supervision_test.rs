@@ -0,0 +1,23 @@
+use crate::eval::Backend;
+use crate::expressions::print;
+use crate::interpreter::Interpreter;
+
+// Spawned processes finish in a different order on each backend, so only the
+// results of the program itself are compared
+#[test]
+fn test_supervisors_restart_failed_children() {
+    for backend in [Backend::TreeWalking, Backend::Bytecode] {
+        let mut interpreter = Interpreter::new();
+        interpreter.set_backend(backend);
+        let results = interpreter.eval_file("examples/supervision.nana").unwrap();
+        let printed: Vec<_> = results.into_iter().map(print).collect();
+
+        assert_eq!(
+            printed[printed.len() - 2..],
+            [
+                "[:ok 3 :stopped]",
+                "[:exit([:too-many-restarts :second-try]) 2 :stopped]"
+            ]
+        );
+    }
+}
@@ -1,20 +1,28 @@
-use crate::{
-    eval::{execute, read_code},
-    expressions::print_many,
-    standard_library,
-};
+use std::time::Instant;
 
-// Disabling this test for now. It's quite slow.
-// #[test]
+use crate::errors::is_error;
+use crate::eval::{read_code, Backend};
+use crate::interpreter::Interpreter;
+
+// Counting to a billion takes far too long, so the benchmark counts to a
+// smaller goal on each backend. It's slow even so, and only prints timings, so
+// it has to be asked for:
+//
+//   cargo test --release time_to_a_billion -- --ignored --nocapture
+#[test]
+#[ignore]
 fn test_counting_to_a_billion() {
-    let code = read_code("examples/abillion.nana");
-    let placeholder = 4;
-    let results = execute(code, standard_library());
-    println!("{}", placeholder);
+    let code = read_code("examples/abillion.nana").replace("Def goal 1000000", "Def goal 100000");
 
-    println!("###### count to a billion in n ms ####################################");
-    println!("{}", print_many(results.head().unwrap().clone().0, "\n"));
-    println!("######################################################################");
+    println!("###### count to 100,000 five times #####################################");
+    for backend in [Backend::TreeWalking, Backend::Bytecode] {
+        let mut interpreter = Interpreter::new();
+        interpreter.set_backend(backend);
 
-    assert_eq!(true, true);
+        let start = Instant::now();
+        let results = interpreter.eval_str(&code).unwrap();
+        println!("{:?}: {} ms", backend, start.elapsed().as_millis());
+        assert!(!results.iter().any(is_error));
+    }
+    println!("########################################################################");
 }
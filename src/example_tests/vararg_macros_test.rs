@@ -0,0 +1,34 @@
+use im::vector;
+
+use crate::eval::{execute_once, read_code};
+use crate::expressions::RuntimeExpression::{Keyword, List, Number, String as NString};
+use crate::helpers::strip_functions;
+use crate::s;
+use crate::standard_library::core::standard_library;
+
+#[test]
+fn test_vararg_macros() {
+    let code = read_code("examples/vararg_macros.nana");
+    let results = strip_functions(execute_once(code, standard_library()));
+    let expected = vector![
+        List(vector![
+            NString(s!("Hello")),
+            NString(s!("Ada")),
+            List(vector![])
+        ]),
+        List(vector![
+            NString(s!("Hi")),
+            NString(s!("Ada")),
+            List(vector![NString(s!("Grace")), NString(s!("Alan"))])
+        ]),
+        List(vector![
+            Keyword(s!("numbers")),
+            List(vector![Number(1), Number(2), Number(3)])
+        ]),
+        Keyword(s!("second")),
+        Keyword(s!("sometimes")),
+        List(vector![Number(1), List(vector![Number(2), Number(3)])]),
+    ];
+
+    assert_eq!(expected, results);
+}
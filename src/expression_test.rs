@@ -1,5 +1,6 @@
-use crate::expressions::{print, Environment, RuntimeExpression};
+use crate::expressions::{print, Arity, Environment, RuntimeExpression};
 use crate::process::Process;
+use crate::span::Span;
 use im::vector;
 use std::sync::Arc;
 use RuntimeExpression::*;
@@ -9,18 +10,30 @@ fn test_print_builtin_function() {
     fn dummy(_: im::Vector<RuntimeExpression>) -> Process<RuntimeExpression> {
         panic!("not called")
     }
-    let expr = BuiltinFunction(dummy);
-    assert_eq!(print(expr), "BuiltinFunction(..)");
+    let expr = RuntimeExpression::builtin("dummy", Arity::Exactly(0), dummy);
+    assert_eq!(print(expr), "dummy/0");
+
+    let expr = RuntimeExpression::builtin("log", Arity::AtLeast(1), dummy);
+    assert_eq!(print(expr), "log/1+");
 }
 
 #[test]
 fn test_print_function() {
     let expr = Function(
-        vector!["x".to_string(), "y".to_string()],
+        None,
+        vector!["x".to_string(), "y".to_string()].into(),
         Environment::new(),
-        vector![],
+        vector![].into(),
     );
     assert_eq!(print(expr), "Function([x y] _)");
+
+    let expr = Function(
+        Some("pair".to_string()),
+        vector!["x".to_string(), "y".to_string()].into(),
+        Environment::new(),
+        vector![].into(),
+    );
+    assert_eq!(print(expr), "Function pair([x y])");
 }
 
 #[test]
@@ -28,8 +41,12 @@ fn test_print_builtin_macro() {
     fn dummy(_: im::Vector<RuntimeExpression>, _: Environment) -> Process<RuntimeExpression> {
         panic!("not called")
     }
-    let expr = BuiltinMacro(vector!["x".to_string(), "y".to_string()], dummy);
-    assert_eq!(print(expr), "BuiltinMacro([x y] _)");
+    let expr = BuiltinMacro(
+        "both".to_string(),
+        vector!["x".to_string(), "y".to_string()],
+        dummy,
+    );
+    assert_eq!(print(expr), "BuiltinMacro both([x y])");
 }
 
 #[test]
@@ -40,7 +57,7 @@ fn test_print_definition() {
 
 #[test]
 fn test_print_hole() {
-    let expr = Hole;
+    let expr = Hole(Span::default());
     assert_eq!(print(expr), "_");
 }
 
@@ -59,7 +76,8 @@ fn test_print_list() {
 #[test]
 fn test_print_macro() {
     let expr = Macro(
-        vector!["a".to_string(), "b".to_string()],
+        None,
+        vector!["a".to_string(), "b".to_string()].into(),
         Environment::new(),
         vector![],
     );
@@ -68,7 +86,11 @@ fn test_print_macro() {
 
 #[test]
 fn test_print_macro_call() {
-    let expr = MacroCall("foo".to_string(), vector![Number(1), Number(2)]);
+    let expr = MacroCall(
+        "foo".to_string(),
+        vector![Number(1), Number(2)],
+        Span::default(),
+    );
     assert_eq!(print(expr), "foo(1 2)");
 }
 
@@ -86,13 +108,13 @@ fn test_print_nstring() {
 
 #[test]
 fn test_print_symbol() {
-    let expr = Symbol("baz".to_string());
+    let expr = Symbol("baz".to_string(), Span::default());
     assert_eq!(print(expr), "baz");
 }
 
 #[test]
 fn test_print_tagged_tuple() {
-    let tag = Arc::new(Symbol("tag".to_string()));
-    let expr = TaggedTuple(tag, vector![Number(1), Number(2)]);
+    let tag = Arc::new(Symbol("tag".to_string(), Span::default()));
+    let expr = TaggedTuple(tag, vector![Number(1), Number(2)], Span::default());
     assert_eq!(print(expr), "tag(1 2)");
 }
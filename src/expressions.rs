@@ -1,69 +1,532 @@
-use std::sync::Arc;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 
-pub use crate::environment::Environment;
-use crate::{process::Process, s};
+use crate::bytecode::{compiler::compile_body, Chunk};
+pub use crate::environment::{Environment, Slot};
+use crate::scheduler::{self, Priority};
+use crate::{process::Process, s, span::Span};
 use im::Vector;
 use RuntimeExpression::{
-    BuiltinFunction, BuiltinMacro, Definition, Function, Hole, Keyword, List, Macro, MacroCall,
-    Number, String as NString, Symbol, TaggedTuple,
+    BuiltinFunction, BuiltinMacro, Definition, Function, Handle, Hole, Keyword, List, Local, Macro,
+    MacroCall, Number, String as NString, Symbol, TaggedTuple,
 };
 
 pub fn is_comment(expression: &LexicalExpression) -> bool {
-    match expression {
-        LexicalExpression::Comment => true,
-        _ => false,
-    }
+    matches!(expression, LexicalExpression::Comment(_))
 }
 
 #[derive(PartialEq, Debug, Clone)]
 pub enum LexicalExpression {
-    TaggedTuple(Arc<LexicalExpression>, Vector<LexicalExpression>),
-    Keyword(String),
-    Hole,
-    List(Vector<LexicalExpression>),
-    MacroName(String),
-    Number(u128),
-    String(String),
-    Symbol(String),
-    Comment,
+    TaggedTuple(Arc<LexicalExpression>, Vector<LexicalExpression>, Span),
+    Keyword(String, Span),
+    Hole(Span),
+    List(Vector<LexicalExpression>, Span),
+    MacroName(String, Span),
+    Number(u128, Span),
+    String(String, Span),
+    Symbol(String, Span),
+    Comment(Span),
+}
+
+impl LexicalExpression {
+    pub fn span(&self) -> &Span {
+        match self {
+            LexicalExpression::TaggedTuple(_, _, span)
+            | LexicalExpression::Keyword(_, span)
+            | LexicalExpression::Hole(span)
+            | LexicalExpression::List(_, span)
+            | LexicalExpression::MacroName(_, span)
+            | LexicalExpression::Number(_, span)
+            | LexicalExpression::String(_, span)
+            | LexicalExpression::Symbol(_, span)
+            | LexicalExpression::Comment(span) => span,
+        }
+    }
+}
+
+// How many arguments a builtin accepts
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Arity {
+    Exactly(usize),
+    AtLeast(usize),
+    Between(usize, usize),
+}
+
+impl Arity {
+    pub fn accepts(&self, count: usize) -> bool {
+        match self {
+            Arity::Exactly(n) => count == *n,
+            Arity::AtLeast(n) => count >= *n,
+            Arity::Between(min, max) => *min <= count && count <= *max,
+        }
+    }
+}
+
+// Printed after a builtin's name, as in add/2 or log/1+
+impl fmt::Display for Arity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Arity::Exactly(n) => write!(f, "{}", n),
+            Arity::AtLeast(n) => write!(f, "{}+", n),
+            Arity::Between(min, max) => write!(f, "{}-{}", min, max),
+        }
+    }
+}
+
+// The parameters of a function or macro, as in [a b [c 10] & rest]. Optional
+// parameters take their default when no argument is given for them and the
+// rest parameter collects any remaining arguments into a list.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Params(Arc<ParamList>);
+
+// Functions are copied every time they're looked up or called, so their
+// params live behind an Arc that the copies share
+#[derive(PartialEq, Debug)]
+struct ParamList {
+    required: Vector<String>,
+    optional: Vector<(String, RuntimeExpression)>,
+    rest: Option<String>,
+    // Every name in order, shared by each frame a call makes
+    names: Arc<[String]>,
+}
+
+impl Params {
+    pub fn new(
+        required: Vector<String>,
+        optional: Vector<(String, RuntimeExpression)>,
+        rest: Option<String>,
+    ) -> Params {
+        let names = required
+            .iter()
+            .chain(optional.iter().map(|(name, _)| name))
+            .chain(rest.iter())
+            .cloned()
+            .collect();
+        Params(Arc::new(ParamList {
+            required,
+            optional,
+            rest,
+            names,
+        }))
+    }
+
+    pub fn required(&self) -> &Vector<String> {
+        &self.0.required
+    }
+
+    pub fn optional(&self) -> &Vector<(String, RuntimeExpression)> {
+        &self.0.optional
+    }
+
+    pub fn rest(&self) -> Option<&String> {
+        self.0.rest.as_ref()
+    }
+
+    pub fn names(&self) -> Arc<[String]> {
+        self.0.names.clone()
+    }
+
+    pub fn arity(&self) -> Arity {
+        let required = self.0.required.len();
+        match (self.0.optional.len(), &self.0.rest) {
+            (_, Some(_)) => Arity::AtLeast(required),
+            (0, None) => Arity::Exactly(required),
+            (optional, None) => Arity::Between(required, required + optional),
+        }
+    }
+
+    // The value of each name, in order. Callers check the arity first.
+    pub fn bind(&self, args: Vector<RuntimeExpression>) -> Vec<RuntimeExpression> {
+        let mut args = args;
+        let mut values = Vec::with_capacity(self.0.names.len());
+
+        for _ in self.0.required.iter() {
+            values.push(args.pop_front().unwrap());
+        }
+        for (_, default) in self.0.optional.iter() {
+            values.push(args.pop_front().unwrap_or_else(|| default.clone()));
+        }
+        if self.0.rest.is_some() {
+            values.push(List(args));
+        }
+        values
+    }
+}
+
+impl From<Vector<String>> for Params {
+    fn from(required: Vector<String>) -> Params {
+        Params::new(required, Vector::new(), None)
+    }
+}
+
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut params: Vec<String> = self.0.required.iter().cloned().collect();
+        params.extend(
+            self.0
+                .optional
+                .iter()
+                .map(|(name, default)| format!("[{} {}]", name, print(default.clone()))),
+        );
+        if let Some(rest) = &self.0.rest {
+            params.push(format!("& {}", rest));
+        }
+        write!(f, "{}", params.join(" "))
+    }
+}
+
+pub type NativeFunction =
+    dyn Fn(Vector<RuntimeExpression>) -> Process<RuntimeExpression> + Send + Sync;
+
+// A function implemented in Rust. Being a closure it can hold on to host state
+// like a connection or a counter.
+#[derive(Clone)]
+pub struct Builtin {
+    pub name: String,
+    pub arity: Arity,
+    pub function: Arc<NativeFunction>,
+}
+
+impl Builtin {
+    pub fn new(
+        name: &str,
+        arity: Arity,
+        function: impl Fn(Vector<RuntimeExpression>) -> Process<RuntimeExpression>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Builtin {
+        Builtin {
+            name: name.to_owned(),
+            arity,
+            function: Arc::new(function),
+        }
+    }
+
+    pub fn call(&self, args: Vector<RuntimeExpression>) -> Process<RuntimeExpression> {
+        (self.function)(args)
+    }
+}
+
+// Two builtins are only equal if they share the same closure
+impl PartialEq for Builtin {
+    fn eq(&self, other: &Builtin) -> bool {
+        self.name == other.name
+            && self.arity == other.arity
+            && Arc::ptr_eq(&self.function, &other.function)
+    }
+}
+
+impl fmt::Debug for Builtin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Builtin({} {:?})", self.name, self.arity)
+    }
+}
+
+static NEXT_PROCESS_ID: AtomicUsize = AtomicUsize::new(1);
+static COMPLETIONS: AtomicUsize = AtomicUsize::new(0);
+
+// A process's pid, which spawn and self return. Messages sent to the process
+// wait in its mailbox until it receives them. A spawned process fills in its
+// result when it completes, and anything holding a copy of the handle can wait
+// for it. Results are numbered as they come in so await-any can tell which
+// process finished first.
+#[derive(Clone)]
+pub struct ProcessHandle {
+    id: usize,
+    state: Arc<ProcessState>,
+}
+
+struct ProcessState {
+    result: OnceLock<(usize, RuntimeExpression)>,
+    mailbox: Mutex<Vector<RuntimeExpression>>,
+    // Set when the process is told to exit, and once it has finished
+    exit_reason: Mutex<Option<RuntimeExpression>>,
+    links: Mutex<Vector<ProcessHandle>>,
+    monitors: Mutex<Vector<ProcessHandle>>,
+    traps_exits: AtomicBool,
+    top_level: bool,
+    priority: Priority,
+}
+
+impl ProcessHandle {
+    pub fn new() -> ProcessHandle {
+        ProcessHandle::with_state(false, Priority::Normal)
+    }
+
+    pub fn with_priority(priority: Priority) -> ProcessHandle {
+        ProcessHandle::with_state(false, priority)
+    }
+
+    // The pid of code that isn't in a spawned process. It can't be made to
+    // exit, so exits of processes linked to it arrive as messages.
+    pub fn top_level() -> ProcessHandle {
+        ProcessHandle::with_state(true, Priority::Normal)
+    }
+
+    fn with_state(top_level: bool, priority: Priority) -> ProcessHandle {
+        ProcessHandle {
+            id: NEXT_PROCESS_ID.fetch_add(1, Ordering::Relaxed),
+            state: Arc::new(ProcessState {
+                result: OnceLock::new(),
+                mailbox: Mutex::new(Vector::new()),
+                exit_reason: Mutex::new(None),
+                links: Mutex::new(Vector::new()),
+                monitors: Mutex::new(Vector::new()),
+                traps_exits: AtomicBool::new(top_level),
+                top_level,
+                priority,
+            }),
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    // None until the process completes
+    pub fn result(&self) -> Option<RuntimeExpression> {
+        self.state.result.get().map(|(_, result)| result.clone())
+    }
+
+    // Lower for processes that completed earlier
+    pub(crate) fn completed_at(&self) -> Option<usize> {
+        self.state
+            .result
+            .get()
+            .map(|(completed_at, _)| *completed_at)
+    }
+
+    pub(crate) fn complete(&self, result: RuntimeExpression) {
+        let completed_at = COMPLETIONS.fetch_add(1, Ordering::Relaxed);
+        let _ = self.state.result.set((completed_at, result));
+        scheduler::notify();
+    }
+
+    pub fn send(&self, message: RuntimeExpression) {
+        self.state.mailbox.lock().unwrap().push_back(message);
+        scheduler::notify();
+    }
+
+    pub(crate) fn message_count(&self) -> usize {
+        self.state.mailbox.lock().unwrap().len()
+    }
+
+    // Removes the first message that select picks something out of. If there
+    // isn't one, returns how many messages were looked at, so a waiting
+    // process can tell which messages arrive after that, even if they're sent
+    // from another thread straight away.
+    pub(crate) fn take_message<T>(
+        &self,
+        select: impl Fn(&RuntimeExpression) -> Option<T>,
+    ) -> Result<T, usize> {
+        let mut mailbox = self.state.mailbox.lock().unwrap();
+        let (index, selected) = mailbox
+            .iter()
+            .enumerate()
+            .find_map(|(index, message)| select(message).map(|selected| (index, selected)))
+            .ok_or(mailbox.len())?;
+        mailbox.remove(index);
+        Ok(selected)
+    }
+
+    pub fn priority(&self) -> Priority {
+        self.state.priority
+    }
+
+    pub fn is_top_level(&self) -> bool {
+        self.state.top_level
+    }
+
+    pub(crate) fn exit_reason(&self) -> Option<RuntimeExpression> {
+        self.state.exit_reason.lock().unwrap().clone()
+    }
+
+    // The first reason given is the one the process exits with
+    pub(crate) fn set_exit_reason(&self, reason: RuntimeExpression) {
+        self.state.exit_reason.lock().unwrap().get_or_insert(reason);
+        scheduler::notify();
+    }
+
+    pub(crate) fn traps_exits(&self) -> bool {
+        self.state.traps_exits.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn trap_exits(&self) {
+        self.state.traps_exits.store(true, Ordering::Relaxed)
+    }
+
+    // Links and monitors can only be added while the process is running.
+    // Checking under the same lock finish takes them with means one added from
+    // another thread as the process finishes can't be missed. Returns whether
+    // it was added.
+    pub(crate) fn add_link(&self, other: &ProcessHandle) -> bool {
+        let mut links = self.state.links.lock().unwrap();
+        if self.result().is_some() {
+            return false;
+        }
+        if !links.contains(other) {
+            links.push_back(other.clone());
+        }
+        true
+    }
+
+    pub(crate) fn remove_link(&self, other: &ProcessHandle) {
+        self.state
+            .links
+            .lock()
+            .unwrap()
+            .retain(|link| link != other);
+    }
+
+    pub(crate) fn add_monitor(&self, monitor: &ProcessHandle) -> bool {
+        let mut monitors = self.state.monitors.lock().unwrap();
+        if self.result().is_some() {
+            return false;
+        }
+        monitors.push_back(monitor.clone());
+        true
+    }
+
+    // Emptied when the process finishes, so links between processes don't
+    // keep each other alive
+    pub(crate) fn take_links(&self) -> Vector<ProcessHandle> {
+        std::mem::take(&mut *self.state.links.lock().unwrap())
+    }
+
+    pub(crate) fn take_monitors(&self) -> Vector<ProcessHandle> {
+        std::mem::take(&mut *self.state.monitors.lock().unwrap())
+    }
 }
 
+impl Default for ProcessHandle {
+    fn default() -> Self {
+        ProcessHandle::new()
+    }
+}
+
+impl PartialEq for ProcessHandle {
+    fn eq(&self, other: &ProcessHandle) -> bool {
+        self.id == other.id
+    }
+}
+
+impl fmt::Debug for ProcessHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ProcessHandle({})", self.id)
+    }
+}
+
+// The expressions a function runs, along with the bytecode compiled from them
+// the first time the bytecode backend calls the function. Copies of a
+// function share the bytecode.
+#[derive(Clone)]
+pub struct Body {
+    expressions: Vector<RuntimeExpression>,
+    compiled: Arc<OnceLock<Arc<Chunk>>>,
+}
+
+impl Body {
+    pub fn expressions(&self) -> &Vector<RuntimeExpression> {
+        &self.expressions
+    }
+
+    pub fn compiled(&self) -> Arc<Chunk> {
+        self.compiled
+            .get_or_init(|| Arc::new(compile_body(&self.expressions)))
+            .clone()
+    }
+}
+
+impl From<Vector<RuntimeExpression>> for Body {
+    fn from(expressions: Vector<RuntimeExpression>) -> Body {
+        Body {
+            expressions,
+            compiled: Arc::new(OnceLock::new()),
+        }
+    }
+}
+
+impl PartialEq for Body {
+    fn eq(&self, other: &Body) -> bool {
+        self.expressions == other.expressions
+    }
+}
+
+impl fmt::Debug for Body {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.expressions.fmt(f)
+    }
+}
+
+// Builtin macros compare by address, which is good enough to tell them apart
+#[allow(unpredictable_function_pointer_comparisons)]
 #[derive(PartialEq, Debug, Clone)]
 pub enum RuntimeExpression {
-    // Maybe builtin functions should have a name for more useful printing
-    BuiltinFunction(fn(Vector<RuntimeExpression>) -> Process<RuntimeExpression>),
-    Function(Vector<String>, Environment, Vector<RuntimeExpression>),
-    TaggedTuple(Arc<RuntimeExpression>, Vector<RuntimeExpression>),
-    Hole,
+    BuiltinFunction(Builtin),
+    // Functions and macros remember the name they were first defined with, if
+    // any, so they can say who they are when printed
+    Function(Option<String>, Params, Environment, Body),
+    TaggedTuple(Arc<RuntimeExpression>, Vector<RuntimeExpression>, Span),
+    Hole(Span),
     List(Vector<RuntimeExpression>),
     BuiltinMacro(
+        String,
         Vector<String>,
         fn(Vector<RuntimeExpression>, Environment) -> Process<RuntimeExpression>,
     ),
-    Macro(Vector<String>, Environment, Vector<RuntimeExpression>),
-    MacroCall(String, Vector<RuntimeExpression>),
+    Macro(
+        Option<String>,
+        Params,
+        Environment,
+        Vector<RuntimeExpression>,
+    ),
+    MacroCall(String, Vector<RuntimeExpression>, Span),
     Number(u128),
     String(String),
-    Symbol(String),
+    Symbol(String, Span),
+    // A symbol that resolve found in an enclosing frame
+    Local(String, Slot, Span),
     Keyword(String),
     Definition(String, Arc<RuntimeExpression>),
+    // A spawned process, which can be awaited
+    Handle(ProcessHandle),
+}
+
+impl RuntimeExpression {
+    pub fn builtin(
+        name: &str,
+        arity: Arity,
+        function: impl Fn(Vector<RuntimeExpression>) -> Process<RuntimeExpression>
+            + Send
+            + Sync
+            + 'static,
+    ) -> RuntimeExpression {
+        BuiltinFunction(Builtin::new(name, arity, function))
+    }
 }
 
 pub fn print(expression: RuntimeExpression) -> String {
     match expression {
-        BuiltinFunction(..) => s!("BuiltinFunction(..)"),
-        Function(args, _env, _body) => format!("Function([{}] _)", print_strings(args, " ")),
-        BuiltinMacro(args, _body) => format!("BuiltinMacro([{}] _)", print_strings(args, " ")),
+        BuiltinFunction(builtin) => format!("{}/{}", builtin.name, builtin.arity),
+        Function(Some(name), params, _env, _body) => format!("Function {}([{}])", name, params),
+        Function(None, params, _env, _body) => format!("Function([{}] _)", params),
+        BuiltinMacro(name, args, _body) => {
+            format!("BuiltinMacro {}([{}])", name, print_strings(args, " "))
+        }
         Definition(name, value) => format!("Definition({} {})", name, print((*value).clone())),
-        Hole => s!("_"),
+        Handle(handle) => format!("Process({})", handle.id()),
+        Hole(_span) => s!("_"),
         Keyword(name) => format!(":{}", name),
-        List(values) => format!("[{}]", print_many(values.clone(), " ")),
-        Macro(args, _env, _body) => format!("Macro([{}] _)", print_strings(args, " ")),
-        MacroCall(name, args) => format!("{}({})", name, print_many(args, " ")),
+        List(values) => format!("[{}]", print_many(values, " ")),
+        Macro(Some(name), params, _env, _body) => format!("Macro {}([{}])", name, params),
+        Macro(None, params, _env, _body) => format!("Macro([{}] _)", params),
+        MacroCall(name, args, _span) => format!("{}({})", name, print_many(args, " ")),
         Number(value) => format!("{}", value),
         NString(value) => format!("\"{}\"", value),
-        Symbol(name) => name,
-        TaggedTuple(tag, values) => {
+        Symbol(name, _span) | Local(name, _, _span) => name,
+        TaggedTuple(tag, values, _span) => {
             format!("{}({})", print((*tag).clone()), print_many(values, " "))
         }
     }
@@ -72,7 +535,7 @@ pub fn print(expression: RuntimeExpression) -> String {
 pub fn print_many(expressions: Vector<RuntimeExpression>, seperator: &str) -> String {
     expressions
         .into_iter()
-        .map(|item| print(item))
+        .map(print)
         .collect::<Vec<_>>()
         .join(seperator)
 }
@@ -2,6 +2,7 @@ use im::Vector;
 
 use crate::expressions::RuntimeExpression;
 use crate::expressions::RuntimeExpression::{Function, Macro};
+use crate::parsers::nana::{input, Input, ParseResult};
 
 #[macro_export]
 macro_rules! s {
@@ -15,10 +16,18 @@ macro_rules! s {
 pub fn strip_functions(expressions: Vector<RuntimeExpression>) -> Vector<RuntimeExpression> {
     expressions
         .into_iter()
-        .filter(|e| match e {
-            Function(..) => false,
-            Macro(..) => false,
-            _ => true,
-        })
+        .filter(|e| !matches!(e, Function(..) | Macro(..)))
         .collect()
 }
+
+// Runs a parser over a string that isn't from a file. The unparsed remainder
+// is returned as a plain &str and errors are discarded so results can be
+// compared directly in tests.
+pub fn parse<'a, O>(
+    mut parser: impl FnMut(Input<'a>) -> ParseResult<'a, O>,
+    code: &'a str,
+) -> Result<(&'a str, O), ()> {
+    parser(input(code))
+        .map(|(rest, output)| (*rest.fragment(), output))
+        .map_err(|_| ())
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use im::Vector;
+
+use crate::errors::{not_found_error, out_of_fuel_error};
+use crate::eval::{apply, execute_program, Backend};
+use crate::expressions::{
+    Arity, Builtin, Environment, LexicalExpression, ProcessHandle, RuntimeExpression,
+};
+use crate::mailbox::as_process;
+use crate::parsers::diagnostics::{parse_program, Diagnostic};
+use crate::parsers::nana::{input, input_from_file};
+use crate::process::{Fuel, Process, RunResult, Value};
+use crate::scheduler::{Reductions, Scheduler};
+use crate::standard_library::core::standard_library;
+
+// Nana embedded in a Rust program. Definitions made by one call are visible to
+// the next, so a program can be loaded once and then called into.
+//
+// Any processes spawned by a call are run to completion before it returns.
+// Errors raised by Nana code are returned as :error(...) values, just as they
+// would be to Nana code. Only problems that stop code from running at all
+// are returned as an Err.
+#[derive(Clone)]
+pub struct Interpreter {
+    environment: Environment,
+    backend: Backend,
+    fuel: Fuel,
+    // Makes a fresh scheduler for each run
+    scheduler: Arc<dyn Fn() -> Box<dyn Scheduler> + Send + Sync>,
+    // More than one runs processes on a work-stealing pool of threads instead
+    #[cfg(not(target_arch = "wasm32"))]
+    threads: usize,
+}
+
+pub(crate) type SpawnedResults = Vector<Vector<RuntimeExpression>>;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    Syntax(Vector<Diagnostic>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "{}", error),
+            Error::Syntax(diagnostics) => {
+                let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+                write!(f, "{}", messages.join("\n"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Interpreter {
+    // An interpreter with just the standard library defined
+    pub fn new() -> Interpreter {
+        Interpreter::with_environment(standard_library())
+    }
+
+    pub fn with_environment(environment: Environment) -> Interpreter {
+        Interpreter {
+            environment,
+            backend: Backend::default(),
+            fuel: Fuel::unlimited(),
+            scheduler: Arc::new(|| Box::new(Reductions::default())),
+            #[cfg(not(target_arch = "wasm32"))]
+            threads: 1,
+        }
+    }
+
+    // Runs top level forms on the bytecode VM rather than the tree walking
+    // evaluator
+    pub fn set_backend(&mut self, backend: Backend) {
+        self.backend = backend;
+    }
+
+    // Limits how much work each call can do, so code that loops forever can't
+    // hang the program embedding it. A call that runs out gives an
+    // :error(:out-of-fuel ...) as its result.
+    pub fn set_fuel(&mut self, fuel: Fuel) {
+        self.fuel = fuel;
+    }
+
+    pub fn fuel(&self) -> Fuel {
+        self.fuel
+    }
+
+    // Picks whose turn it is while processes run. Each call gets a scheduler
+    // of its own from make_scheduler. The default counts reductions.
+    pub fn set_scheduler<T: Scheduler + 'static>(
+        &mut self,
+        make_scheduler: impl Fn() -> T + Send + Sync + 'static,
+    ) {
+        self.scheduler = Arc::new(move || Box::new(make_scheduler()));
+    }
+
+    // Runs processes on this many threads, each stealing work from the others
+    // when it runs out. Processes then complete in whatever order the threads
+    // get to them, rather than the scheduler's. One thread, the default, uses
+    // the scheduler.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    fn run_process<T: Value>(&self, process: Process<T>) -> RunResult<T, T> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.threads > 1 {
+            return process.run_on_threads(self.threads, self.fuel);
+        }
+        process.run_with(&mut *(self.scheduler)(), self.fuel)
+    }
+
+    // Returns the result of each top level form
+    pub fn eval_str(&mut self, code: &str) -> Result<Vector<RuntimeExpression>, Error> {
+        self.run(parse_program(input(code)))
+            .map(|(results, _spawned)| results)
+            .map_err(Error::Syntax)
+    }
+
+    // Like eval_str, but errors will say which file they came from
+    pub fn eval_file(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> Result<Vector<RuntimeExpression>, Error> {
+        let path = path.as_ref();
+        let code = fs::read_to_string(path).map_err(Error::Io)?;
+        let file = path.to_string_lossy();
+        self.run(parse_program(input_from_file(&code, &file)))
+            .map(|(results, _spawned)| results)
+            .map_err(Error::Syntax)
+    }
+
+    // Runs code and returns the result of each top level form, along with the
+    // results of any processes it spawned. Code that can't be parsed isn't run
+    // at all, so it defines nothing.
+    //
+    // The closure handing back a RunResult trips result_large_err, but its Err
+    // is only made when the fuel runs out
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn run(
+        &mut self,
+        parsed: Result<Vector<LexicalExpression>, Vector<Diagnostic>>,
+    ) -> Result<(Vector<RuntimeExpression>, SpawnedResults), Vector<Diagnostic>> {
+        let ((results, environment), spawned) =
+            execute_program(parsed?, self.environment.clone(), self.backend, |process| {
+                self.run_process(process)
+            });
+        self.environment = environment;
+        Ok((results, spawned))
+    }
+
+    pub fn define(&mut self, name: &str, value: impl Into<RuntimeExpression>) {
+        self.environment = self.environment.clone().add(name.to_owned(), value.into());
+    }
+
+    // Defines a function implemented in Rust. It can capture whatever state
+    // it needs.
+    pub fn define_builtin(
+        &mut self,
+        name: &str,
+        arity: Arity,
+        function: impl Fn(Vector<RuntimeExpression>) -> Process<RuntimeExpression>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.register(vec![Builtin::new(name, arity, function)]);
+    }
+
+    // Defines every builtin in a native module
+    pub fn register(&mut self, module: impl IntoIterator<Item = Builtin>) {
+        self.environment = self.environment.clone().add_module(module);
+    }
+
+    pub fn get(&self, name: &str) -> Option<RuntimeExpression> {
+        self.environment.get(name)
+    }
+
+    // Calls a function defined in Nana (or a builtin) with already evaluated
+    // arguments
+    pub fn call(&self, function_name: &str, args: Vec<RuntimeExpression>) -> RuntimeExpression {
+        let process = match self.environment.get(function_name) {
+            Some(function) => {
+                let args: Vector<_> = args.into_iter().collect();
+                let call =
+                    Process::Running(Arc::new(move || apply(function.clone(), args.clone())));
+                as_process(ProcessHandle::top_level(), call)
+            }
+            None => not_found_error(&format!("No function '{}' found", function_name)),
+        };
+        match self.run_process(process) {
+            Ok((result, _spawned)) => result,
+            Err(out_of_fuel) => match out_of_fuel.completed {
+                (Some(result), _spawned) => result,
+                (None, _spawned) => out_of_fuel_error(out_of_fuel.steps, out_of_fuel.timed_out),
+            },
+        }
+    }
+
+    // The names of everything defined, in alphabetical order
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.environment.iter().map(|(name, _)| name).collect();
+        names.sort();
+        names
+    }
+
+    pub fn environment(&self) -> &Environment {
+        &self.environment
+    }
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Interpreter::new()
+    }
+}
@@ -0,0 +1,156 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use im::vector;
+
+use crate::clock::{with_clock, Clock, VirtualClock};
+use crate::errors::error_variety;
+use crate::eval::Backend;
+use crate::expressions::RuntimeExpression::Number;
+use crate::expressions::{print, Arity};
+use crate::interpreter::{Error, Interpreter};
+use crate::parsers::diagnostics::parse_program;
+use crate::parsers::nana::input;
+use crate::process::Fuel;
+use crate::process::Process::Complete;
+
+#[test]
+fn test_definitions_are_kept_between_calls() {
+    let mut interpreter = Interpreter::new();
+
+    interpreter.eval_str("Func double [n] add(n n)").unwrap();
+    let results = interpreter.eval_str("double(21)").unwrap();
+
+    assert_eq!(results, vector![Number(42)]);
+}
+
+#[test]
+fn test_define_get_and_call() {
+    let mut interpreter = Interpreter::new();
+    interpreter.define("limit", 10);
+    interpreter
+        .eval_str("Func under-limit [n] subtract(limit n)")
+        .unwrap();
+
+    assert_eq!(interpreter.get("limit"), Some(Number(10)));
+    assert_eq!(interpreter.get("missing"), None);
+    assert_eq!(interpreter.call("under-limit", vec![Number(3)]), Number(7));
+    assert_eq!(interpreter.call("add", vec![1.into(), 2.into()]), Number(3));
+
+    let missing = interpreter.call("missing", vec![]);
+    assert_eq!(error_variety(&missing), Some("not-found"));
+}
+
+#[test]
+fn test_syntax_errors_are_returned_as_errors() {
+    let mut interpreter = Interpreter::new();
+
+    match interpreter.eval_str("Def x 1\n[1 2") {
+        Err(Error::Syntax(diagnostics)) => assert_eq!(diagnostics.len(), 1),
+        _ => panic!("Expected a syntax error"),
+    }
+    assert_eq!(interpreter.get("x"), None);
+}
+
+#[test]
+fn test_eval_file() {
+    let mut interpreter = Interpreter::new();
+
+    let results = interpreter.eval_file("examples/spawn.nana").unwrap();
+    let last = results.last().map(|result| print(result.clone()));
+    assert_eq!(
+        last.as_deref(),
+        Some("[[:c :done] [:b :done] [[:a :done] [:b :done]]]")
+    );
+
+    assert!(matches!(
+        interpreter.eval_file("examples/missing.nana"),
+        Err(Error::Io(_))
+    ));
+}
+
+#[test]
+fn test_builtins_can_hold_host_state() {
+    let mut interpreter = Interpreter::new();
+    let total = Arc::new(Mutex::new(0));
+    let running_total = total.clone();
+    interpreter.define_builtin("tally", Arity::Exactly(1), move |args| {
+        let mut total = running_total.lock().unwrap();
+        let Some(Number(n)) = args.head() else {
+            panic!("tally is only called with a number")
+        };
+        *total += n;
+        Complete(Number(*total))
+    });
+
+    let results = interpreter.eval_str("tally(2)\ntally(3)").unwrap();
+
+    assert_eq!(results, vector![Number(2), Number(5)]);
+    assert_eq!(*total.lock().unwrap(), 5);
+}
+
+#[test]
+fn test_builtins_check_their_arity() {
+    for backend in [Backend::TreeWalking, Backend::Bytecode] {
+        let mut interpreter = Interpreter::new();
+        interpreter.set_backend(backend);
+        interpreter.define_builtin("pair", Arity::Exactly(2), |args| {
+            Complete(Number(args.len() as u128))
+        });
+
+        let results = interpreter.eval_str("pair(1)\npair(1 2)").unwrap();
+
+        assert_eq!(
+            print(results[0].clone()),
+            ":error(:arity \"pair takes 2 arguments but was given 1\" :at(\"1:1\"))"
+        );
+        assert_eq!(results[1], Number(2));
+    }
+}
+
+#[test]
+fn test_code_that_runs_out_of_fuel_stops_with_an_error() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_fuel(Fuel::steps(1000));
+
+    let results = interpreter
+        .eval_str("Func forever [] forever()\n:before\nforever()\n:after")
+        .unwrap();
+    let printed: Vec<_> = results.into_iter().skip(1).map(print).collect();
+    assert_eq!(
+        printed,
+        [
+            ":before",
+            ":error(:out-of-fuel \"Ran out of fuel after 1000 steps\")"
+        ]
+    );
+
+    // Processes that outlive the code that spawned them are stopped too
+    let (results, spawned) = interpreter
+        .run(parse_program(input("spawn(Fn [] forever())\n:done")))
+        .unwrap();
+    assert_eq!(print(results.last().unwrap().clone()), ":done");
+    assert_eq!(
+        error_variety(spawned.last().unwrap().last().unwrap()),
+        Some("out-of-fuel")
+    );
+
+    assert_eq!(
+        error_variety(&interpreter.call("forever", vec![])),
+        Some("out-of-fuel")
+    );
+}
+
+#[test]
+fn test_time_limits() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_fuel(Fuel::unlimited().with_time_limit(Duration::from_secs(1)));
+
+    let clock = VirtualClock::default();
+    let results = with_clock(Arc::new(clock.clone()), || {
+        interpreter.eval_str("Do [sleep(60000) :woke]").unwrap()
+    });
+
+    assert_eq!(error_variety(&results[0]), Some("out-of-fuel"));
+    assert_eq!(clock.now(), Duration::from_secs(1));
+}
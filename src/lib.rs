@@ -1,9 +1,29 @@
 #![allow(dead_code)]
+// Checking that RuntimeExpression is Send and Sync goes deep, since it
+// contains itself through handles, environments and processes
+#![recursion_limit = "256"]
 
+#[cfg(feature = "component")]
 #[allow(warnings)]
 mod bindings;
 mod helpers;
 
+mod bytecode;
+mod check;
+#[cfg(test)]
+mod check_test;
+#[cfg(feature = "cli")]
+pub mod cli;
+mod clock;
+
+#[cfg(feature = "component")]
+mod component;
+#[cfg(all(test, feature = "component"))]
+mod component_test;
+
+mod conversions;
+#[cfg(test)]
+mod conversions_test;
 mod environment;
 #[cfg(test)]
 mod environment_test;
@@ -12,8 +32,20 @@ mod eval;
 #[cfg(test)]
 mod expression_test;
 mod expressions;
+mod interpreter;
+#[cfg(test)]
+mod interpreter_test;
+mod mailbox;
+mod output;
 mod parsers;
 mod process;
+mod resolve;
+#[cfg(test)]
+mod resolve_test;
+mod scheduler;
+#[cfg(test)]
+mod scheduler_test;
+mod span;
 mod standard_library;
 
 #[cfg(test)]
@@ -21,39 +53,24 @@ mod eval_test;
 mod example_tests;
 #[cfg(test)]
 mod process_test;
+mod run;
+#[cfg(test)]
+mod run_test;
+mod session;
+#[cfg(test)]
+mod session_test;
+// wasm has no threads to spare, so it only has the single threaded schedulers
+#[cfg(not(target_arch = "wasm32"))]
+mod work_stealing;
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod work_stealing_test;
 
-use bindings::exports::component::nana::nana::Guest as Nana;
-use bindings::exports::wasi::cli::run::Guest as Command;
-use eval::execute;
-use expressions::print;
-use standard_library::core::standard_library;
-
-struct Component;
-
-impl Command for Component {
-    fn run() -> Result<(), ()> {
-        execute(PROGRAM_CODE.to_owned(), standard_library());
-        Ok(())
-    }
-}
-
-impl Nana for Component {
-    fn evaluate(name: String) -> String {
-        let results = execute(name, standard_library());
-        results
-            .into_iter()
-            .map(|(result, _env)| {
-                result
-                    .into_iter()
-                    .map(|item| print(item))
-                    .collect::<Vec<_>>()
-                    .join("\n")
-            })
-            .collect::<Vec<_>>()
-            .join("\n#-------- Results from processes in order of completion ----------------\n")
-    }
-}
-
-bindings::export!(Component with_types_in bindings);
-
-pub static PROGRAM_CODE: &str = include_str!("../examples/main.nana");
+// The embedding API. Everything else is an implementation detail.
+pub use environment::Environment;
+pub use eval::Backend;
+pub use expressions::{print, Arity, Builtin, RuntimeExpression};
+pub use interpreter::{Error, Interpreter};
+pub use parsers::diagnostics::Diagnostic;
+pub use process::{Fuel, OutOfFuel, Process, Value};
+pub use scheduler::{Priority, Reductions, RoundRobin, Scheduler, TaskId};
+pub use span::Span;
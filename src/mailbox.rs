@@ -0,0 +1,287 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use im::{vector, Vector};
+
+use crate::clock;
+use crate::errors::is_error;
+use crate::expressions::ProcessHandle;
+use crate::expressions::RuntimeExpression::{self, Handle, Keyword, List, TaggedTuple};
+use crate::process::Process::{self, Complete, Running, Spawn, Waiting};
+use crate::process::{Stepable, Value, Wake};
+use crate::s;
+use crate::scheduler::Priority;
+use crate::span::Span;
+
+thread_local! {
+    static CURRENT_PROCESS: RefCell<Option<ProcessHandle>> = const { RefCell::new(None) };
+}
+
+// The pid of the process being stepped, if any
+pub(crate) fn current_process() -> Option<ProcessHandle> {
+    CURRENT_PROCESS.with(|current| current.borrow().clone())
+}
+
+// Makes pid the current process whenever this process is stepped. Processes
+// it spawns aren't affected, they have pids of their own.
+pub(crate) fn as_process<I: Value, S: Value>(
+    pid: ProcessHandle,
+    process: Process<I, S>,
+) -> Process<I, S> {
+    match process {
+        Complete(result) => Complete(result),
+        Running(stepable) => Running(Arc::new(AsProcess(pid, stepable))),
+        Spawn(continuation, spawned) => {
+            Spawn(Arc::new(as_process(pid, (*continuation).clone())), spawned)
+        }
+        Waiting(wake, continuation) => {
+            Waiting(wake, Arc::new(as_process(pid, (*continuation).clone())))
+        }
+    }
+}
+
+struct AsProcess<I: Clone, S: Clone>(ProcessHandle, Arc<dyn Stepable<I, S>>);
+
+impl<I: Value, S: Value> Stepable<I, S> for AsProcess<I, S> {
+    fn step(&self) -> Process<I, S> {
+        let AsProcess(pid, stepable) = self;
+        let previous = CURRENT_PROCESS.with(|current| current.replace(Some(pid.clone())));
+        let next = stepable.step();
+        CURRENT_PROCESS.with(|current| current.replace(previous));
+        as_process(pid.clone(), next)
+    }
+
+    fn priority(&self) -> Priority {
+        self.0.priority()
+    }
+}
+
+// Ready once the mailbox holds more messages than the process had already
+// looked at when it started waiting, or once the deadline passes
+pub(crate) struct MessageWake {
+    pid: ProcessHandle,
+    seen: usize,
+    deadline: Option<Duration>,
+    abandoned: AtomicBool,
+}
+
+impl MessageWake {
+    pub(crate) fn new(pid: ProcessHandle, seen: usize, deadline: Option<Duration>) -> MessageWake {
+        MessageWake {
+            pid,
+            seen,
+            deadline,
+            abandoned: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn abandoned(&self) -> bool {
+        self.abandoned.load(Ordering::Relaxed)
+    }
+}
+
+impl Wake for MessageWake {
+    fn ready(&self) -> bool {
+        self.pid.message_count() > self.seen
+            || self
+                .deadline
+                .is_some_and(|deadline| clock::now() >= deadline)
+            || self.abandoned()
+    }
+
+    fn deadline(&self) -> Option<Duration> {
+        self.deadline
+    }
+
+    fn abandon(&self) {
+        self.abandoned.store(true, Ordering::Relaxed)
+    }
+}
+
+// Ready once any of the processes completes
+pub(crate) struct CompletionWake {
+    handles: Vector<ProcessHandle>,
+    abandoned: AtomicBool,
+}
+
+impl CompletionWake {
+    pub(crate) fn new(handles: Vector<ProcessHandle>) -> CompletionWake {
+        CompletionWake {
+            handles,
+            abandoned: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn abandoned(&self) -> bool {
+        self.abandoned.load(Ordering::Relaxed)
+    }
+}
+
+impl Wake for CompletionWake {
+    fn ready(&self) -> bool {
+        self.handles.iter().any(|handle| handle.result().is_some()) || self.abandoned()
+    }
+
+    fn deadline(&self) -> Option<Duration> {
+        None
+    }
+
+    fn abandon(&self) {
+        self.abandoned.store(true, Ordering::Relaxed)
+    }
+}
+
+// Lets a spawned process be ended from outside. Once it has an exit reason it
+// completes with :exit(reason) rather than taking another step, and stops
+// waiting if it was.
+pub(crate) fn exitable(
+    pid: ProcessHandle,
+    process: Process<RuntimeExpression>,
+) -> Process<RuntimeExpression> {
+    match process {
+        Complete(result) => Complete(result),
+        Running(stepable) => Running(Arc::new(Exitable(pid, stepable))),
+        Spawn(continuation, spawned) => {
+            Spawn(Arc::new(exitable(pid, (*continuation).clone())), spawned)
+        }
+        Waiting(wake, continuation) => Waiting(
+            Arc::new(ExitWake(pid.clone(), wake)),
+            // Stepped, even if it's complete, so the exit reason is checked
+            Arc::new(Running(Arc::new(Exitable(
+                pid,
+                Arc::new(move || (*continuation).clone()),
+            )))),
+        ),
+    }
+}
+
+struct Exitable(ProcessHandle, Arc<dyn Stepable<RuntimeExpression>>);
+
+impl Stepable<RuntimeExpression> for Exitable {
+    fn step(&self) -> Process<RuntimeExpression> {
+        let Exitable(pid, stepable) = self;
+        match pid.exit_reason() {
+            Some(reason) => Complete(tagged("exit", vector![reason])),
+            None => exitable(pid.clone(), stepable.step()),
+        }
+    }
+
+    fn priority(&self) -> Priority {
+        self.0.priority()
+    }
+}
+
+struct ExitWake(ProcessHandle, Arc<dyn Wake>);
+
+impl Wake for ExitWake {
+    fn ready(&self) -> bool {
+        self.0.exit_reason().is_some() || self.1.ready()
+    }
+
+    fn deadline(&self) -> Option<Duration> {
+        self.1.deadline()
+    }
+
+    fn abandon(&self) {
+        self.1.abandon()
+    }
+}
+
+// Ready once the clock reaches the deadline
+pub(crate) struct Timer(pub(crate) Duration);
+
+impl Wake for Timer {
+    fn ready(&self) -> bool {
+        clock::now() >= self.0
+    }
+
+    fn deadline(&self) -> Option<Duration> {
+        Some(self.0)
+    }
+
+    fn abandon(&self) {}
+}
+
+// Ready straight away. Waiting on it makes a process give up its turn, even
+// within the bytecode VM, so an exit takes effect before it goes any further.
+pub(crate) struct Yield;
+
+impl Wake for Yield {
+    fn ready(&self) -> bool {
+        true
+    }
+
+    fn deadline(&self) -> Option<Duration> {
+        None
+    }
+
+    fn abandon(&self) {}
+}
+
+// Records how a spawned process ended and tells the processes watching it.
+// The reason is :normal, the error it completed with, or the reason it was
+// told to exit with.
+pub(crate) fn finish(pid: &ProcessHandle, result: RuntimeExpression) -> RuntimeExpression {
+    let reason = match pid.exit_reason() {
+        Some(reason) => reason,
+        None if is_error(&result) => result.clone(),
+        None => Keyword(s!("normal")),
+    };
+    pid.set_exit_reason(reason.clone());
+    pid.complete(result.clone());
+
+    for monitor in pid.take_monitors() {
+        monitor.send(notice("down", pid, reason.clone()));
+    }
+    for linked in pid.take_links() {
+        linked.remove_link(pid);
+        signal(&linked, pid, reason.clone());
+    }
+    result
+}
+
+// Tells a process that from exited. Processes that trap exits get a message,
+// the rest exit too, unless from exited normally.
+pub(crate) fn signal(pid: &ProcessHandle, from: &ProcessHandle, reason: RuntimeExpression) {
+    if pid.result().is_some() {
+        return;
+    }
+    if pid.traps_exits() {
+        pid.send(notice("exit", from, reason));
+    } else if reason != Keyword(s!("normal")) {
+        pid.set_exit_reason(reason);
+    }
+}
+
+// Either process exiting makes the other exit too
+pub(crate) fn link(pid: &ProcessHandle, other: &ProcessHandle) {
+    if pid == other {
+        return;
+    }
+    if other.add_link(pid) {
+        pid.add_link(other);
+    } else if let Some(reason) = other.exit_reason() {
+        signal(pid, other, reason)
+    }
+}
+
+// The monitor is sent [:down pid reason] when pid exits, straight away if it
+// already has
+pub(crate) fn monitor(monitor: &ProcessHandle, pid: &ProcessHandle) {
+    if pid.add_monitor(monitor) {
+        return;
+    }
+    if let Some(reason) = pid.exit_reason() {
+        monitor.send(notice("down", pid, reason))
+    }
+}
+
+fn notice(kind: &str, pid: &ProcessHandle, reason: RuntimeExpression) -> RuntimeExpression {
+    List(vector![Keyword(s!(kind)), Handle(pid.clone()), reason])
+}
+
+fn tagged(tag: &str, values: Vector<RuntimeExpression>) -> RuntimeExpression {
+    TaggedTuple(Arc::new(Keyword(s!(tag))), values, Span::default())
+}
@@ -0,0 +1,66 @@
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+
+// Somewhere for builtins like log to send their output. The standard library's
+// builtins are built once and shared by every interpreter, so a sink can't be
+// captured when they're made. Instead they write to whichever one the current
+// thread is running with, which each evaluation sets. Threads that step
+// processes for a run are given the sink of the thread that started it.
+pub trait Sink: Send + Sync {
+    fn write_line(&self, line: &str);
+}
+
+pub struct Stdout;
+
+impl Sink for Stdout {
+    fn write_line(&self, line: &str) {
+        println!("{}", line);
+    }
+}
+
+// Collects lines so they can be returned alongside an evaluation's results
+#[derive(Clone, Default)]
+pub struct Buffer(Arc<Mutex<Vec<String>>>);
+
+impl Buffer {
+    pub fn lines(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl Sink for Buffer {
+    fn write_line(&self, line: &str) {
+        self.0.lock().unwrap().push(line.to_owned());
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Arc<dyn Sink>> = RefCell::new(Arc::new(Stdout));
+}
+
+// Restores the previous sink when dropped, so a panicking evaluation doesn't
+// leave its sink installed
+struct Restore(Option<Arc<dyn Sink>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            CURRENT.with(|current| current.replace(previous));
+        }
+    }
+}
+
+// Runs f with everything written by builtins going to sink instead of stdout
+pub fn with_sink<T>(sink: Arc<dyn Sink>, f: impl FnOnce() -> T) -> T {
+    let _restore = Restore(Some(CURRENT.with(|current| current.replace(sink))));
+    f()
+}
+
+// The sink the current thread is writing to
+pub fn current_sink() -> Arc<dyn Sink> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+pub fn write_line(line: &str) {
+    current_sink().write_line(line);
+}
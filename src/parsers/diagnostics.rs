@@ -0,0 +1,123 @@
+use std::fmt;
+
+use im::{vector, Vector};
+use nom::character::complete::multispace0;
+use nom::error::{VerboseError, VerboseErrorKind};
+use nom::Slice;
+
+use super::nana::{expression, span, Input};
+use crate::errors::located_error;
+use crate::expressions::{LexicalExpression, RuntimeExpression};
+use crate::span::Span;
+
+// A problem found while parsing. Parsing carries on after a diagnostic is
+// recorded so that several mistakes can be reported at once.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Diagnostic {
+    pub expected: String,
+    pub found: String,
+    pub span: Span,
+    // The line of source the problem was found on
+    pub snippet: String,
+}
+
+impl Diagnostic {
+    fn new(position: &Input, expected: &str) -> Diagnostic {
+        // Control characters would break the message up, so they're escaped
+        let found = match position.fragment().chars().next() {
+            Some('\n') => String::from("end of line"),
+            Some(c) if c.is_control() => format!("'{}'", c.escape_default()),
+            Some(c) => format!("'{}'", c),
+            None => String::from("end of input"),
+        };
+        Diagnostic {
+            expected: expected.to_string(),
+            found,
+            span: span(position),
+            snippet: String::from_utf8_lossy(position.get_line_beginning()).into_owned(),
+        }
+    }
+
+    fn from_error(form: &Input, error: VerboseError<Input>) -> Diagnostic {
+        let expected = error.errors.iter().find_map(|(_, kind)| match kind {
+            VerboseErrorKind::Context(context) => Some(*context),
+            _ => None,
+        });
+
+        match (expected, error.errors.first()) {
+            (Some(expected), Some((position, _))) => Diagnostic::new(position, expected),
+            _ => Diagnostic::new(form, "an expression"),
+        }
+    }
+
+    pub fn message(&self) -> String {
+        format!("expected {}, found {}", self.expected, self.found)
+    }
+
+    pub fn to_error(&self) -> RuntimeExpression {
+        located_error("syntax", &self.message(), &self.span)
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.span, self.message())?;
+        writeln!(f, "  {}", self.snippet)?;
+        write!(f, "  {}^", " ".repeat(self.span.column.saturating_sub(1)))
+    }
+}
+
+// After a mistake we skip ahead to the next line that starts in the first
+// column. Top level forms start there, while the lines of a form that spans
+// several lines are indented, so this avoids reporting one mistake many times.
+fn skip_to_next_form<'a>(position: &Input<'a>) -> Input<'a> {
+    let fragment = position.fragment();
+    let mut offset = 0;
+
+    while let Some(newline) = fragment[offset..].find('\n') {
+        offset += newline + 1;
+        match fragment[offset..].chars().next() {
+            Some(c) if !c.is_whitespace() => return position.slice(offset..),
+            _ => {}
+        }
+    }
+
+    position.slice(fragment.len()..)
+}
+
+// Parses a whole program. Unlike program, which stops at the first thing it
+// can't parse, this keeps going and returns every problem it finds.
+pub fn parse_program(input: Input) -> Result<Vector<LexicalExpression>, Vector<Diagnostic>> {
+    let mut remaining = input;
+    let mut expressions = vector![];
+    let mut diagnostics = vector![];
+
+    loop {
+        let (form, _) = multispace0::<Input, VerboseError<Input>>(remaining)
+            .expect("Skipping whitespace can't fail");
+
+        if form.fragment().is_empty() {
+            break;
+        }
+
+        match expression(form.clone()) {
+            Ok((rest, expression)) => {
+                expressions.push_back(expression);
+                remaining = rest;
+            }
+            Err(nom::Err::Error(error)) | Err(nom::Err::Failure(error)) => {
+                let diagnostic = Diagnostic::from_error(&form, error);
+                let position = form.slice(diagnostic.span.offset - form.location_offset()..);
+                diagnostics.push_back(diagnostic);
+                remaining = skip_to_next_form(&position);
+            }
+            Err(nom::Err::Incomplete(_)) => unreachable!("Nana's parsers are all complete"),
+        }
+    }
+
+    if diagnostics.is_empty() {
+        Ok(expressions)
+    } else {
+        Err(diagnostics)
+    }
+}
@@ -0,0 +1,124 @@
+use im::vector;
+
+use super::diagnostics::{parse_program, Diagnostic};
+use super::nana::{input, input_from_file};
+use crate::expressions::LexicalExpression::{Comment, Number, Symbol};
+use crate::s;
+use crate::span::Span;
+
+fn diagnose(code: &str) -> Vec<(String, String)> {
+    parse_program(input_from_file(code, "test.nana"))
+        .unwrap_err()
+        .iter()
+        .map(|diagnostic| (diagnostic.span.to_string(), diagnostic.message()))
+        .collect()
+}
+
+#[test]
+fn test_parses_without_diagnostics() {
+    assert_eq!(
+        Ok(vector![
+            Number(1, Span::default()),
+            Symbol(s!("foo"), Span::default())
+        ]),
+        parse_program(input("1\nfoo"))
+    );
+}
+
+#[test]
+fn test_comment_on_last_line_without_newline() {
+    assert_eq!(
+        Ok(vector![
+            Number(1, Span::default()),
+            Comment(Span::default())
+        ]),
+        parse_program(input("1\n# the end"))
+    );
+}
+
+#[test]
+fn test_unclosed_list() {
+    assert_eq!(
+        vec![(
+            s!("test.nana:1:8"),
+            s!("expected ']' to close the list, found end of input")
+        )],
+        diagnose("[1 2 3 ")
+    );
+}
+
+#[test]
+fn test_control_characters_are_escaped() {
+    assert_eq!(
+        vec![(
+            s!("test.nana:1:5"),
+            s!("expected ')' to close the tagged tuple, found end of line")
+        )],
+        diagnose("log(\n")
+    );
+    assert_eq!(
+        vec![(
+            s!("test.nana:1:2"),
+            s!("expected ']' to close the list, found '\\t'")
+        )],
+        diagnose("[\t")
+    );
+}
+
+#[test]
+fn test_stray_closing_paren() {
+    assert_eq!(
+        vec![(s!("test.nana:2:1"), s!("expected an expression, found ')'"))],
+        diagnose("foo(1)\n)\nbar")
+    );
+}
+
+#[test]
+fn test_unclosed_string_and_tuple() {
+    assert_eq!(
+        vec![(
+            s!("test.nana:1:5"),
+            s!("expected '\"' to close the string, found end of input")
+        )],
+        diagnose("\"foo")
+    );
+    assert_eq!(
+        vec![(
+            s!("test.nana:1:10"),
+            s!("expected ')' to close the tagged tuple, found ']'")
+        )],
+        diagnose("foo(1 2 3]")
+    );
+}
+
+#[test]
+fn test_reports_several_mistakes() {
+    let code = "foo(1\n  2]\nbar\n)\nbaz(99999999999999999999999999999999999999999)";
+    assert_eq!(
+        vec![
+            (
+                s!("test.nana:2:4"),
+                s!("expected ')' to close the tagged tuple, found ']'")
+            ),
+            (s!("test.nana:4:1"), s!("expected an expression, found ')'")),
+            (
+                s!("test.nana:5:5"),
+                s!("expected a number small enough to fit in 128 bits, found '9'")
+            ),
+        ],
+        diagnose(code)
+    );
+}
+
+#[test]
+fn test_display() {
+    let diagnostic: Diagnostic = parse_program(input("[1 2\n  3 4 )"))
+        .unwrap_err()
+        .head()
+        .unwrap()
+        .clone();
+    assert_eq!(
+        "2:7: expected ']' to close the list, found ')'\n    3 4 )\n        ^",
+        diagnostic.to_string()
+    );
+}
@@ -1,19 +1,26 @@
 use super::general::*;
+use crate::helpers::parse;
 
 #[test]
 fn parse_lowercase() {
-    assert_eq!(lowercase_char("f"), Ok(("", 'f')));
-    assert_eq!(lowercase_char("foo"), Ok(("oo", 'f')));
-    assert_eq!(lower_start_word("foo"), Ok(("", "foo".to_string())));
-    assert_eq!(lower_start_word("foo Bar"), Ok((" Bar", "foo".to_string())));
-    assert!(lower_start_word("Foo Bar").is_err());
+    assert_eq!(parse(lowercase_char, "f"), Ok(("", 'f')));
+    assert_eq!(parse(lowercase_char, "foo"), Ok(("oo", 'f')));
+    assert_eq!(parse(lower_start_word, "foo"), Ok(("", "foo".to_string())));
+    assert_eq!(
+        parse(lower_start_word, "foo Bar"),
+        Ok((" Bar", "foo".to_string()))
+    );
+    assert!(parse(lower_start_word, "Foo Bar").is_err());
 }
 
 #[test]
 fn parse_uppercase() {
-    assert_eq!(uppercase_char("F"), Ok(("", 'F')));
-    assert_eq!(uppercase_char("Foo"), Ok(("oo", 'F')));
-    assert_eq!(titlecase_word("Foo"), Ok(("", "Foo".to_string())));
-    assert_eq!(titlecase_word("Foo Bar"), Ok((" Bar", "Foo".to_string())));
-    assert!(titlecase_word("foo Bar").is_err());
+    assert_eq!(parse(uppercase_char, "F"), Ok(("", 'F')));
+    assert_eq!(parse(uppercase_char, "Foo"), Ok(("oo", 'F')));
+    assert_eq!(parse(titlecase_word, "Foo"), Ok(("", "Foo".to_string())));
+    assert_eq!(
+        parse(titlecase_word, "Foo Bar"),
+        Ok((" Bar", "Foo".to_string()))
+    );
+    assert!(parse(titlecase_word, "foo Bar").is_err());
 }
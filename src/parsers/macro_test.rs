@@ -6,73 +6,83 @@ use im::vector;
 
 use super::macros::build_macros;
 use super::nana::program;
+use crate::helpers::parse;
 
 use crate::expressions::Environment;
 use crate::expressions::RuntimeExpression::{self, MacroCall};
+use crate::span::Span;
 
 pub fn create_env_with_macros() -> Environment {
     Environment::from(hashmap! {
         String::from("Package") =>
         RuntimeExpression::Macro(
-            vector!["name".to_string()],
+            None,
+            vector!["name".to_string()].into(),
             Environment::new(),
             vector![],
         ),
 
         String::from("World") =>
         RuntimeExpression::Macro(
-            vector!["name".to_string(), "body".to_string()],
+            None,
+            vector!["name".to_string(), "body".to_string()].into(),
             Environment::new(),
             vector![],
         ),
 
         String::from("Import") =>
         RuntimeExpression::Macro(
-            vector!["name".to_string()],
+            None,
+            vector!["name".to_string()].into(),
             Environment::new(),
             vector![],
         ),
 
         String::from("Export") =>
         RuntimeExpression::Macro(
+            None,
             vector![
                 "name".to_string(),
                 "args".to_string(),
                 "return_type".to_string(),
-            ],
+            ].into(),
             Environment::new(),
             vector![],
         ),
 
         String::from("Func") =>
         RuntimeExpression::Macro(
+            None,
             vector![
                 "name".to_string(),
                 "args".to_string(),
                 "return_type".to_string(),
                 "body".to_string(),
-            ],
+            ].into(),
             Environment::new(),
             vector![],
         ),
 
         String::from("Match") =>
         RuntimeExpression::Macro(
-            vector!["condition".to_string(), "branches".to_string()],
+            None,
+            vector!["condition".to_string(), "branches".to_string()].into(),
             Environment::new(),
             vector![],
         ),
 
         String::from("Let") =>
         RuntimeExpression::Macro(
-            vector!["bindings".to_string(), "body".to_string()],
+            None,
+            vector!["bindings".to_string(), "body".to_string()].into(),
             Environment::new(),
             vector![],
         ),
 
         String::from("For") =>
         RuntimeExpression::Macro(
-            vector!["binding".to_string(), "body".to_string()],
+            None,
+            vector!["binding".to_string(), "body".to_string()].into(),
             Environment::new(),
             vector![],
         ),
@@ -81,13 +91,14 @@ pub fn create_env_with_macros() -> Environment {
 
 #[test]
 fn parses_basic_macro() {
-    let result = program("Package \"foo\"")
-        .and_then(|(_, es)| Ok(build_macros(&es, &create_env_with_macros())));
+    let result = parse(program, "Package \"foo\"")
+        .map(|(_, es)| build_macros(&es, &create_env_with_macros()));
     assert_eq!(
         Ok((
             Some(MacroCall(
                 "Package".to_string(),
                 vector![RuntimeExpression::String("foo".to_string())],
+                Span::default(),
             )),
             vector![],
         )),
@@ -97,8 +108,8 @@ fn parses_basic_macro() {
 
 #[test]
 fn parses_nested_macros() {
-    let result = program("Package Package \"foo\"")
-        .and_then(|(_, es)| Ok(build_macros(&es, &create_env_with_macros())));
+    let result = parse(program, "Package Package \"foo\"")
+        .map(|(_, es)| build_macros(&es, &create_env_with_macros()));
     assert_eq!(
         Ok((
             Some(MacroCall(
@@ -106,7 +117,9 @@ fn parses_nested_macros() {
                 vector![MacroCall(
                     "Package".to_string(),
                     vector![RuntimeExpression::String("foo".to_string())],
+                    Span::default(),
                 )],
+                Span::default(),
             )),
             vector![],
         )),
@@ -125,14 +138,16 @@ fn parses_macros_in_lists() {
                     vector![MacroCall(
                         "Package".to_string(),
                         vector![RuntimeExpression::String("two".to_string())],
+                        Span::default(),
                     )],
+                    Span::default(),
                 ),
                 RuntimeExpression::Number(3)
             ])),
             vector![],
         )),
-        program("[1 Package Package \"two\" 3]")
-            .and_then(|(_, es)| Ok(build_macros(&es, &create_env_with_macros())))
+        parse(program, "[1 Package Package \"two\" 3]")
+            .map(|(_, es)| build_macros(&es, &create_env_with_macros()))
     )
 }
 
@@ -141,7 +156,10 @@ fn parses_macros_in_args_to_functions() {
     assert_eq!(
         Ok((
             Some(RuntimeExpression::TaggedTuple(
-                Arc::new(RuntimeExpression::Symbol("println".to_string())),
+                Arc::new(RuntimeExpression::Symbol(
+                    "println".to_string(),
+                    Span::default()
+                )),
                 vector![
                     RuntimeExpression::Number(1),
                     MacroCall(
@@ -149,14 +167,17 @@ fn parses_macros_in_args_to_functions() {
                         vector![MacroCall(
                             "Package".to_string(),
                             vector![RuntimeExpression::String("two".to_string())],
+                            Span::default(),
                         )],
+                        Span::default(),
                     ),
                     RuntimeExpression::Number(3)
-                ]
+                ],
+                Span::default()
             )),
             vector![],
         )),
-        program("println(1 Package Package \"two\" 3)")
-            .and_then(|(_, es)| Ok(build_macros(&es, &create_env_with_macros())))
+        parse(program, "println(1 Package Package \"two\" 3)")
+            .map(|(_, es)| build_macros(&es, &create_env_with_macros()))
     )
 }
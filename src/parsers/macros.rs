@@ -1,52 +1,87 @@
 use std::sync::Arc;
 
+use crate::errors::located_error;
+use crate::eval::macro_form_count;
 use crate::expressions::Environment;
 use crate::expressions::LexicalExpression;
 use crate::expressions::RuntimeExpression;
+use crate::span::Span;
 use im::vector;
 use im::Vector;
 
 fn build_macro_args(
-    params: Vector<String>,
+    count: usize,
     rest: Vector<LexicalExpression>,
     environment: &Environment,
-    name: &String,
+    name: &str,
+    span: &Span,
 ) -> (Option<RuntimeExpression>, Vector<LexicalExpression>) {
-    let (final_args, new_rest) =
-        (0..params.len()).fold((Vector::new(), rest), |(args, curr_rest), _| {
-            let (arg, remainder) = build_macros(&curr_rest, &environment);
-            let new_args = arg
-                .and_then(|arg| Some(args.clone() + Vector::unit(arg)))
-                .unwrap_or(args);
-            (new_args, remainder)
-        });
+    let mut args = Vector::new();
+    let mut remaining = rest;
+
+    while args.len() < count {
+        if remaining.is_empty() {
+            let message = format!(
+                "{} takes {} arguments but the program ended after {}",
+                name,
+                count,
+                args.len()
+            );
+            return (
+                Some(located_error("missing-macro-arguments", &message, span)),
+                remaining,
+            );
+        }
+
+        let (arg, new_remaining) = build_macros(&remaining, environment);
+        if let Some(arg) = arg {
+            args.push_back(arg)
+        }
+        remaining = new_remaining;
+    }
+
     (
-        Some(RuntimeExpression::MacroCall(name.to_string(), final_args)),
-        new_rest,
+        Some(RuntimeExpression::MacroCall(
+            name.to_string(),
+            args,
+            span.clone(),
+        )),
+        remaining,
     )
 }
 
 fn build_non_macro(expression: LexicalExpression, environment: &Environment) -> RuntimeExpression {
     match expression {
-        LexicalExpression::MacroName(_) => {
-            panic!("Macro should've been handled by build_macros")
+        LexicalExpression::MacroName(_, span) => {
+            panic!("{}: Macro should've been handled by build_macros", span)
         }
-        LexicalExpression::Comment => {
-            panic!("Comment should've been handled by build_macros")
+        LexicalExpression::Comment(span) => {
+            panic!("{}: Comment should've been handled by build_macros", span)
         }
-        LexicalExpression::List(expressions) => {
-            RuntimeExpression::List(build_many_macros(&expressions, &environment))
+        LexicalExpression::List(expressions, _span) => {
+            RuntimeExpression::List(build_many_macros(&expressions, environment))
         }
 
-        LexicalExpression::TaggedTuple(tag, expressions) => RuntimeExpression::TaggedTuple(
+        // TODO: MacroName tagged tuples should evaluate as macros
+        LexicalExpression::TaggedTuple(tag, _, span)
+            if matches!(*tag, LexicalExpression::MacroName(..)) =>
+        {
+            located_error(
+                "invalid-tag",
+                "Macro names can't be used to tag a tuple yet",
+                &span,
+            )
+        }
+        LexicalExpression::TaggedTuple(tag, expressions, span) => RuntimeExpression::TaggedTuple(
             Arc::new(build_non_macro((*tag).clone(), environment)),
-            build_many_macros(&expressions, &environment),
+            build_many_macros(&expressions, environment),
+            span,
         ),
-        LexicalExpression::Keyword(name) => RuntimeExpression::Keyword(name.to_string()),
-        LexicalExpression::Symbol(name) => RuntimeExpression::Symbol(name.to_string()),
-        LexicalExpression::Number(value) => RuntimeExpression::Number(value),
-        LexicalExpression::String(value) => RuntimeExpression::String(value.to_string()),
-        LexicalExpression::Hole => RuntimeExpression::Hole,
+        LexicalExpression::Keyword(name, _span) => RuntimeExpression::Keyword(name),
+        LexicalExpression::Symbol(name, span) => RuntimeExpression::Symbol(name, span),
+        LexicalExpression::Number(value, _span) => RuntimeExpression::Number(value),
+        LexicalExpression::String(value, _span) => RuntimeExpression::String(value),
+        LexicalExpression::Hole(span) => RuntimeExpression::Hole(span),
     }
 }
 
@@ -54,7 +89,7 @@ pub fn build_macros(
     expressions: &Vector<LexicalExpression>,
     environment: &Environment,
 ) -> (Option<RuntimeExpression>, Vector<LexicalExpression>) {
-    if expressions.len() == 0 {
+    if expressions.is_empty() {
         panic!("We can't build macros from an empty expression list")
     }
 
@@ -62,16 +97,30 @@ pub fn build_macros(
 
     match expressions.head() {
         None => panic!("We can't build macros from an empty expression list"),
-        Some(LexicalExpression::Comment) => (None, rest),
-        Some(LexicalExpression::MacroName(name)) => match environment.get(name) {
-            Some(RuntimeExpression::Macro(params, _, _)) => {
-                build_macro_args(params, rest, environment, name)
+        Some(LexicalExpression::Comment(_)) => (None, rest),
+        Some(LexicalExpression::MacroName(name, span)) => match environment.get(name) {
+            Some(RuntimeExpression::Macro(_, params, _, _)) => {
+                build_macro_args(macro_form_count(&params), rest, environment, name, span)
             }
-            Some(RuntimeExpression::BuiltinMacro(params, _)) => {
-                build_macro_args(params, rest, environment, name)
+            Some(RuntimeExpression::BuiltinMacro(_, params, _)) => {
+                build_macro_args(params.len(), rest, environment, name, span)
             }
-            Some(_) => panic!("A macro name should only ever point to a macro in the environment"),
-            None => panic!("Macro {} was referenced but has not defined", name),
+            Some(_) => (
+                Some(located_error(
+                    "not-a-macro",
+                    &format!("{} is defined but is not a macro", name),
+                    span,
+                )),
+                rest,
+            ),
+            None => (
+                Some(located_error(
+                    "not-found",
+                    &format!("Macro {} was referenced but has not been defined", name),
+                    span,
+                )),
+                rest,
+            ),
         },
         Some(expression) => (Some(build_non_macro(expression.clone(), environment)), rest),
     }
@@ -85,8 +134,10 @@ fn build_many_macros(
     let mut outgoing_exprs: Vector<RuntimeExpression> = vector![];
 
     while !remaining_exprs.is_empty() {
-        let (built_expression, new_remaining_exprs) = build_macros(&remaining_exprs, &environment);
-        built_expression.map(|expr| outgoing_exprs.push_back(expr));
+        let (built_expression, new_remaining_exprs) = build_macros(&remaining_exprs, environment);
+        if let Some(expr) = built_expression {
+            outgoing_exprs.push_back(expr)
+        }
         remaining_exprs = new_remaining_exprs;
     }
 
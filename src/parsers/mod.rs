@@ -1,7 +1,10 @@
+pub mod diagnostics;
 pub mod general;
 pub mod macros;
 pub mod nana;
 
+#[cfg(test)]
+pub mod diagnostics_test;
 #[cfg(test)]
 pub mod general_test;
 #[cfg(test)]
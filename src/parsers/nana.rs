@@ -2,70 +2,131 @@ use std::sync::Arc;
 
 use super::general::*;
 use crate::expressions::LexicalExpression;
+use crate::s;
+use crate::span::Span;
 use im::Vector;
 use nom::{
     branch::alt,
     character::complete::{char, digit1, multispace0, none_of},
+    combinator::{cut, opt},
+    error::{context, VerboseError, VerboseErrorKind},
     multi::many0,
-    sequence::{delimited, tuple},
+    sequence::{delimited, preceded, tuple},
     IResult, Parser,
 };
+use nom_locate::{position, LocatedSpan};
 
-pub fn comment(input: &str) -> IResult<&str, LexicalExpression> {
-    delimited(char('#'), many0(none_of("\n")), char('\n'))
-        .map(|_| LexicalExpression::Comment)
+// Parsers work over a located span so every expression can record where it
+// was written. The extra field holds the name of the file being parsed, if
+// there is one.
+pub type Input<'a> = LocatedSpan<&'a str, Option<Arc<str>>>;
+
+// Errors keep the context of every parser they pass through so diagnostics
+// can say what was expected. Once an opening delimiter has been read the rest
+// of the form is cut, so a missing closer is reported where it was expected
+// rather than as an unexpected opener.
+pub type ParseResult<'a, O> = IResult<Input<'a>, O, VerboseError<Input<'a>>>;
+
+pub fn input(code: &str) -> Input<'_> {
+    LocatedSpan::new_extra(code, None)
+}
+
+pub fn input_from_file<'a>(code: &'a str, file: &str) -> Input<'a> {
+    LocatedSpan::new_extra(code, Some(Arc::from(file)))
+}
+
+pub fn span(position: &Input) -> Span {
+    Span {
+        file: position.extra.clone(),
+        offset: position.location_offset(),
+        line: position.location_line(),
+        column: position.get_utf8_column(),
+    }
+}
+
+// A comment on the last line doesn't need a trailing newline
+pub fn comment(input: Input) -> ParseResult<LexicalExpression> {
+    tuple((position, char('#'), many0(none_of("\n")), opt(char('\n'))))
+        .map(|(start, _, _, _)| LexicalExpression::Comment(span(&start)))
         .parse(input)
 }
 
-pub fn macro_name(input: &str) -> IResult<&str, LexicalExpression> {
-    titlecase_word
-        .map(LexicalExpression::MacroName)
+pub fn macro_name(input: Input) -> ParseResult<LexicalExpression> {
+    tuple((position, titlecase_word))
+        .map(|(start, name)| LexicalExpression::MacroName(name, span(&start)))
         .parse(input)
 }
 
-pub fn value_name(input: &str) -> IResult<&str, LexicalExpression> {
-    lower_start_word.map(LexicalExpression::Symbol).parse(input)
+pub fn value_name(input: Input) -> ParseResult<LexicalExpression> {
+    // & on its own marks a rest param, as in [first & rest]
+    let rest_marker = char('&').map(|_| s!("&"));
+    tuple((position, alt((lower_start_word, rest_marker))))
+        .map(|(start, name)| LexicalExpression::Symbol(name, span(&start)))
+        .parse(input)
 }
 
-pub fn tagged_tuple(input: &str) -> IResult<&str, LexicalExpression> {
+pub fn tagged_tuple(input: Input) -> ParseResult<LexicalExpression> {
     // TODO: Allow tagging tagged_tuples
     let taggable = alt((value_name, keyword, list, macro_name, string));
-    tuple((taggable, char('('), many0(expression), char(')')))
-        .map(|(expr, _, args, _)| LexicalExpression::TaggedTuple(Arc::new(expr), args.into()))
-        .parse(input)
+    tuple((
+        position,
+        taggable,
+        char('('),
+        many0(expression),
+        cut(context("')' to close the tagged tuple", char(')'))),
+    ))
+    .map(|(start, expr, _, args, _)| {
+        LexicalExpression::TaggedTuple(Arc::new(expr), args.into(), span(&start))
+    })
+    .parse(input)
 }
 
-pub fn list(input: &str) -> IResult<&str, LexicalExpression> {
-    delimited(char('['), many0(expression), char(']'))
-        .map(|v| LexicalExpression::List(v.into()))
-        .parse(input)
+pub fn list(input: Input) -> ParseResult<LexicalExpression> {
+    tuple((
+        position,
+        preceded(char('['), many0(expression)),
+        cut(context("']' to close the list", char(']'))),
+    ))
+    .map(|(start, v, _)| LexicalExpression::List(v.into(), span(&start)))
+    .parse(input)
 }
 
-pub fn number(input: &str) -> IResult<&str, LexicalExpression> {
-    digit1
-        .map(|s: &str| s.parse().unwrap())
-        .map(LexicalExpression::Number)
-        .parse(input)
+pub fn number(input: Input) -> ParseResult<LexicalExpression> {
+    let (rest, (start, digits)) = tuple((position, digit1)).parse(input)?;
+    match digits.fragment().parse() {
+        Ok(value) => Ok((rest, LexicalExpression::Number(value, span(&start)))),
+        Err(_) => Err(nom::Err::Failure(VerboseError {
+            errors: vec![(
+                digits,
+                VerboseErrorKind::Context("a number small enough to fit in 128 bits"),
+            )],
+        })),
+    }
 }
 
-pub fn string(input: &str) -> IResult<&str, LexicalExpression> {
-    delimited(char('"'), many0(none_of("\"")), char('"'))
-        .map(|chars| chars.iter().collect())
-        .map(LexicalExpression::String)
-        .parse(input)
+pub fn string(input: Input) -> ParseResult<LexicalExpression> {
+    tuple((
+        position,
+        preceded(char('"'), many0(none_of("\""))),
+        cut(context("'\"' to close the string", char('"'))),
+    ))
+    .map(|(start, chars, _)| LexicalExpression::String(chars.iter().collect(), span(&start)))
+    .parse(input)
 }
 
-pub fn keyword(input: &str) -> IResult<&str, LexicalExpression> {
-    tuple((char(':'), lower_start_word))
-        .map(|(_, name)| LexicalExpression::Keyword(name))
+pub fn keyword(input: Input) -> ParseResult<LexicalExpression> {
+    tuple((position, char(':'), lower_start_word))
+        .map(|(start, _, name)| LexicalExpression::Keyword(name, span(&start)))
         .parse(input)
 }
 
-pub fn hole(input: &str) -> IResult<&str, LexicalExpression> {
-    char('_').map(|_| LexicalExpression::Hole).parse(input)
+pub fn hole(input: Input) -> ParseResult<LexicalExpression> {
+    tuple((position, char('_')))
+        .map(|(start, _)| LexicalExpression::Hole(span(&start)))
+        .parse(input)
 }
 
-pub fn expression(input: &str) -> IResult<&str, LexicalExpression> {
+pub fn expression(input: Input) -> ParseResult<LexicalExpression> {
     let expressions = alt((
         comment,
         tagged_tuple,
@@ -80,6 +141,6 @@ pub fn expression(input: &str) -> IResult<&str, LexicalExpression> {
     delimited(multispace0, expressions, multispace0).parse(input)
 }
 
-pub fn program(input: &str) -> IResult<&str, Vector<LexicalExpression>> {
+pub fn program(input: Input) -> ParseResult<Vector<LexicalExpression>> {
     many0(expression).map(|v| v.into()).parse(input)
 }
@@ -1,40 +1,72 @@
 use super::nana::*;
 use crate::expressions::LexicalExpression;
+use crate::helpers::parse;
+use crate::span::Span;
 
 #[test]
 fn test_macro_name() {
     assert_eq!(
-        macro_name("Hello"),
-        Ok(("", LexicalExpression::MacroName("Hello".to_string())))
+        parse(macro_name, "Hello"),
+        Ok((
+            "",
+            LexicalExpression::MacroName("Hello".to_string(), Span::default())
+        ))
     );
     assert_eq!(
-        macro_name("Hello World"),
-        Ok((" World", LexicalExpression::MacroName("Hello".to_string())))
+        parse(macro_name, "Hello World"),
+        Ok((
+            " World",
+            LexicalExpression::MacroName("Hello".to_string(), Span::default())
+        ))
     );
     assert_eq!(
-        macro_name("Greetings::English/Hello-World"),
+        parse(macro_name, "Greetings::English/Hello-World"),
         Ok((
             "",
-            LexicalExpression::MacroName("Greetings::English/Hello-World".to_string())
+            LexicalExpression::MacroName(
+                "Greetings::English/Hello-World".to_string(),
+                Span::default()
+            )
         ))
     );
-    assert!(macro_name("hello").is_err());
-    assert!(macro_name("123Hello").is_err());
+    assert!(parse(macro_name, "hello").is_err());
+    assert!(parse(macro_name, "123Hello").is_err());
 }
 
 #[test]
 fn test_value_name() {
     assert_eq!(
-        value_name("hello"),
-        Ok(("", LexicalExpression::Symbol("hello".to_string())))
+        parse(value_name, "hello"),
+        Ok((
+            "",
+            LexicalExpression::Symbol("hello".to_string(), Span::default())
+        ))
     );
     assert_eq!(
-        value_name("greetings::english/hello-world"),
+        parse(value_name, "greetings::english/hello-world"),
         Ok((
             "",
-            LexicalExpression::Symbol("greetings::english/hello-world".to_string())
+            LexicalExpression::Symbol(
+                "greetings::english/hello-world".to_string(),
+                Span::default()
+            )
         ))
     );
-    assert!(value_name("Hello").is_err());
-    assert!(value_name("123hello").is_err());
+    assert!(parse(value_name, "Hello").is_err());
+    assert!(parse(value_name, "123hello").is_err());
+}
+
+#[test]
+fn test_spans() {
+    let (_, expressions) = program(input_from_file("foo\n  [1 bar(2)]", "spans.nana")).unwrap();
+    let list = expressions.get(1).unwrap();
+    assert_eq!(list.span().to_string(), "spans.nana:2:3");
+    assert_eq!(list.span().offset, 6);
+
+    match list {
+        LexicalExpression::List(items, _) => {
+            assert_eq!(items.get(1).unwrap().span().to_string(), "spans.nana:2:6");
+        }
+        _ => panic!("Expected a list"),
+    }
 }
@@ -1,22 +1,114 @@
 use core::panic;
 use im::{vector, Vector};
 use std::sync::Arc;
-use Process::{Complete, Running, Spawn};
+use std::time::Duration;
 
-pub trait Stepable<I: Clone> {
-    fn step(&self) -> Process<I>;
+use crate::scheduler::{run_tasks, Priority, Reductions, RoundRobin, Scheduler, Task};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::work_stealing::run_tasks_on_threads;
+use Process::{Complete, Running, Spawn, Waiting};
+
+pub trait Stepable<I: Clone, S: Clone = I>: Send + Sync {
+    fn step(&self) -> Process<I, S>;
+
+    // How urgently the process wants its turns. Wrappers pass on the priority
+    // of whatever they wrap.
+    fn priority(&self) -> Priority {
+        Priority::Normal
+    }
+}
+
+// Why a process is waiting, which the scheduler checks to know when it can
+// carry on
+pub trait Wake: Send + Sync {
+    fn ready(&self) -> bool;
+    // When the process will be ready even if nothing else happens
+    fn deadline(&self) -> Option<Duration>;
+    // Called when nothing could ever make the process ready, because every
+    // process is waiting. The process should give up when it next runs.
+    fn abandon(&self);
+}
+
+// A limit on how much work a run can do. Every step a process takes uses up a
+// unit of fuel, and the run stops once there's none left or the time limit has
+// passed. The default is unlimited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Fuel {
+    pub max_steps: Option<u64>,
+    pub time_limit: Option<Duration>,
+}
+
+impl Fuel {
+    pub fn unlimited() -> Fuel {
+        Fuel::default()
+    }
+
+    pub fn steps(max_steps: u64) -> Fuel {
+        Fuel {
+            max_steps: Some(max_steps),
+            time_limit: None,
+        }
+    }
+
+    pub fn with_time_limit(self, time_limit: Duration) -> Fuel {
+        Fuel {
+            time_limit: Some(time_limit),
+            ..self
+        }
+    }
+}
+
+// What a run had finished when its fuel ran out
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutOfFuel<T> {
+    pub completed: T,
+    pub steps: u64,
+    // Whether it was the time limit that ran out, rather than the steps
+    pub timed_out: bool,
 }
 
+// A process's result, if it had completed, and the results of the processes it
+// spawned that had
+pub type Partial<I, S> = (Option<I>, Vector<S>);
+
+pub type RunResult<I, S> = Result<(I, Vector<S>), OutOfFuel<Partial<I, S>>>;
+
+impl<T> OutOfFuel<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> OutOfFuel<U> {
+        OutOfFuel {
+            completed: f(self.completed),
+            steps: self.steps,
+            timed_out: self.timed_out,
+        }
+    }
+}
+
+// What processes produce. Processes can be stepped on any thread, so their
+// results have to be able to move between threads too.
+pub trait Value: Clone + Send + Sync + 'static {}
+
+impl<T: Clone + Send + Sync + 'static> Value for T {}
+
+// I is what the process produces and S is what the processes it spawns
+// produce. They only differ part way through a computation, like when
+// run_in_sequence is collecting the results of processes that can spawn.
 #[derive(Clone)]
-pub enum Process<I: Clone> {
-    Spawn(Arc<Process<I>>, Vector<Process<I>>),
-    Running(Arc<dyn Stepable<I>>),
+pub enum Process<I: Clone, S: Clone = I> {
+    Spawn(Arc<Process<I, S>>, Vector<Process<S>>),
+    Running(Arc<dyn Stepable<I, S>>),
+    // Not stepped again until it's ready, so it costs nothing while it waits
+    Waiting(Arc<dyn Wake>, Arc<Process<I, S>>),
     Complete(I),
 }
 
 // Functions that return Processes count as Stepable by just calling themselves
-impl<I: Clone + 'static, F: Fn() -> Process<I> + 'static> Stepable<I> for F {
-    fn step(&self) -> Process<I> {
+impl<I, S, F> Stepable<I, S> for F
+where
+    I: Value,
+    S: Value,
+    F: Fn() -> Process<I, S> + Send + Sync + 'static,
+{
+    fn step(&self) -> Process<I, S> {
         self()
     }
 }
@@ -26,19 +118,39 @@ impl<I: Clone + 'static, F: Fn() -> Process<I> + 'static> Stepable<I> for F {
 // ends. The result will be passed to the function passed as a second argument
 // to AndThen. That function should return a new process that will be returned
 // directly by AndThen, ending the cycle of wrapping.
-struct AndThen<A: Clone, B: Clone>(Process<A>, Arc<dyn Fn(A) -> Process<B>>);
+struct AndThen<A: Clone, B: Clone, S: Clone>(Process<A, S>, Arc<AndThenFn<A, B, S>>);
+
+type AndThenFn<A, B, S> = dyn Fn(A) -> Process<B, S> + Send + Sync;
 
-impl<A: Clone + 'static, B: Clone + 'static> Stepable<B> for AndThen<A, B> {
-    fn step(&self) -> Process<B> {
+impl<A: Value, B: Value, S: Value> Stepable<B, S> for AndThen<A, B, S> {
+    fn step(&self) -> Process<B, S> {
         let AndThen(process, and_then) = self;
 
         match process {
             Complete(result) => (and_then)(result.clone()),
             Running(stepable) => Running(Arc::new(AndThen(stepable.step(), and_then.clone()))),
-            // TODO: AndThen spawn
-            Spawn(..) => todo!(),
+            // The continuation still owes us a result, so it stays wrapped.
+            // The spawned processes are passed outwards untouched.
+            Spawn(continuation, spawned_processes) => Spawn(
+                Arc::new(Running(Arc::new(AndThen(
+                    (**continuation).clone(),
+                    and_then.clone(),
+                )))),
+                spawned_processes.clone(),
+            ),
+            Waiting(wake, continuation) => Waiting(
+                wake.clone(),
+                Arc::new(Running(Arc::new(AndThen(
+                    (**continuation).clone(),
+                    and_then.clone(),
+                )))),
+            ),
         }
     }
+
+    fn priority(&self) -> Priority {
+        self.0.priority()
+    }
 }
 
 // The process data structure itself mostly just wraps a "running" thunk, or a
@@ -46,41 +158,97 @@ impl<A: Clone + 'static, B: Clone + 'static> Stepable<B> for AndThen<A, B> {
 // not. Most of the time Running will be holding a lambda, or an AndThen
 // wrapping a lambda. step on the process just proxies down to the contained
 // stepable (or panics).
-impl<I: Clone + 'static> Process<I> {
+impl<I: Value> Process<I> {
     pub fn run_until_complete(self) -> Vector<I> {
-        Process::round_robin(vector![self])
+        match self.run_until_complete_with_fuel(Fuel::unlimited()) {
+            Ok(complete_processes) => complete_processes,
+            Err(_) => unreachable!("Unlimited fuel can't run out"),
+        }
     }
 
-    pub fn run_once_until_complete(self) -> I {
-        Process::round_robin(vector![self]).head().unwrap().clone()
+    // Like run_until_complete, but stops once the fuel runs out
+    pub fn run_until_complete_with_fuel(
+        self,
+        fuel: Fuel,
+    ) -> Result<Vector<I>, OutOfFuel<Vector<I>>> {
+        run_all(vector![self], &mut Reductions::default(), fuel)
     }
 
+    // Gives each process a single step in turn, whatever its priority, until
+    // they've all completed
     pub fn round_robin(processes: Vector<Process<I>>) -> Vector<I> {
-        let mut active_processes = processes;
-        let mut complete_processes: Vector<I> = vector![];
+        match run_all(processes, &mut RoundRobin::default(), Fuel::unlimited()) {
+            Ok(complete_processes) => complete_processes,
+            Err(_) => unreachable!("Unlimited fuel can't run out"),
+        }
+    }
+}
 
-        while !active_processes.is_empty() {
-            match active_processes.pop_front().unwrap() {
-                Complete(result) => complete_processes.push_back(result),
-                Running(stepable) => active_processes.push_back(stepable.step()),
-                Spawn(continuation, spawned_processes) => {
-                    active_processes.append(spawned_processes);
-                    active_processes.push_back((*continuation).clone());
-                }
-            }
+fn run_all<I: Value>(
+    processes: Vector<Process<I>>,
+    scheduler: &mut dyn Scheduler,
+    fuel: Fuel,
+) -> Result<Vector<I>, OutOfFuel<Vector<I>>> {
+    let tasks = processes.into_iter().map(Task::Spawned).collect();
+    run_tasks::<I, I>(tasks, scheduler, fuel)
+        .map(|(_main_result, complete_processes)| complete_processes)
+        .map_err(|out_of_fuel| out_of_fuel.map(|(_main_result, complete)| complete))
+}
+
+impl<I: Value, S: Value> Process<I, S> {
+    // Like run_until_complete, but keeps this process's result apart from the
+    // results of the processes it spawned, which come back in order of
+    // completion.
+    pub fn run_until_complete_with_spawned(self) -> (I, Vector<S>) {
+        match self.run_with_fuel(Fuel::unlimited()) {
+            Ok(results) => results,
+            Err(_) => unreachable!("Unlimited fuel can't run out"),
+        }
+    }
+
+    // Like run_until_complete_with_spawned, but stops once the fuel runs out.
+    // This process's result is None if it hadn't completed by then.
+    pub fn run_with_fuel(self, fuel: Fuel) -> RunResult<I, S> {
+        self.run_with(&mut Reductions::default(), fuel)
+    }
+
+    // Like run_with_fuel, but the scheduler picks whose turn it is
+    pub fn run_with(self, scheduler: &mut dyn Scheduler, fuel: Fuel) -> RunResult<I, S> {
+        let (main_result, spawned_results) = run_tasks(vector![Task::Main(self)], scheduler, fuel)?;
+        Ok((main_result.unwrap(), spawned_results))
+    }
+
+    // Like run_with_fuel, but the processes are stepped by a pool of threads
+    // that take work from each other when they run out. Processes no longer
+    // complete in a reproducible order.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn run_on_threads(self, threads: usize, fuel: Fuel) -> RunResult<I, S> {
+        let (main_result, spawned_results) =
+            run_tasks_on_threads(vector![Task::Main(self)], threads, fuel)?;
+        Ok((main_result.unwrap(), spawned_results))
+    }
+
+    // The priority of whatever the process will do next
+    pub fn priority(&self) -> Priority {
+        match self {
+            Running(stepable) => stepable.priority(),
+            Spawn(continuation, _) | Waiting(_, continuation) => continuation.priority(),
+            Complete(_) => Priority::Normal,
         }
+    }
 
-        complete_processes
+    pub fn run_once_until_complete(self) -> I {
+        self.run_until_complete_with_spawned().0
     }
 
-    pub fn run_in_sequence(processes: Vector<Process<I>>) -> Process<Vector<I>> {
+    pub fn run_in_sequence(processes: Vector<Process<I, S>>) -> Process<Vector<I>, S> {
         Process::run_in_sequence_with_results(processes, vector![])
     }
 
     fn run_in_sequence_with_results(
-        processes: Vector<Process<I>>,
+        processes: Vector<Process<I, S>>,
         results: Vector<I>,
-    ) -> Process<Vector<I>> {
+    ) -> Process<Vector<I>, S> {
         if processes.is_empty() {
             Complete(results)
         } else {
@@ -92,8 +260,34 @@ impl<I: Clone + 'static> Process<I> {
             match active_process {
                 Complete(result) => results.push_back(result),
                 Running(stepable) => processes.push_front(stepable.step()),
-                // TODO: run_in_sequence_with_results spawn
-                Spawn(..) => todo!(),
+                // Like run_in_sequence_tco, the continuation goes back where
+                // the spawn came from and the spawned processes go outwards
+                Spawn(continuation, spawned_processes) => {
+                    processes.push_front((*continuation).clone());
+
+                    return Spawn(
+                        Arc::new(Running(Arc::new(move || {
+                            Process::run_in_sequence_with_results(
+                                processes.clone(),
+                                results.clone(),
+                            )
+                        }))),
+                        spawned_processes,
+                    );
+                }
+                Waiting(wake, continuation) => {
+                    processes.push_front((*continuation).clone());
+
+                    return Waiting(
+                        wake,
+                        Arc::new(Running(Arc::new(move || {
+                            Process::run_in_sequence_with_results(
+                                processes.clone(),
+                                results.clone(),
+                            )
+                        }))),
+                    );
+                }
             }
 
             Running(Arc::new(move || {
@@ -102,7 +296,7 @@ impl<I: Clone + 'static> Process<I> {
         }
     }
 
-    pub fn run_in_sequence_tco(processes: Vector<Process<I>>) -> Process<I> {
+    pub fn run_in_sequence_tco(processes: Vector<Process<I, S>>) -> Process<I, S> {
         if processes.is_empty() {
             panic!("We must run at least one process");
         } else if processes.len() == 1 {
@@ -140,14 +334,21 @@ impl<I: Clone + 'static> Process<I> {
                         spawned_processes,
                     )
                 }
+                Waiting(wake, continuation) => {
+                    processes.push_front((*continuation).clone());
+
+                    Waiting(
+                        wake,
+                        Arc::new(Running(Arc::new(move || {
+                            Process::run_in_sequence_tco(processes.clone())
+                        }))),
+                    )
+                }
             }
         }
     }
 
-    pub fn and_then<B: Clone + 'static>(
-        self,
-        and_then: Arc<dyn Fn(I) -> Process<B>>,
-    ) -> Process<B> {
+    pub fn and_then<B: Value>(self, and_then: Arc<AndThenFn<I, B, S>>) -> Process<B, S> {
         Running(Arc::new(AndThen(self, and_then)))
     }
 }
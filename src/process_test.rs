@@ -1,9 +1,12 @@
 use im::{vector, Vector};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::expressions::RuntimeExpression::{self, List, Number};
-use crate::process::Process::{Complete, Running, Spawn};
+use crate::process::Process::{Complete, Running, Spawn, Waiting};
 use crate::process::*;
+use crate::scheduler;
 
 fn make_process(a: u128, b: u128, c: u128) -> Process<RuntimeExpression> {
     Running(Arc::new(move || {
@@ -20,18 +23,21 @@ fn step_process(process: Process<RuntimeExpression>) -> Process<RuntimeExpressio
         Running(stepable) => stepable.step(),
         Complete(_) => panic!("Tried to step a complete process"),
         Spawn(..) => panic!("Tried to step a spawn process"),
+        Waiting(..) => panic!("Tried to step a waiting process"),
     }
 }
 
 #[test]
+#[allow(clippy::assertions_on_constants)]
 fn test_process_by_steps() {
     let actual = step_process(step_process(step_process(make_process(1, 2, 3))));
 
     let expected = List(vector![Number(1), Number(2), Number(3)]);
     match actual {
         Complete(result) => assert_eq!(expected, result),
-        Running(_) => assert!(false), // Actual should be complete
-        Spawn(..) => assert!(false),  // Actual should be complete
+        Running(_) => assert!(false),  // Actual should be complete
+        Spawn(..) => assert!(false),   // Actual should be complete
+        Waiting(..) => assert!(false), // Actual should be complete
     }
 }
 
@@ -116,6 +122,160 @@ fn test_running_sequence() {
 
 #[test]
 fn test_and_then() {
-    let process = Running(Arc::new(|| Complete(1))).and_then(Arc::new(|n| Complete((n, 2))));
+    let process: Process<i32> = Running(Arc::new(|| Complete(1)));
+    let process = process.and_then(Arc::new(|n| Complete((n, 2))));
     assert_eq!((1, 2), process.run_once_until_complete());
 }
+
+// A process that spawns one process producing spawned_value, then completes
+// with value
+fn spawning(value: i32, spawned_value: i32) -> Process<i32> {
+    Spawn(
+        Arc::new(Running(Arc::new(move || Complete(value)))),
+        vector![Running(Arc::new(move || Complete(spawned_value)))],
+    )
+}
+
+#[test]
+fn test_and_then_passes_spawns_through() {
+    let process = spawning(1, 10).and_then(Arc::new(|n| Complete((n, 2))));
+    assert_eq!(
+        ((1, 2), vector![10]),
+        process.run_until_complete_with_spawned()
+    );
+}
+
+#[test]
+fn test_run_in_sequence_passes_spawns_through() {
+    let process = Process::run_in_sequence(vector![Complete(1), spawning(2, 20), spawning(3, 30)]);
+    assert_eq!(
+        (vector![1, 2, 3], vector![20, 30]),
+        process.run_until_complete_with_spawned()
+    );
+}
+
+#[test]
+fn test_run_in_sequence_tco_passes_spawns_through() {
+    let process = Process::run_in_sequence_tco(vector![spawning(1, 10), spawning(2, 20)]);
+    assert_eq!(
+        (2, vector![10, 20]),
+        process.run_until_complete_with_spawned()
+    );
+}
+
+#[test]
+fn test_round_robin_runs_spawned_processes() {
+    let actual = Process::round_robin(vector![spawning(1, 10), Complete(2)]);
+    assert_eq!(vector![1, 2, 10], actual);
+}
+
+#[test]
+fn test_spawned_processes_can_spawn() {
+    let process = Spawn(Arc::new(Complete(1)), vector![spawning(10, 100)]);
+    assert_eq!(
+        (1, vector![10, 100]),
+        process.run_until_complete_with_spawned()
+    );
+}
+
+// Ready once the flag is set, which the test or an abandon can do
+struct Flag(AtomicBool);
+
+impl Flag {
+    fn new(set: bool) -> Flag {
+        Flag(AtomicBool::new(set))
+    }
+
+    fn set(&self) {
+        self.0.store(true, Ordering::Relaxed)
+    }
+
+    fn is_set(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl Wake for Flag {
+    fn ready(&self) -> bool {
+        self.is_set()
+    }
+
+    fn deadline(&self) -> Option<Duration> {
+        None
+    }
+
+    fn abandon(&self) {
+        self.set()
+    }
+}
+
+#[test]
+fn test_waiting_processes_are_not_stepped_until_ready() {
+    let flag = Arc::new(Flag::new(false));
+    let steps = Arc::new(AtomicUsize::new(0));
+
+    let counted = steps.clone();
+    let waiter: Process<i32> = Waiting(
+        flag.clone(),
+        Arc::new(Running(Arc::new(move || {
+            counted.fetch_add(1, Ordering::Relaxed);
+            Complete(1)
+        }))),
+    );
+    let setter = flag.clone();
+    let waker: Process<i32> = Running(Arc::new(move || {
+        setter.set();
+        scheduler::notify();
+        Complete(2)
+    }));
+
+    assert_eq!(vector![2, 1], Process::round_robin(vector![waiter, waker]));
+    assert_eq!(1, steps.load(Ordering::Relaxed));
+}
+
+#[test]
+fn test_waiting_processes_are_abandoned_when_nothing_else_can_run() {
+    let flag = Arc::new(Flag::new(false));
+    let abandoned = flag.clone();
+    let process: Process<bool> = Waiting(
+        flag,
+        Arc::new(Running(Arc::new(move || Complete(abandoned.is_set())))),
+    );
+
+    assert!(process.run_once_until_complete());
+}
+
+#[test]
+fn test_combinators_pass_waits_through() {
+    let waiting = || -> Process<i32> { Waiting(Arc::new(Flag::new(true)), Arc::new(Complete(1))) };
+
+    let and_then = waiting().and_then(Arc::new(|n| Complete(n + 1)));
+    assert_eq!(2, and_then.run_once_until_complete());
+
+    let sequence = Process::run_in_sequence(vector![waiting(), Complete(2), waiting()]);
+    assert_eq!(vector![1, 2, 1], sequence.run_once_until_complete());
+
+    let tco = Process::run_in_sequence_tco(vector![waiting(), waiting()]);
+    assert_eq!(1, tco.run_once_until_complete());
+}
+
+#[test]
+fn test_running_out_of_fuel_keeps_what_completed() {
+    fn forever() -> Process<i32> {
+        Running(Arc::new(forever))
+    }
+    let process = Spawn(Arc::new(Complete(1)), vector![forever()]);
+
+    let out_of_fuel = process
+        .run_until_complete_with_fuel(Fuel::steps(10))
+        .unwrap_err();
+    assert_eq!(out_of_fuel.completed, vector![1]);
+    assert_eq!(out_of_fuel.steps, 10);
+    assert!(!out_of_fuel.timed_out);
+
+    let complete = make_process(1, 2, 3).run_until_complete_with_fuel(Fuel::steps(10));
+    assert_eq!(
+        complete,
+        Ok(vector![List(vector![Number(1), Number(2), Number(3)])])
+    );
+}
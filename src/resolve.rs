@@ -0,0 +1,152 @@
+use std::sync::Arc;
+
+use crate::environment::Slot;
+use crate::expressions::Environment;
+use crate::expressions::RuntimeExpression::{self, List, Local, MacroCall, Symbol, TaggedTuple};
+use crate::standard_library::core::{
+    is_builtin_macro, is_timeout_case, parse_params, pattern_names,
+};
+
+// Turns each symbol that names a local into a slot, so evaluating it doesn't
+// have to search for it by name. Scopes are the names bound by each frame that
+// will be in place when the code runs, outermost first.
+//
+// Fn, Macro, Match and Receive bodies are resolved with the frame they'll run
+// in added, as long as env still binds those names to the builtin macros. The
+// arguments of other macros are left alone. They're only code
+// until the macro expands them, and the expansion is resolved before it's
+// evaluated. Anything left as a symbol is still found by name.
+pub fn resolve(
+    expression: RuntimeExpression,
+    scopes: &[Arc<[String]>],
+    env: &Environment,
+) -> RuntimeExpression {
+    match expression {
+        Symbol(name, span) => match find(&name, scopes) {
+            Some(slot) => Local(name, slot, span),
+            None => Symbol(name, span),
+        },
+        TaggedTuple(tag, values, span) => TaggedTuple(
+            Arc::new(resolve((*tag).clone(), scopes, env)),
+            resolve_all(values, scopes, env),
+            span,
+        ),
+        List(values) => List(resolve_all(values, scopes, env)),
+        MacroCall(name, args, span) => {
+            let builtin = env
+                .get(&name)
+                .is_some_and(|a_macro| is_builtin_macro(&name, &a_macro));
+            let args = match (name.as_str(), args.len()) {
+                _ if !builtin => args,
+                ("Fn" | "Macro", 2) => resolve_function(args, scopes, env),
+                ("Match", 2) => resolve_match(args, scopes, env),
+                ("Receive", 1) => {
+                    let mut args = args;
+                    let cases = args.pop_back().unwrap();
+                    im::vector![resolve_cases(cases, scopes, env)]
+                }
+                ("Def", 2) => {
+                    let mut args = args;
+                    let value = args.pop_back().unwrap();
+                    args.push_back(resolve(value, scopes, env));
+                    args
+                }
+                _ => args,
+            };
+            MacroCall(name, args, span)
+        }
+        expression => expression,
+    }
+}
+
+fn resolve_all(
+    expressions: im::Vector<RuntimeExpression>,
+    scopes: &[Arc<[String]>],
+    env: &Environment,
+) -> im::Vector<RuntimeExpression> {
+    expressions
+        .into_iter()
+        .map(|expression| resolve(expression, scopes, env))
+        .collect()
+}
+
+fn find(name: &str, scopes: &[Arc<[String]>]) -> Option<Slot> {
+    scopes.iter().rev().enumerate().find_map(|(depth, names)| {
+        names
+            .iter()
+            .position(|bound| bound == name)
+            .map(|index| Slot { depth, index })
+    })
+}
+
+fn within(scopes: &[Arc<[String]>], names: Arc<[String]>) -> Vec<Arc<[String]>> {
+    let mut scopes = scopes.to_vec();
+    scopes.push(names);
+    scopes
+}
+
+// Defaults are evaluated where the function is made, so only the body is
+// resolved, within the frame each call makes
+fn resolve_function(
+    mut args: im::Vector<RuntimeExpression>,
+    scopes: &[Arc<[String]>],
+    env: &Environment,
+) -> im::Vector<RuntimeExpression> {
+    let body = args.pop_back().unwrap();
+    let body = match args.front() {
+        Some(List(params)) => match parse_params(params) {
+            Some(params) => resolve(body, &within(scopes, params.names()), env),
+            None => body,
+        },
+        _ => body,
+    };
+    args.push_back(body);
+    args
+}
+
+fn resolve_match(
+    mut args: im::Vector<RuntimeExpression>,
+    scopes: &[Arc<[String]>],
+    env: &Environment,
+) -> im::Vector<RuntimeExpression> {
+    let cases = args.pop_back().unwrap();
+    let value = args.pop_back().unwrap();
+    im::vector![
+        resolve(value, scopes, env),
+        resolve_cases(cases, scopes, env),
+    ]
+}
+
+fn resolve_cases(
+    cases: RuntimeExpression,
+    scopes: &[Arc<[String]>],
+    env: &Environment,
+) -> RuntimeExpression {
+    match cases {
+        List(cases) => List(
+            cases
+                .iter()
+                .enumerate()
+                .map(|(index, case)| match index % 2 {
+                    // The time a Receive waits is evaluated before it waits
+                    0 if is_timeout_case(case) => match case {
+                        TaggedTuple(tag, values, span) => TaggedTuple(
+                            tag.clone(),
+                            resolve_all(values.clone(), scopes, env),
+                            span.clone(),
+                        ),
+                        _ => unreachable!(),
+                    },
+                    // Patterns are matched rather than evaluated
+                    0 => case.clone(),
+                    _ => {
+                        let pattern = &cases[index - 1];
+                        let names = pattern_names(pattern).into();
+                        resolve(case.clone(), &within(scopes, names), env)
+                    }
+                })
+                .collect(),
+        ),
+        cases => cases,
+    }
+}
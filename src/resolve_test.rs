@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use im::vector;
+
+use crate::environment::Slot;
+use crate::expressions::RuntimeExpression::{Local, MacroCall, Symbol};
+use crate::expressions::{Environment, RuntimeExpression};
+use crate::parsers::macros::build_macros;
+use crate::parsers::nana::{input, program};
+use crate::resolve::resolve;
+use crate::s;
+use crate::span::Span;
+use crate::standard_library::core::standard_library;
+
+fn resolved(code: &str, scopes: &[Arc<[String]>]) -> RuntimeExpression {
+    resolved_in(code, scopes, &standard_library())
+}
+
+fn resolved_in(code: &str, scopes: &[Arc<[String]>], env: &Environment) -> RuntimeExpression {
+    let (_, expressions) = program(input(code)).unwrap();
+    let (expression, _) = build_macros(&expressions, env);
+    resolve(expression.unwrap(), scopes, env)
+}
+
+fn local(name: &str, depth: usize, index: usize) -> RuntimeExpression {
+    Local(s!(name), Slot { depth, index }, Span::default())
+}
+
+#[test]
+fn test_symbols_resolve_to_the_innermost_frame_binding_them() {
+    let scopes: Vec<Arc<[String]>> =
+        vec![Arc::from(vec![s!("a"), s!("b")]), Arc::from(vec![s!("b")])];
+
+    assert_eq!(resolved("a", &scopes), local("a", 1, 0));
+    assert_eq!(resolved("b", &scopes), local("b", 0, 0));
+    assert_eq!(resolved("c", &scopes), Symbol(s!("c"), Span::default()));
+}
+
+#[test]
+fn test_fn_and_match_bodies_resolve_within_their_own_frame() {
+    let expression = resolved("Fn [x & rest] Match x [[y] [x y rest outer] _ x]", &[]);
+
+    let MacroCall(_, fn_args, _) = expression else {
+        panic!("Expected a call to Fn");
+    };
+    let MacroCall(_, match_args, _) = fn_args[1].clone() else {
+        panic!("Expected a call to Match");
+    };
+
+    assert_eq!(match_args[0], local("x", 0, 0));
+    let RuntimeExpression::List(cases) = match_args[1].clone() else {
+        panic!("Expected a list of cases");
+    };
+    assert_eq!(
+        cases[1],
+        RuntimeExpression::List(vector![
+            local("x", 1, 0),
+            local("y", 0, 0),
+            local("rest", 1, 1),
+            Symbol(s!("outer"), Span::default()),
+        ])
+    );
+    // A case binding nothing still runs in a frame of its own
+    assert_eq!(cases[3], local("x", 1, 0));
+}
+
+#[test]
+fn test_quoted_code_and_macro_arguments_are_left_alone() {
+    let scopes: Vec<Arc<[String]>> = vec![Arc::from(vec![s!("x")])];
+
+    let quoted = resolved("Quote x", &scopes);
+    assert_eq!(
+        quoted,
+        MacroCall(
+            s!("Quote"),
+            vector![Symbol(s!("x"), Span::default())],
+            Span::default()
+        )
+    );
+
+    let if_call = resolved("If x x x", &scopes);
+    let MacroCall(_, args, _) = if_call else {
+        panic!("Expected a call to If");
+    };
+    assert!(args.iter().all(|arg| matches!(arg, Symbol(..))));
+}
+
+#[test]
+fn test_receive_bodies_resolve_within_their_own_frame() {
+    let expression = resolved("Fn [x] Receive [[:add y] [x y] after(x) x]", &[]);
+
+    let MacroCall(_, fn_args, _) = expression else {
+        panic!("Expected a call to Fn");
+    };
+    let MacroCall(_, receive_args, _) = fn_args[1].clone() else {
+        panic!("Expected a call to Receive");
+    };
+    let RuntimeExpression::List(cases) = receive_args[0].clone() else {
+        panic!("Expected a list of cases");
+    };
+
+    assert_eq!(
+        cases[1],
+        RuntimeExpression::List(vector![local("x", 1, 0), local("y", 0, 0)])
+    );
+    // The timeout is evaluated before waiting, outside the case's frame
+    let RuntimeExpression::TaggedTuple(_, timeout, _) = cases[2].clone() else {
+        panic!("Expected an after(ms) case");
+    };
+    assert_eq!(timeout, vector![local("x", 0, 0)]);
+    assert_eq!(cases[3], local("x", 1, 0));
+}
+
+#[test]
+fn test_macros_defined_in_place_of_builtins_are_left_alone() {
+    let scopes: Vec<Arc<[String]>> = vec![Arc::from(vec![s!("x")])];
+    let env = standard_library();
+    let env = env.clone().add(s!("Match"), env.get("AssertEq").unwrap());
+
+    let MacroCall(_, args, _) = resolved_in("Match x [y y]", &scopes, &env) else {
+        panic!("Expected a call to Match");
+    };
+    assert_eq!(args[0], Symbol(s!("x"), Span::default()));
+    assert_eq!(
+        args[1],
+        RuntimeExpression::List(vector![
+            Symbol(s!("y"), Span::default()),
+            Symbol(s!("y"), Span::default()),
+        ])
+    );
+}
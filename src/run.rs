@@ -0,0 +1,31 @@
+use crate::errors::is_error;
+use crate::expressions::print;
+use crate::interpreter::Interpreter;
+use crate::parsers::diagnostics::parse_program;
+use crate::parsers::nana::input_from_file;
+
+// Runs a whole program the way a command does, with its arguments bound to
+// argv. Syntax errors and any errors the program returns are written to
+// stderr. Returns false if the program couldn't be parsed or one of its top
+// level forms returned an error. Errors in spawned processes are only
+// reported.
+pub fn run_program(file: &str, code: &str, args: &[String]) -> bool {
+    let mut interpreter = Interpreter::new();
+    interpreter.define("argv", args.to_vec());
+    let (results, spawned) = match interpreter.run(parse_program(input_from_file(code, file))) {
+        Ok(results) => results,
+        Err(diagnostics) => {
+            diagnostics.iter().for_each(|d| eprintln!("{}", d));
+            return false;
+        }
+    };
+
+    spawned
+        .into_iter()
+        .flatten()
+        .chain(results.iter().cloned())
+        .filter(is_error)
+        .for_each(|error| eprintln!("{}", print(error)));
+
+    !results.iter().any(is_error)
+}
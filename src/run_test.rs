@@ -0,0 +1,21 @@
+use crate::run::run_program;
+use crate::s;
+
+#[test]
+fn test_programs_fail_when_a_top_level_form_errors() {
+    assert!(run_program("ok.nana", "add(1 2)", &[]));
+    assert!(!run_program("bad.nana", "add(1 2)\nmissing\n:after", &[]));
+    assert!(!run_program("syntax.nana", "[1 2", &[]));
+}
+
+#[test]
+fn test_errors_in_spawned_processes_do_not_fail_the_program() {
+    assert!(run_program("spawn.nana", "spawn(Fn [] missing)\n:ok", &[]));
+}
+
+#[test]
+fn test_arguments_are_bound_to_argv() {
+    let args = [s!("one")];
+    assert!(run_program("argv.nana", "Match argv [[\"one\"] :ok]", &args));
+    assert!(!run_program("argv.nana", "Match argv [[\"one\"] :ok]", &[]));
+}
@@ -0,0 +1,359 @@
+use std::cell::Cell;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use im::{vector, Vector};
+
+use crate::clock;
+use crate::process::Process::{self, Complete, Running, Spawn, Waiting};
+use crate::process::{Fuel, OutOfFuel, Partial, Value, Wake};
+
+static EVENTS: AtomicUsize = AtomicUsize::new(0);
+
+// Called when something happens that could make a waiting process ready, like
+// a message being sent or a process completing. Until then the scheduler
+// doesn't look at waiting processes again.
+pub fn notify() {
+    EVENTS.fetch_add(1, Ordering::Relaxed);
+}
+
+// How many events there have been, so a run loop can tell whether there's
+// been another
+pub(crate) fn events() -> usize {
+    EVENTS.load(Ordering::Relaxed)
+}
+
+thread_local! {
+    static REDUCTIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+// Every step counts as one reduction. Steps that do more work than that, like
+// the VM running a batch of instructions, add the rest here so their turns end
+// sooner.
+pub(crate) fn reduce(reductions: u64) {
+    REDUCTIONS.with(|counted| counted.set(counted.get() + reductions))
+}
+
+fn take_reductions() -> u64 {
+    REDUCTIONS.with(|counted| counted.replace(0))
+}
+
+pub type TaskId = usize;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+// Decides which runnable task takes the next turn, and how many reductions it
+// can use before the turn ends. Everything else, like parking tasks that are
+// waiting and waking them up again, is up to the run loop.
+pub trait Scheduler {
+    // The task is ready for a turn, because it's new, it's been woken, or its
+    // last turn ran out
+    fn push(&mut self, task: TaskId, priority: Priority);
+    fn pop(&mut self) -> Option<(TaskId, u64)>;
+}
+
+pub const REDUCTIONS_PER_TURN: u64 = 2000;
+
+// So low priority tasks still get a turn while there are normal ones to run
+const NORMAL_TURNS_PER_LOW_TURN: usize = 8;
+
+// The default scheduler, modelled on BEAM's. Each turn lasts for
+// REDUCTIONS_PER_TURN reductions, so a process doing expensive steps gets
+// fewer of them. High priority tasks always go first, and low priority tasks
+// get one turn for every few normal ones.
+#[derive(Default)]
+pub struct Reductions {
+    high: VecDeque<TaskId>,
+    normal: VecDeque<TaskId>,
+    low: VecDeque<TaskId>,
+    normal_turns: usize,
+}
+
+impl Scheduler for Reductions {
+    fn push(&mut self, task: TaskId, priority: Priority) {
+        match priority {
+            Priority::High => self.high.push_back(task),
+            Priority::Normal => self.normal.push_back(task),
+            Priority::Low => self.low.push_back(task),
+        }
+    }
+
+    fn pop(&mut self) -> Option<(TaskId, u64)> {
+        let low_turn = self.normal_turns >= NORMAL_TURNS_PER_LOW_TURN || self.normal.is_empty();
+        let task = match self.high.pop_front() {
+            Some(task) => task,
+            None if low_turn && !self.low.is_empty() => {
+                self.normal_turns = 0;
+                self.low.pop_front()?
+            }
+            None => {
+                self.normal_turns += 1;
+                self.normal.pop_front()?
+            }
+        };
+        Some((task, REDUCTIONS_PER_TURN))
+    }
+}
+
+// Every task takes a single step per turn, whatever its priority
+#[derive(Default)]
+pub struct RoundRobin(VecDeque<TaskId>);
+
+impl Scheduler for RoundRobin {
+    fn push(&mut self, task: TaskId, _priority: Priority) {
+        self.0.push_back(task)
+    }
+
+    fn pop(&mut self) -> Option<(TaskId, u64)> {
+        self.0.pop_front().map(|task| (task, 1))
+    }
+}
+
+// A process waiting for its turn
+#[derive(Clone)]
+pub(crate) enum Task<I: Clone, S: Clone> {
+    Main(Process<I, S>),
+    Spawned(Process<S>),
+}
+
+impl<I: Value, S: Value> Task<I, S> {
+    pub(crate) fn priority(&self) -> Priority {
+        match self {
+            Task::Main(process) => process.priority(),
+            Task::Spawned(process) => process.priority(),
+        }
+    }
+
+    // Steps a running task, and returns what it does next along with how many
+    // reductions the step used
+    pub(crate) fn step(self) -> (Task<I, S>, u64) {
+        take_reductions();
+        let next = match self {
+            Task::Main(Running(stepable)) => Task::Main(stepable.step()),
+            Task::Spawned(Running(stepable)) => Task::Spawned(stepable.step()),
+            task => return (task, 0),
+        };
+        (next, 1 + take_reductions())
+    }
+}
+
+// Asking the clock for the time on every step would slow everything down
+pub(crate) const STEPS_BETWEEN_TIME_CHECKS: u64 = 64;
+
+// Runs turns in the order the scheduler picks. A task's turn lasts until its
+// reductions are used up, it completes, or it has to wait. Spawning doesn't end
+// a turn, the new tasks just join the scheduler's queue.
+//
+// Waiting tasks are parked until there's been an event or one of their
+// deadlines has passed, so they cost nothing while they wait. If every task is
+// parked and none has a deadline, nothing can ever wake them, so they're
+// abandoned and each can give up.
+struct Runner<'a, I: Clone, S: Clone> {
+    scheduler: &'a mut dyn Scheduler,
+    tasks: HashMap<TaskId, Task<I, S>>,
+    next_id: TaskId,
+    parked: Vec<(Arc<dyn Wake>, TaskId)>,
+    events_seen: usize,
+    next_deadline: Option<Duration>,
+    steps: u64,
+    max_steps: Option<u64>,
+    time_limit: Option<Duration>,
+    main_result: Option<I>,
+    spawned_results: Vector<S>,
+}
+
+// Runs the tasks until they've all completed, or until the fuel runs out
+pub(crate) fn run_tasks<I: Value, S: Value>(
+    tasks: Vector<Task<I, S>>,
+    scheduler: &mut dyn Scheduler,
+    fuel: Fuel,
+) -> Result<Partial<I, S>, OutOfFuel<Partial<I, S>>> {
+    let mut runner = Runner {
+        scheduler,
+        tasks: HashMap::new(),
+        next_id: 0,
+        parked: vec![],
+        events_seen: events(),
+        next_deadline: None,
+        steps: 0,
+        max_steps: fuel.max_steps,
+        time_limit: fuel.time_limit.map(|limit| clock::now() + limit),
+        main_result: None,
+        spawned_results: vector![],
+    };
+    tasks.into_iter().for_each(|task| runner.add(task));
+
+    let out_of_fuel = runner.run();
+    let results = (runner.main_result, runner.spawned_results);
+    match out_of_fuel {
+        None => Ok(results),
+        Some(timed_out) => Err(OutOfFuel {
+            completed: results,
+            steps: runner.steps,
+            timed_out,
+        }),
+    }
+}
+
+impl<I: Value, S: Value> Runner<'_, I, S> {
+    // Returns whether the time limit passed if the fuel ran out
+    fn run(&mut self) -> Option<bool> {
+        loop {
+            self.unpark_ready();
+
+            let Some((id, reductions)) = self.scheduler.pop() else {
+                if self.parked.is_empty() {
+                    return None;
+                }
+                if self.past_time_limit() {
+                    return Some(true);
+                }
+                match self.next_deadline {
+                    Some(deadline) => clock::sleep_until(
+                        self.time_limit
+                            .map_or(deadline, |limit| limit.min(deadline)),
+                    ),
+                    None => {
+                        self.parked.iter().for_each(|(wake, _)| wake.abandon());
+                        self.unpark(|_| true);
+                    }
+                }
+                continue;
+            };
+
+            let task = self.tasks.remove(&id).unwrap();
+            if let Some(timed_out) = self.take_turn(id, task, reductions) {
+                return Some(timed_out);
+            }
+        }
+    }
+
+    fn take_turn(&mut self, id: TaskId, mut task: Task<I, S>, reductions: u64) -> Option<bool> {
+        let mut used = 0;
+        loop {
+            task = match task {
+                Task::Main(Complete(result)) => {
+                    self.main_result = Some(result);
+                    return None;
+                }
+                Task::Spawned(Complete(result)) => {
+                    self.spawned_results.push_back(result);
+                    return None;
+                }
+                Task::Main(Spawn(continuation, spawned_processes)) => {
+                    self.spawn(spawned_processes);
+                    Task::Main((*continuation).clone())
+                }
+                Task::Spawned(Spawn(continuation, spawned_processes)) => {
+                    self.spawn(spawned_processes);
+                    Task::Spawned((*continuation).clone())
+                }
+                Task::Main(Waiting(wake, continuation)) => {
+                    self.park(wake, id, Task::Main((*continuation).clone()));
+                    return None;
+                }
+                Task::Spawned(Waiting(wake, continuation)) => {
+                    self.park(wake, id, Task::Spawned((*continuation).clone()));
+                    return None;
+                }
+                running => {
+                    if used >= reductions {
+                        self.queue(id, running);
+                        return None;
+                    }
+                    if let Some(timed_out) = self.out_of_fuel() {
+                        return Some(timed_out);
+                    }
+                    self.steps += 1;
+                    let (next, reductions) = running.step();
+                    used += reductions;
+                    next
+                }
+            }
+        }
+    }
+
+    fn out_of_fuel(&self) -> Option<bool> {
+        if self
+            .max_steps
+            .is_some_and(|max_steps| self.steps >= max_steps)
+        {
+            Some(false)
+        } else if self.steps.is_multiple_of(STEPS_BETWEEN_TIME_CHECKS) && self.past_time_limit() {
+            Some(true)
+        } else {
+            None
+        }
+    }
+
+    fn past_time_limit(&self) -> bool {
+        self.time_limit.is_some_and(|limit| clock::now() >= limit)
+    }
+
+    fn add(&mut self, task: Task<I, S>) {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.queue(id, task);
+    }
+
+    fn queue(&mut self, id: TaskId, task: Task<I, S>) {
+        self.scheduler.push(id, task.priority());
+        self.tasks.insert(id, task);
+    }
+
+    fn spawn(&mut self, processes: Vector<Process<S>>) {
+        processes
+            .into_iter()
+            .for_each(|process| self.add(Task::Spawned(process)));
+    }
+
+    // Whatever it's waiting for may have already happened
+    fn park(&mut self, wake: Arc<dyn Wake>, id: TaskId, task: Task<I, S>) {
+        if wake.ready() {
+            self.queue(id, task);
+        } else {
+            if let Some(deadline) = wake.deadline() {
+                self.next_deadline = Some(
+                    self.next_deadline
+                        .map_or(deadline, |next| next.min(deadline)),
+                );
+            }
+            self.tasks.insert(id, task);
+            self.parked.push((wake, id));
+        }
+    }
+
+    fn unpark_ready(&mut self) {
+        let events = events();
+        let timed_out = self
+            .next_deadline
+            .is_some_and(|deadline| clock::now() >= deadline);
+        if events != self.events_seen || timed_out {
+            self.events_seen = events;
+            self.unpark(|wake| wake.ready());
+        }
+    }
+
+    fn unpark(&mut self, ready: impl Fn(&Arc<dyn Wake>) -> bool) {
+        let (woken, parked): (Vec<_>, Vec<_>) =
+            self.parked.drain(..).partition(|(wake, _)| ready(wake));
+        self.parked = parked;
+        self.next_deadline = self
+            .parked
+            .iter()
+            .filter_map(|(wake, _)| wake.deadline())
+            .min();
+        for (_, id) in woken {
+            let task = self.tasks.remove(&id).unwrap();
+            self.queue(id, task);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use im::vector;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::clock::{with_clock, Clock, VirtualClock};
+use crate::expressions::print;
+use crate::interpreter::Interpreter;
+use crate::parsers::diagnostics::parse_program;
+use crate::parsers::nana::input;
+use crate::process::Process::{self, Complete, Running, Spawn, Waiting};
+use crate::process::{Fuel, Wake};
+use crate::scheduler::{reduce, Priority, Reductions, RoundRobin, Scheduler, REDUCTIONS_PER_TURN};
+
+// Counts how often the scheduler asks whether it's ready
+struct Counted(AtomicUsize);
+
+impl Wake for Counted {
+    fn ready(&self) -> bool {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        false
+    }
+
+    fn deadline(&self) -> Option<Duration> {
+        None
+    }
+
+    fn abandon(&self) {}
+}
+
+fn busy(steps: usize) -> Process<usize> {
+    match steps {
+        0 => Complete(0),
+        _ => Running(Arc::new(move || busy(steps - 1))),
+    }
+}
+
+#[test]
+fn test_waiting_processes_are_not_checked_until_something_happens() {
+    let wake = Arc::new(Counted(AtomicUsize::new(0)));
+    let waiting: Process<usize> = Waiting(wake.clone(), Arc::new(Complete(1)));
+
+    assert_eq!(
+        vector![0, 1],
+        Process::round_robin(vector![waiting, busy(1000)])
+    );
+    // Once when it started waiting and once when it was abandoned
+    let checked = wake.0.load(Ordering::Relaxed);
+    assert!(checked <= 2, "checked {} times", checked);
+}
+
+#[test]
+fn test_sleeping_processes_wake_in_deadline_order() {
+    let clock = VirtualClock::default();
+    let (results, spawned) = with_clock(Arc::new(clock.clone()), || {
+        Interpreter::new()
+            .run(parse_program(input(
+                "spawn(Fn [] Do [sleep(30000) :slow])
+                 spawn(Fn [] Do [sleep(10000) :fast])
+                 Do [sleep(20000) :main]",
+            )))
+            .unwrap()
+    });
+
+    assert_eq!(print(results.last().unwrap().clone()), ":main");
+    let spawned: Vec<_> = spawned.into_iter().flatten().map(print).collect();
+    assert_eq!(spawned, [":fast", ":slow"]);
+    // The clock jumped to each deadline rather than the test waiting for it
+    assert_eq!(clock.now(), Duration::from_secs(30));
+}
+
+#[test]
+fn test_timeouts_use_the_current_clock() {
+    let clock = VirtualClock::default();
+    let results = with_clock(Arc::new(clock.clone()), || {
+        Interpreter::new()
+            .eval_str("Receive [:never :received after(60000) :timed-out]")
+            .unwrap()
+    });
+
+    assert_eq!(print(results.last().unwrap().clone()), ":timed-out");
+    assert_eq!(clock.now(), Duration::from_secs(60));
+}
+
+// Each step reports a whole turn's worth of reductions
+fn expensive(steps: usize) -> Process<usize> {
+    match steps {
+        0 => Complete(1),
+        _ => Running(Arc::new(move || {
+            reduce(REDUCTIONS_PER_TURN);
+            expensive(steps - 1)
+        })),
+    }
+}
+
+#[test]
+fn test_turns_end_once_their_reductions_are_used_up() {
+    let process = Spawn(Arc::new(Complete(0)), vector![expensive(3), busy(100)]);
+    let (_, spawned) = process
+        .run_with(&mut Reductions::default(), Fuel::unlimited())
+        .unwrap();
+    // busy takes more steps, but they're cheap enough to fit in one turn
+    assert_eq!(spawned, vector![0, 1]);
+
+    let process = Spawn(Arc::new(Complete(0)), vector![expensive(3), busy(100)]);
+    let (_, spawned) = process
+        .run_with(&mut RoundRobin::default(), Fuel::unlimited())
+        .unwrap();
+    assert_eq!(spawned, vector![1, 0]);
+}
+
+#[test]
+fn test_high_priority_tasks_go_first_and_low_priority_tasks_still_get_turns() {
+    let mut scheduler = Reductions::default();
+    scheduler.push(0, Priority::Low);
+    (1..=10).for_each(|task| scheduler.push(task, Priority::Normal));
+    scheduler.push(11, Priority::High);
+
+    let order: Vec<_> = std::iter::from_fn(|| scheduler.pop().map(|(task, _)| task)).collect();
+    assert_eq!(order, [11, 1, 2, 3, 4, 5, 6, 7, 8, 0, 9, 10]);
+}
+
+fn spawned_results(interpreter: &mut Interpreter, code: &str) -> Vec<String> {
+    let (_, spawned) = interpreter.run(parse_program(input(code))).unwrap();
+    spawned.into_iter().flatten().map(print).collect()
+}
+
+#[test]
+fn test_spawn_with_priority() {
+    let spawned = spawned_results(
+        &mut Interpreter::new(),
+        "spawn-with(:priority :low Fn [] :low)
+         spawn(Fn [] :normal)
+         spawn-with(:priority :high Fn [] :high)",
+    );
+    assert_eq!(spawned, [":high", ":normal", ":low"]);
+}
+
+#[test]
+fn test_interpreters_can_use_other_schedulers() {
+    let code = "Func count-down [n result] Match n [0 result _ count-down(decrement(n) result)]
+                spawn(Fn [] count-down(50 :slow))
+                spawn(Fn [] count-down(5 :fast))";
+
+    let mut interpreter = Interpreter::new();
+    assert_eq!(spawned_results(&mut interpreter, code), [":slow", ":fast"]);
+
+    interpreter.set_scheduler(RoundRobin::default);
+    assert_eq!(spawned_results(&mut interpreter, code), [":fast", ":slow"]);
+}
@@ -0,0 +1,93 @@
+use std::cell::RefCell;
+use std::sync::Arc;
+
+use im::Vector;
+
+use crate::check::check_program;
+use crate::errors::error_variety;
+use crate::expressions::RuntimeExpression;
+use crate::interpreter::Interpreter;
+use crate::output::{with_sink, Buffer};
+use crate::parsers::diagnostics::{parse_program, Diagnostic};
+use crate::parsers::nana::input;
+use crate::process::Fuel;
+
+// The interpreter behind the WIT session resource and the repl. Each
+// evaluation runs against the definitions left behind by the previous one, and
+// captures what it logs rather than printing it.
+pub struct Session {
+    interpreter: RefCell<Interpreter>,
+}
+
+pub struct Evaluation {
+    pub results: Vector<RuntimeExpression>,
+    pub spawned: Vector<Vector<RuntimeExpression>>,
+    // Everything logged while the code ran
+    pub logs: Vec<String>,
+}
+
+impl Session {
+    pub fn new() -> Session {
+        Session {
+            interpreter: RefCell::new(Interpreter::new()),
+        }
+    }
+
+    // Code that can't be parsed isn't run at all, so it leaves the session's
+    // definitions untouched.
+    pub fn evaluate(&self, code: &str) -> Result<Evaluation, Vector<Diagnostic>> {
+        self.evaluate_with_fuel(code, Fuel::unlimited())
+    }
+
+    // Like evaluate, but stops once the fuel runs out, so code from somewhere
+    // untrusted can't hang the host
+    pub fn evaluate_with_fuel(
+        &self,
+        code: &str,
+        fuel: Fuel,
+    ) -> Result<Evaluation, Vector<Diagnostic>> {
+        let logs = Buffer::default();
+        let (results, spawned) = with_sink(Arc::new(logs.clone()), || {
+            let mut interpreter = self.interpreter.borrow_mut();
+            interpreter.set_fuel(fuel);
+            interpreter.run(parse_program(input(code)))
+        })?;
+        Ok(Evaluation {
+            results,
+            spawned,
+            logs: logs.lines(),
+        })
+    }
+
+    // Whether code stops part way through a form, so a REPL should read another
+    // line before evaluating it
+    pub fn needs_more_input(&self, code: &str) -> bool {
+        match parse_program(input(code)) {
+            // Nothing but whitespace after the problem means the form was
+            // cut short, like a REPL entry ending in a newline after log(
+            Err(diagnostics) => diagnostics.iter().any(|d| {
+                code.get(d.span.offset..)
+                    .is_some_and(|rest| rest.trim().is_empty())
+            }),
+            Ok(expressions) => {
+                check_program(expressions, self.interpreter.borrow().environment().clone())
+                    .iter()
+                    .any(|error| error_variety(error) == Some("missing-macro-arguments"))
+            }
+        }
+    }
+
+    pub fn reset(&self) {
+        self.interpreter.replace(Interpreter::new());
+    }
+
+    pub fn bindings(&self) -> Vec<String> {
+        self.interpreter.borrow().names()
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session::new()
+    }
+}
@@ -0,0 +1,103 @@
+use im::vector;
+
+use crate::expressions::print;
+use crate::expressions::RuntimeExpression::{Keyword, Number, String as NString};
+use crate::process::Fuel;
+use crate::s;
+use crate::session::Session;
+
+#[test]
+fn test_definitions_last_between_evaluations() {
+    let session = Session::new();
+
+    let first = session.evaluate("Def x 1").ok().unwrap();
+    assert_eq!(first.results, vector![Number(1)]);
+
+    let second = session.evaluate("add(x 2)").ok().unwrap();
+    assert_eq!(second.results, vector![Number(3)]);
+}
+
+#[test]
+fn test_code_that_fails_to_parse_defines_nothing() {
+    let session = Session::new();
+
+    let diagnostics = session.evaluate("Def y 1\n[1 2").err().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert!(!session.bindings().contains(&s!("y")));
+}
+
+#[test]
+fn test_reset_forgets_definitions() {
+    let session = Session::new();
+    session.evaluate("Def z :zed").ok().unwrap();
+    assert!(session.bindings().contains(&s!("z")));
+
+    session.reset();
+
+    assert!(!session.bindings().contains(&s!("z")));
+    assert!(session.bindings().contains(&s!("add")));
+    let after = session.evaluate("z").ok().unwrap();
+    assert_ne!(after.results, vector![Keyword(s!("zed"))]);
+}
+
+#[test]
+fn test_spawned_results_are_kept_apart() {
+    let session = Session::new();
+
+    let evaluation = session
+        .evaluate("spawn(Fn [] :child)\n:parent")
+        .ok()
+        .unwrap();
+
+    assert_eq!(evaluation.results.last(), Some(&Keyword(s!("parent"))));
+    assert_eq!(evaluation.spawned, vector![vector![Keyword(s!("child"))]]);
+}
+
+#[test]
+fn test_logs_are_captured() {
+    let session = Session::new();
+
+    let evaluation = session
+        .evaluate("log(1 :two)\nlog(\"three\")")
+        .ok()
+        .unwrap();
+
+    assert_eq!(evaluation.logs, vec![s!("1 :two"), s!("\"three\"")]);
+    assert_eq!(evaluation.results, vector![Number(1), NString(s!("three"))]);
+}
+
+#[test]
+fn test_needs_more_input() {
+    let session = Session::new();
+
+    assert!(session.needs_more_input("[1 2"));
+    assert!(session.needs_more_input("log(\n"));
+    assert!(session.needs_more_input("[\n"));
+    assert!(session.needs_more_input("Match x [\n"));
+    assert!(session.needs_more_input("Func add-one [n]"));
+    assert!(!session.needs_more_input("Func add-one [n]\n  add(n 1)"));
+    assert!(!session.needs_more_input("[1 2]"));
+    assert!(!session.needs_more_input("[1 2]]"));
+}
+
+#[test]
+fn test_forms_that_finish_before_the_fuel_runs_out_still_define_things() {
+    let session = Session::new();
+
+    let evaluation = session
+        .evaluate_with_fuel(
+            "Def kept 1\nFunc forever [] forever()\nforever()",
+            Fuel::steps(500),
+        )
+        .ok()
+        .unwrap();
+    assert_eq!(
+        print(evaluation.results.last().unwrap().clone()),
+        ":error(:out-of-fuel \"Ran out of fuel after 500 steps\")"
+    );
+    assert!(session.bindings().contains(&s!("kept")));
+
+    // The limit only applies to the evaluation it was given for
+    let evaluation = session.evaluate("kept").ok().unwrap();
+    assert_eq!(evaluation.results, vector![Number(1)]);
+}
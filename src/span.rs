@@ -0,0 +1,44 @@
+use std::fmt;
+use std::sync::Arc;
+
+// A position in Nana source code. The parser records one for every lexical
+// expression and build_macros copies them onto the runtime expressions that
+// can fail when evaluated (symbols, tagged tuples, macro calls, and holes), so
+// errors can point back at the code that caused them.
+//
+// Spans never take part in equality. Two expressions that read the same are
+// equal no matter where they were written, which keeps pattern matching and
+// assertions working on values built at runtime.
+#[derive(Debug, Clone, Default)]
+pub struct Span {
+    pub file: Option<Arc<str>>,
+    pub offset: usize,
+    pub line: u32,
+    pub column: usize,
+}
+
+impl Span {
+    // Lines start at 1, so the default span (line 0) marks an expression that
+    // was built by Rust code rather than parsed from source.
+    pub fn is_known(&self) -> bool {
+        self.line > 0
+    }
+}
+
+impl PartialEq for Span {
+    fn eq(&self, _other: &Span) -> bool {
+        true
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.is_known() {
+            return write!(f, "unknown location");
+        }
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}", file, self.line, self.column),
+            None => write!(f, "{}:{}", self.line, self.column),
+        }
+    }
+}
@@ -2,8 +2,9 @@ use std::sync::RwLock;
 
 use im::{hashmap, Vector};
 
+use crate::expressions::Arity::Exactly;
 use crate::expressions::Environment;
-use crate::expressions::RuntimeExpression::{self, BuiltinFunction};
+use crate::expressions::RuntimeExpression;
 use crate::s;
 
 struct Atom {
@@ -16,7 +17,7 @@ struct Atom {
 
 pub fn atom_builtins() -> Environment {
     Environment::from(hashmap! {
-      s!("atom") => BuiltinFunction(|_args| {
+      s!("atom") => RuntimeExpression::builtin("atom", Exactly(2), |_args| {
         // Args:
         //   - value: Any.
         //   - watchers: List<Function | BuiltinFunction>
@@ -30,7 +31,7 @@ pub fn atom_builtins() -> Environment {
         todo!()
       }),
 
-      s!("get") => BuiltinFunction(|_args| {
+      s!("get") => RuntimeExpression::builtin("get", Exactly(1), |_args| {
         // Args:
         //   - value: Atom
         //
@@ -39,7 +40,7 @@ pub fn atom_builtins() -> Environment {
         todo!()
       }),
 
-      s!("set!") => BuiltinFunction(|_args| {
+      s!("set!") => RuntimeExpression::builtin("set!", Exactly(2), |_args| {
         // Args:
         //   - atom: Atom
         //   - new-value: Any
@@ -54,7 +55,7 @@ pub fn atom_builtins() -> Environment {
         todo!()
       }),
 
-      s!("transact!") => BuiltinFunction(|_args| {
+      s!("transact!") => RuntimeExpression::builtin("transact!", Exactly(1), |_args| {
         // Args:
         //   - transaction: (Function | BuiltinFunction)<new: Any, old: Any> -> Any
         //
@@ -70,7 +71,7 @@ pub fn atom_builtins() -> Environment {
         todo!()
       }),
 
-      s!("subscribe!") => BuiltinFunction(|_args| {
+      s!("subscribe!") => RuntimeExpression::builtin("subscribe!", Exactly(2), |_args| {
         // Args:
         //   - name: Keyword
         //   - watcher: Function<Any, Any> -> :ok
@@ -86,7 +87,7 @@ pub fn atom_builtins() -> Environment {
         todo!()
       }),
 
-      s!("unsubscribe!") => BuiltinFunction(|_args| {
+      s!("unsubscribe!") => RuntimeExpression::builtin("unsubscribe!", Exactly(1), |_args| {
         // Args:
         //   - name: Keyword
         //
@@ -1,83 +1,399 @@
-use core::panic;
-use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use im::{hashmap, vector, Vector};
 
-use crate::errors::{argument_error, error, error_with_message};
-use crate::eval::{apply, eval, execute, quote};
+use crate::clock;
+use crate::errors::{
+    argument_error, arithmetic_error, error, error_with_message, is_error, panic_error,
+};
+use crate::eval::{apply, eval, execute_file_contents, quote};
+use crate::expressions::Arity::{AtLeast, Between, Exactly};
 use crate::expressions::RuntimeExpression::{
-    BuiltinFunction, BuiltinMacro, Definition, Function, Hole, Keyword, List, Macro, MacroCall,
-    Number, String as NString, Symbol, TaggedTuple,
+    BuiltinFunction, BuiltinMacro, Definition, Function, Handle, Hole, Keyword, List, Local, Macro,
+    MacroCall, Number, String as NString, Symbol, TaggedTuple,
+};
+use crate::expressions::{print_many, Environment, Params, ProcessHandle, RuntimeExpression};
+use crate::mailbox::{
+    as_process, current_process, exitable, finish, link, monitor, signal, CompletionWake,
+    MessageWake, Timer, Yield,
 };
-use crate::expressions::{print_many, Environment, RuntimeExpression};
-use crate::process::Process::{Complete, Spawn};
+use crate::output;
+use crate::process::Process::{self, Complete, Running, Spawn, Waiting};
 use crate::s;
+use crate::scheduler::Priority;
+
+// The values bound by a pattern, in the order their names first appear in it
+pub(crate) type Bindings = Vec<(String, RuntimeExpression)>;
+
+// Matches [a b & rest] against a list with at least two values
+fn does_match_rest(
+    patterns: &Vector<RuntimeExpression>,
+    values: &Vector<RuntimeExpression>,
+    bindings: &mut Bindings,
+) -> bool {
+    let mut patterns = patterns.clone();
+    let rest_pattern = patterns.pop_back().unwrap();
+    patterns.pop_back();
+    if values.len() < patterns.len() {
+        return false;
+    }
+
+    let (mut firsts, rest) = values.clone().split_at(patterns.len());
+    patterns.push_back(rest_pattern);
+    firsts.push_back(List(rest));
+    does_match(&List(patterns), &List(firsts), bindings)
+}
+
+fn is_rest_marker(expression: Option<&RuntimeExpression>) -> bool {
+    matches!(expression, Some(Symbol(name, _)) if name == "&")
+}
 
-fn does_match(pattern: RuntimeExpression, value: RuntimeExpression) -> Option<Environment> {
+// A name used twice in one pattern has to match the same value both times
+fn bind(name: &str, value: &RuntimeExpression, bindings: &mut Bindings) -> bool {
+    match bindings.iter().find(|(bound, _)| bound == name) {
+        Some((_, existing_value)) => existing_value == value,
+        None => {
+            bindings.push((name.to_owned(), value.clone()));
+            true
+        }
+    }
+}
+
+pub(crate) fn does_match(
+    pattern: &RuntimeExpression,
+    value: &RuntimeExpression,
+    bindings: &mut Bindings,
+) -> bool {
     match pattern {
-        Symbol(name) => Some(Environment::from(hashmap! {name => value})),
-        Hole => Some(Environment::new()),
-        List(patterns) if patterns.len() == 0 => match value {
-            List(values) if values.len() == 0 => Some(Environment::new()),
-            _ => None,
-        },
+        Symbol(name, _) | Local(name, _, _) => bind(name, value, bindings),
+        Hole(_) => true,
+        List(patterns)
+            if patterns.len() >= 2 && is_rest_marker(patterns.get(patterns.len() - 2)) =>
+        {
+            match value {
+                List(values) => does_match_rest(patterns, values, bindings),
+                _ => false,
+            }
+        }
         List(patterns) => match value {
             List(values) if values.len() == patterns.len() => patterns
                 .iter()
-                .cloned()
-                .zip(values.iter().cloned())
-                .map(|(pattern, value)| does_match(pattern, value))
-                .fold(Some(Environment::new()), |acc, bindings| {
-                    acc.and_then(|acc| {
-                        bindings.and_then(|bindings| {
-                            for (key, value) in bindings.iter() {
-                                if let Some(existing_value) = acc.get(&key) {
-                                    if existing_value != value {
-                                        return None;
-                                    }
-                                }
-                            }
-                            Some(acc.union(bindings))
-                        })
-                    })
-                }),
-            _ => None,
+                .zip(values.iter())
+                .all(|(pattern, value)| does_match(pattern, value, bindings)),
+            _ => false,
         },
-        NString(_) if pattern == value => Some(Environment::new()),
-        NString(_) => None,
-        Number(_) if pattern == value => Some(Environment::new()),
-        Number(_) => None,
-        Keyword(_) if pattern == value => Some(Environment::new()),
-        Keyword(_) => None,
-        TaggedTuple(..) => None,
-        MacroCall(..) => None, // Macro calls should maybe evaluate and then compare
-        BuiltinFunction(_) => None, // Builtins shouldn't be comparable
-        Function(..) => None,  // Functions shouldn't be comparable
-        BuiltinMacro(..) => None, // Builtins shouldn't be comparable
-        Macro(..) => None,     // Macros shouldn't be comparable
-        Definition(..) => None, // Definitions should just be at the top level.
+        NString(_) | Number(_) | Keyword(_) | Handle(_) => pattern == value,
+        TaggedTuple(..) => false,
+        MacroCall(..) => false, // Macro calls should maybe evaluate and then compare
+        BuiltinFunction(_) => false, // Builtins shouldn't be comparable
+        Function(..) => false,  // Functions shouldn't be comparable
+        BuiltinMacro(..) => false, // Builtins shouldn't be comparable
+        Macro(..) => false,     // Macros shouldn't be comparable
+        Definition(..) => false, // Definitions should just be at the top level.
+    }
+}
+
+// The names a pattern binds, in the order a match binds them
+pub(crate) fn pattern_names(pattern: &RuntimeExpression) -> Vec<String> {
+    fn collect(pattern: &RuntimeExpression, names: &mut Vec<String>) {
+        match pattern {
+            Symbol(name, _) | Local(name, _, _) if !names.contains(name) => {
+                names.push(name.clone())
+            }
+            List(patterns) => {
+                let rest_marker = match patterns.len() {
+                    len if len >= 2 && is_rest_marker(patterns.get(len - 2)) => Some(len - 2),
+                    _ => None,
+                };
+                patterns
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| Some(*index) != rest_marker)
+                    .for_each(|(_, pattern)| collect(pattern, names))
+            }
+            _ => {}
+        }
+    }
+    let mut names = vec![];
+    collect(pattern, &mut names);
+    names
+}
+
+// Functions and macros take the name of the first definition they're given
+fn named(value: RuntimeExpression, name: &str) -> RuntimeExpression {
+    match value {
+        Function(None, params, env, body) => Function(Some(name.to_owned()), params, env, body),
+        Macro(None, params, env, body) => Macro(Some(name.to_owned()), params, env, body),
+        value => value,
+    }
+}
+
+// Reads params like [a b [c 10] & rest]. Required params come first, then
+// optional params with the (unevaluated) expression for their default, then
+// at most one rest param.
+pub(crate) fn parse_params(params: &Vector<RuntimeExpression>) -> Option<Params> {
+    let mut required = Vector::new();
+    let mut optional = Vector::new();
+    let mut params = params.iter();
+
+    while let Some(param) = params.next() {
+        match param {
+            Symbol(name, _) if name == "&" => {
+                return match (params.next(), params.next()) {
+                    (Some(Symbol(rest, _)), None) if rest != "&" => {
+                        Some(Params::new(required, optional, Some(rest.clone())))
+                    }
+                    _ => None,
+                };
+            }
+            Symbol(name, _) if optional.is_empty() => required.push_back(name.clone()),
+            List(pair) => match (pair.get(0), pair.get(1), pair.len()) {
+                (Some(Symbol(name, _)), Some(default), 2) if name != "&" => {
+                    optional.push_back((name.clone(), default.clone()))
+                }
+                _ => return None,
+            },
+            _ => return None,
+        }
+    }
+
+    Some(Params::new(required, optional, None))
+}
+
+// Defaults are evaluated once, when the function is made
+fn make_function(
+    params: Params,
+    body: RuntimeExpression,
+    env: Environment,
+) -> Process<RuntimeExpression> {
+    let defaults = params
+        .optional()
+        .iter()
+        .map(|(_, default)| eval(default.clone(), env.clone()))
+        .collect();
+
+    Process::run_in_sequence(defaults).and_then(Arc::new(move |defaults| {
+        // A default that failed would otherwise hide inside the function
+        if let Some(failed) = defaults.iter().find(|default| is_error(default)) {
+            return Complete(failed.clone());
+        }
+        let optional = params
+            .optional()
+            .iter()
+            .map(|(name, _)| name.clone())
+            .zip(defaults)
+            .collect();
+        let params = Params::new(params.required().clone(), optional, params.rest().cloned());
+        Complete(Function(
+            None,
+            params,
+            env.clone(),
+            vector![body.clone()].into(),
+        ))
+    }))
+}
+
+// The body of the first case whose pattern matches the value, along with
+// what the pattern bound. Cases alternate patterns and bodies.
+fn find_case(
+    cases: &Vector<RuntimeExpression>,
+    value: &RuntimeExpression,
+) -> Option<(RuntimeExpression, Bindings)> {
+    let mut iter = cases.iter();
+    while let (Some(pattern), Some(body)) = (iter.next(), iter.next()) {
+        let mut bindings = vec![];
+        if does_match(pattern, value, &mut bindings) {
+            return Some((body.clone(), bindings));
+        }
+    }
+    None
+}
+
+// The bindings are made as the value is matched, in a frame that shadows
+// anything of the same name
+fn eval_case(
+    body: RuntimeExpression,
+    bindings: Bindings,
+    env: Environment,
+) -> Process<RuntimeExpression> {
+    let (names, values): (Vec<_>, Vec<_>) = bindings.into_iter().unzip();
+    eval(body, env.push_frame(names.into(), values))
+}
+
+// Runs the function in a new process that records its result in the handle
+fn spawn(function: RuntimeExpression, priority: Priority) -> Process<RuntimeExpression> {
+    let handle = ProcessHandle::with_priority(priority);
+    let spawned_handle = handle.clone();
+    let process = Running(Arc::new(move || apply(function.clone(), vector![])));
+    let process = exitable(handle.clone(), as_process(handle.clone(), process)).and_then(Arc::new(
+        move |result| Complete(finish(&spawned_handle, result)),
+    ));
+    Spawn(Arc::new(Complete(Handle(handle))), vector![process])
+}
+
+// The priority given by spawn-with's options, which come in pairs of a keyword
+// and a value. Err says what's wrong with them.
+fn spawn_options(options: Vector<RuntimeExpression>) -> Result<Priority, &'static str> {
+    let mut priority = Priority::Normal;
+    for pair in options.into_iter().collect::<Vec<_>>().chunks(2) {
+        priority = match pair {
+            [Keyword(option), value] if option == "priority" => match value {
+                Keyword(name) if name == "low" => Priority::Low,
+                Keyword(name) if name == "normal" => Priority::Normal,
+                Keyword(name) if name == "high" => Priority::High,
+                _ => return Err("A process's priority must be :low, :normal or :high"),
+            },
+            _ => {
+                return Err("spawn-with takes options like :priority :high followed by a function")
+            }
+        };
     }
+    Ok(priority)
+}
+
+// Waits until the handle's process completes
+fn await_handle(handle: ProcessHandle) -> Process<RuntimeExpression> {
+    await_any(vector![handle])
+}
+
+// Waits until one of the handles' processes completes and returns the result
+// of the first to do so
+fn await_any(handles: Vector<ProcessHandle>) -> Process<RuntimeExpression> {
+    let first = handles
+        .iter()
+        .filter(|handle| handle.completed_at().is_some())
+        .min_by_key(|handle| handle.completed_at());
+    match first.and_then(ProcessHandle::result) {
+        Some(result) => Complete(result),
+        None => {
+            let wake = Arc::new(CompletionWake::new(handles.clone()));
+            let waiting_on = wake.clone();
+            Waiting(
+                wake,
+                Arc::new(Running(Arc::new(move || {
+                    if waiting_on.abandoned() {
+                        error_with_message(
+                            "deadlock",
+                            "await is waiting on a process that can never complete",
+                        )
+                    } else {
+                        await_any(handles.clone())
+                    }
+                }))),
+            )
+        }
+    }
+}
+
+// What a Receive is waiting for: a message that matches one of its cases, or
+// for its deadline to pass
+struct Receiver {
+    pid: ProcessHandle,
+    cases: Vector<RuntimeExpression>,
+    timeout: Option<(Duration, RuntimeExpression)>,
+    env: Environment,
+}
+
+// Takes the first message in the mailbox that matches a case and runs the
+// case. With no match the process waits for more messages, rather than being
+// stepped again.
+fn receive(receiver: Arc<Receiver>) -> Process<RuntimeExpression> {
+    let Receiver {
+        pid,
+        cases,
+        timeout,
+        env,
+    } = &*receiver;
+
+    let seen = match pid.take_message(|message| find_case(cases, message)) {
+        Ok((body, bindings)) => return eval_case(body, bindings, env.clone()),
+        Err(seen) => seen,
+    };
+    if let Some((deadline, body)) = timeout {
+        if clock::now() >= *deadline {
+            return eval_case(body.clone(), vec![], env.clone());
+        }
+    }
+
+    let wake = Arc::new(MessageWake::new(
+        pid.clone(),
+        seen,
+        timeout.as_ref().map(|(deadline, _)| *deadline),
+    ));
+    let waiting_on = wake.clone();
+    Waiting(
+        wake,
+        Arc::new(Running(Arc::new(move || {
+            if waiting_on.abandoned() {
+                error_with_message(
+                    "deadlock",
+                    "Receive is waiting for a message that can never arrive",
+                )
+            } else {
+                receive(receiver.clone())
+            }
+        }))),
+    )
+}
+
+// The after(ms) case that ends a Receive's cases, if there is one
+pub(crate) fn is_timeout_case(pattern: &RuntimeExpression) -> bool {
+    match pattern {
+        TaggedTuple(tag, values, _) => {
+            values.len() == 1
+                && matches!(&**tag, Symbol(name, _) | Local(name, _, _) if name == "after")
+        }
+        _ => false,
+    }
+}
+
+fn handles(values: &Vector<RuntimeExpression>) -> Option<Vector<ProcessHandle>> {
+    values
+        .iter()
+        .map(|value| match value {
+            Handle(handle) => Some(handle.clone()),
+            _ => None,
+        })
+        .collect()
 }
 
 pub fn builtins() -> Environment {
     Environment::from(hashmap! {
         // TODO: Make expressions print themselves in a readable form
-        s!("log") => BuiltinFunction(|args| {
-            println!("{}", print_many(args.clone(), " "));
-            Complete(args.head().unwrap().clone())
+        s!("log") => RuntimeExpression::builtin("log", AtLeast(1), |args| {
+            match args.head() {
+                Some(first) => {
+                    output::write_line(&print_many(args.clone(), " "));
+                    Complete(first.clone())
+                }
+                None => argument_error("log takes at least 1 argument"),
+            }
         }),
 
-        s!("panic") => BuiltinFunction(|args| {
-            panic!("Panic called with {:?}", args);
+        // Ends the process that calls it, with :error(:panic args...) as the
+        // reason, so processes linked to it or monitoring it hear about it.
+        // Outside a spawned process that error is just returned.
+        s!("panic") => RuntimeExpression::builtin("panic", AtLeast(0), |args| {
+            let reason = panic_error(args);
+            match current_process() {
+                Some(current) if !current.is_top_level() => {
+                    current.set_exit_reason(reason);
+                    Waiting(Arc::new(Yield), Arc::new(Complete(Keyword(s!("ok")))))
+                }
+                _ => Complete(reason),
+            }
         }),
 
         // TODO: equality
 
-        s!("add") => BuiltinFunction(|mut args| {
+        s!("add") => RuntimeExpression::builtin("add", Exactly(2), |mut args| {
             if args.len() == 2 {
                 match [args.pop_front().unwrap(), args.pop_front().unwrap()] {
-                    [Number(l), Number(r)] => Complete(Number(l + r)),
+                    [Number(l), Number(r)] => match l.checked_add(r) {
+                        Some(result) => Complete(Number(result)),
+                        None => arithmetic_error(&format!("add({} {}) is too large to represent", l, r)),
+                    },
                     _ => argument_error("add takes exactly 2 numbers")
                 }
             } else {
@@ -85,10 +401,13 @@ pub fn builtins() -> Environment {
             }
         }),
 
-        s!("subtract") => BuiltinFunction(|mut args| {
+        s!("subtract") => RuntimeExpression::builtin("subtract", Exactly(2), |mut args| {
             if args.len() == 2 {
                 match [args.pop_front().unwrap(), args.pop_front().unwrap()] {
-                    [Number(l), Number(r)] => Complete(Number(l - r)),
+                    [Number(l), Number(r)] => match l.checked_sub(r) {
+                        Some(result) => Complete(Number(result)),
+                        None => arithmetic_error(&format!("subtract({} {}) would be negative", l, r)),
+                    },
                     _ => argument_error("subtract takes exactly 2 numbers")
                 }
             } else {
@@ -96,10 +415,13 @@ pub fn builtins() -> Environment {
             }
         }),
 
-        s!("multiply") => BuiltinFunction(|mut args| {
+        s!("multiply") => RuntimeExpression::builtin("multiply", Exactly(2), |mut args| {
             if args.len() == 2 {
                 match [args.pop_front().unwrap(), args.pop_front().unwrap()] {
-                    [Number(l), Number(r)] => Complete(Number(l * r)),
+                    [Number(l), Number(r)] => match l.checked_mul(r) {
+                        Some(result) => Complete(Number(result)),
+                        None => arithmetic_error(&format!("multiply({} {}) is too large to represent", l, r)),
+                    },
                     _ => argument_error("multiply takes exactly 2 numbers")
                 }
             } else {
@@ -107,10 +429,13 @@ pub fn builtins() -> Environment {
             }
         }),
 
-        s!("divide") => BuiltinFunction(|mut args| {
+        s!("divide") => RuntimeExpression::builtin("divide", Exactly(2), |mut args| {
             if args.len() == 2 {
                 match [args.pop_front().unwrap(), args.pop_front().unwrap()] {
-                    [Number(l), Number(r)] => Complete(Number(l / r)),
+                    [Number(l), Number(r)] => match l.checked_div(r) {
+                        Some(result) => Complete(Number(result)),
+                        None => arithmetic_error(&format!("divide({} {}) divides by zero", l, r)),
+                    },
                     _ => argument_error("divide takes exactly 2 numbers")
                 }
             } else {
@@ -118,10 +443,13 @@ pub fn builtins() -> Environment {
             }
         }),
 
-        s!("remainder") => BuiltinFunction(|mut args| {
+        s!("remainder") => RuntimeExpression::builtin("remainder", Exactly(2), |mut args| {
             if args.len() == 2 {
                 match [args.pop_front().unwrap(), args.pop_front().unwrap()] {
-                    [Number(l), Number(r)] => Complete(Number(l % r)),
+                    [Number(l), Number(r)] => match l.checked_rem(r) {
+                        Some(result) => Complete(Number(result)),
+                        None => arithmetic_error(&format!("remainder({} {}) divides by zero", l, r)),
+                    },
                     _ => argument_error("remainder takes exactly 2 numbers")
                 }
             } else {
@@ -130,6 +458,7 @@ pub fn builtins() -> Environment {
         }),
 
         s!("Def") => BuiltinMacro(
+            s!("Def"),
             vector![
               s!("name"),
               s!("value")
@@ -140,9 +469,10 @@ pub fn builtins() -> Environment {
                     let value = args.pop_front().unwrap();
 
                     match name {
-                        Symbol(name) | NString(name) => {
+                        Symbol(name, _) | NString(name) => {
                             let new_env = env.prepare(name.clone());
                             eval(value, new_env.clone()).and_then(Arc::new(move |result| {
+                                let result = named(result, &name);
                                 new_env.provide(&name, result.clone()).expect("Providing a prepared value should not fail");
                                 Complete(Definition(name.clone(), Arc::new(result.clone())))
                             }))
@@ -156,6 +486,7 @@ pub fn builtins() -> Environment {
         ),
 
         s!("Match") => BuiltinMacro(
+            s!("Match"),
             vector![
               s!("value"),
               s!("cases")
@@ -171,28 +502,10 @@ pub fn builtins() -> Environment {
                                 if cases.len() % 2 != 0 {
                                     argument_error("Match cases must be a list with an even number of elements")
                                 } else {
-                                    let mut iter = cases.iter();
-                                    while let (Some(pattern), Some(body)) = (iter.next(), iter.next()) {
-                                        match does_match(pattern.clone(), value.clone()) {
-                                            // TODO: I think this is probably why
-                                            // shadowing isn't working correctly. WE
-                                            // shouldn't be evalling here. Instead
-                                            // we should be returning a blob of code
-                                            // that uses calls to llambdas to define
-                                            // local variables and then maybe makes
-                                            // use of a Builtin If statement or
-                                            // something. More should be happening
-                                            // at runtime rather than macro expand time.
-                                            // TODO: Split macro-expand out as a
-                                            // function so macros can be debugged
-                                            // better. Implement the existing macro
-                                            // running stuff in terms of
-                                            // macro-expand followed by eval.
-                                            Some(bindings) => return eval(body.clone(), env.clone().union(bindings)),
-                                            None => {}
-                                        }
+                                    match find_case(&cases, &value) {
+                                        Some((body, bindings)) => eval_case(body, bindings, env.clone()),
+                                        None => error("no-match-found"),
                                     }
-                                    error("no-match-found")
                                 }
                             },
                             _ => error_with_message("argument", "Match takes a value and a list of cases")
@@ -204,7 +517,56 @@ pub fn builtins() -> Environment {
             }
         ),
 
+        // Like Match, but for the first message in the process's mailbox that
+        // matches a case. A last case of after(ms) body runs the body if no
+        // message matches within ms milliseconds.
+        s!("Receive") => BuiltinMacro(
+            s!("Receive"),
+            vector![
+              s!("cases")
+            ],
+            |mut args, env| {
+                let Some(pid) = current_process() else {
+                    return error_with_message("no-process", "Receive can only be used within a process");
+                };
+                match args.pop_front() {
+                    Some(List(mut cases)) if cases.len() % 2 == 0 => {
+                        let timeout = match cases.len() {
+                            len if len >= 2 && is_timeout_case(&cases[len - 2]) => {
+                                let body = cases.pop_back().unwrap();
+                                let pattern = cases.pop_back().unwrap();
+                                match pattern {
+                                    TaggedTuple(_, mut values, _) => Some((values.pop_front().unwrap(), body)),
+                                    _ => unreachable!(),
+                                }
+                            }
+                            _ => None,
+                        };
+
+                        match timeout {
+                            Some((ms, body)) => eval(ms, env.clone()).and_then(Arc::new(move |ms| match ms {
+                                Number(ms) => {
+                                    let timeout = Duration::from_millis(ms.min(u64::MAX as u128) as u64);
+                                    receive(Arc::new(Receiver {
+                                        pid: pid.clone(),
+                                        cases: cases.clone(),
+                                        timeout: Some((clock::now() + timeout, body.clone())),
+                                        env: env.clone(),
+                                    }))
+                                }
+                                _ => argument_error("Receive's after takes a number of milliseconds"),
+                            })),
+                            None => receive(Arc::new(Receiver { pid, cases, timeout: None, env })),
+                        }
+                    }
+                    Some(List(_)) => argument_error("Receive cases must be a list with an even number of elements"),
+                    _ => argument_error("Receive takes a list of cases"),
+                }
+            }
+        ),
+
         s!("Fn") => BuiltinMacro(
+            s!("Fn"),
             vector![
               s!("params"),
               s!("body")
@@ -215,13 +577,9 @@ pub fn builtins() -> Environment {
                     let body = args.pop_front().unwrap();
 
                     match params {
-                        List(params) => {
-                            let param_strings = params.iter().map(|p| match p {
-                                Symbol(s) => s,
-                                // TODO: Rewrite this as an argument_error
-                                _ => panic!("Func params must be ValueNames")
-                            }).cloned().collect::<Vector<String>>();
-                            Complete(Function(param_strings, env, vector![body]))
+                        List(params) => match parse_params(&params) {
+                            Some(params) => make_function(params, body, env),
+                            None => argument_error("Fn params must be symbols, then [name default] pairs, then & and a symbol"),
                         },
                         _ => argument_error("Fn takes a list of params and a single body expression")
                     }
@@ -232,6 +590,7 @@ pub fn builtins() -> Environment {
         ),
 
         s!("Quote") => BuiltinMacro(
+            s!("Quote"),
             vector![
               s!("value")
             ],
@@ -245,6 +604,7 @@ pub fn builtins() -> Environment {
         ),
 
         s!("Macro") => BuiltinMacro(
+            s!("Macro"),
             vector![
               s!("params"),
               s!("body")
@@ -255,13 +615,10 @@ pub fn builtins() -> Environment {
                     let body = args.pop_front().unwrap();
 
                     match params {
-                        List(params) => {
-                            let param_strings = params.iter().map(|p| match p {
-                                Symbol(s) => s,
-                                // TODO: Rewrite this as an argument error
-                                _ => panic!("Macro params must be symbols")
-                            }).cloned().collect::<Vector<String>>();
-                            Complete(Macro(param_strings, env, vector![body]))
+                        List(params) => match parse_params(&params) {
+                            Some(params) if params.optional().is_empty() => Complete(Macro(None, params, env, vector![body])),
+                            Some(_) => argument_error("Macro params can't have defaults. Use & to take a list of forms instead"),
+                            None => argument_error("Macro params must be symbols, then & and a symbol"),
                         },
                         _ => argument_error("Macro takes a list of params and a single body expression")
                     }
@@ -270,42 +627,190 @@ pub fn builtins() -> Environment {
                 }
             }
         ),
-        s!("time") => BuiltinFunction(|args| {
-            if args.len() == 0 {
-                let start = SystemTime::now();
-                let since_epoch = start.duration_since(UNIX_EPOCH).expect("Time went backwards");
+        s!("time") => RuntimeExpression::builtin("time", Exactly(0), |_args| {
+            let start = SystemTime::now();
+            let since_epoch = start.duration_since(UNIX_EPOCH).expect("Time went backwards");
 
-                Complete(Number(since_epoch.as_millis()))
-            } else {
-                argument_error("time takes no arguments")
+            Complete(Number(since_epoch.as_millis()))
+        }),
+
+        // Waits for at least ms milliseconds while other processes carry on
+        s!("sleep") => RuntimeExpression::builtin("sleep", Exactly(1), |args| {
+            match args.head() {
+                Some(Number(ms)) => {
+                    let duration = Duration::from_millis((*ms).min(u64::MAX as u128) as u64);
+                    Waiting(
+                        Arc::new(Timer(clock::now() + duration)),
+                        Arc::new(Complete(Keyword(s!("ok")))),
+                    )
+                }
+                _ => argument_error("sleep takes a number of milliseconds"),
             }
         }),
 
-        // TODO: spawn needs to:
-        // 1. Create a promise
-        // 2. Wrap the readable side of the promise in a Complete process
-        // 3. Create a Running process that will call the Fn and
-        // when it completes use its return value to resolve the
-        // process.
-        // 4. Return the (Complete(..), Some(Running(..)))
-        s!("spawn") => BuiltinFunction(|mut args| {
+        // Returns a handle to the new process straight away
+        s!("spawn") => RuntimeExpression::builtin("spawn", Exactly(1), |mut args| {
             if args.len() == 1 {
                 let first_arg = args.pop_front().unwrap();
                 match first_arg {
-                    Function(..) => Spawn(Arc::new(Complete(Keyword(s!("process-spawned")))),
-                                          vector![apply(first_arg, vector![])]),
+                    Function(..) => spawn(first_arg, Priority::Normal),
                     // TODO: Should probably support BuiltinFunction too
                     _ => argument_error("spawn takes 1 function (with no arguments) as an argument")
                 }
             } else {
                 argument_error("spawn takes 1 function (with no arguments) as an argument")
             }
+        }),
+
+        // Like spawn, but the function comes after options like :priority :high
+        s!("spawn-with") => RuntimeExpression::builtin("spawn-with", AtLeast(1), |mut args| {
+            match (args.pop_back(), spawn_options(args)) {
+                (Some(function @ Function(..)), Ok(priority)) => spawn(function, priority),
+                (_, Err(message)) => argument_error(message),
+                _ => argument_error("spawn-with takes options like :priority :high followed by a function"),
+            }
+        }),
+
+        // The result of a spawned process, which may be an error
+        s!("await") => RuntimeExpression::builtin("await", Exactly(1), |args| {
+            match args.head() {
+                Some(Handle(handle)) => await_handle(handle.clone()),
+                _ => argument_error("await takes a process handle"),
+            }
+        }),
+
+        // The results of every process, in the order they were given
+        s!("await-all") => RuntimeExpression::builtin("await-all", Exactly(1), |args| {
+            match args.head() {
+                Some(List(values)) => match handles(values) {
+                    Some(handles) => {
+                        Process::run_in_sequence(handles.into_iter().map(await_handle).collect())
+                            .and_then(Arc::new(|results| Complete(List(results))))
+                    }
+                    None => argument_error("await-all takes a list of process handles"),
+                },
+                _ => argument_error("await-all takes a list of process handles"),
+            }
+        }),
+
+        // The result of whichever process completes first
+        s!("await-any") => RuntimeExpression::builtin("await-any", Exactly(1), |args| {
+            match args.head() {
+                Some(List(values)) if !values.is_empty() => match handles(values) {
+                    Some(handles) => await_any(handles),
+                    None => argument_error("await-any takes a non-empty list of process handles"),
+                },
+                _ => argument_error("await-any takes a non-empty list of process handles"),
+            }
+        }),
+
+        // The pid of the process that calls it
+        s!("self") => RuntimeExpression::builtin("self", Exactly(0), |_args| {
+            match current_process() {
+                Some(pid) => Complete(Handle(pid)),
+                None => error_with_message("no-process", "self can only be called within a process"),
+            }
+        }),
+
+        // Puts the message in the process's mailbox and returns it
+        s!("send") => RuntimeExpression::builtin("send", Exactly(2), |args| {
+            match (args.get(0), args.get(1)) {
+                (Some(Handle(pid)), Some(message)) => {
+                    pid.send(message.clone());
+                    Complete(message.clone())
+                }
+                _ => argument_error("send takes a pid and a message"),
+            }
+        }),
+
+        // Links the calling process and pid, so that if either exits
+        // abnormally the other does too
+        s!("link") => RuntimeExpression::builtin("link", Exactly(1), |args| {
+            match (current_process(), args.head()) {
+                (None, _) => error_with_message("no-process", "link can only be called within a process"),
+                (Some(current), Some(Handle(pid))) => {
+                    link(&current, pid);
+                    Complete(Keyword(s!("ok")))
+                }
+                _ => argument_error("link takes a pid"),
+            }
+        }),
+
+        // Sends the calling process [:down pid reason] when pid exits
+        s!("monitor") => RuntimeExpression::builtin("monitor", Exactly(1), |args| {
+            match (current_process(), args.head()) {
+                (None, _) => error_with_message("no-process", "monitor can only be called within a process"),
+                (Some(current), Some(Handle(pid))) => {
+                    monitor(&current, pid);
+                    Complete(Keyword(s!("ok")))
+                }
+                _ => argument_error("monitor takes a pid"),
+            }
+        }),
+
+        // exit(reason) ends the calling process. exit(pid reason) tells pid
+        // to exit as if a process linked to it had exited with reason.
+        s!("exit") => RuntimeExpression::builtin("exit", Between(1, 2), |args| {
+            let Some(current) = current_process() else {
+                return error_with_message("no-process", "exit can only be called within a process");
+            };
+            match (args.get(0), args.get(1)) {
+                (Some(_), None) if current.is_top_level() => {
+                    error_with_message("no-process", "exit can only end a spawned process")
+                }
+                (Some(reason), None) => {
+                    current.set_exit_reason(reason.clone());
+                    Waiting(Arc::new(Yield), Arc::new(Complete(Keyword(s!("ok")))))
+                }
+                (Some(Handle(pid)), Some(reason)) => {
+                    signal(pid, &current, reason.clone());
+                    Waiting(Arc::new(Yield), Arc::new(Complete(Keyword(s!("ok")))))
+                }
+                _ => argument_error("exit takes a reason, or a pid and a reason"),
+            }
+        }),
+
+        // Exits of linked processes arrive in the calling process's mailbox as
+        // [:exit pid reason], rather than making it exit
+        s!("trap-exits") => RuntimeExpression::builtin("trap-exits", Exactly(0), |_args| {
+            match current_process() {
+                Some(current) => {
+                    current.trap_exits();
+                    Complete(Keyword(s!("ok")))
+                }
+                None => error_with_message("no-process", "trap-exits can only be called within a process"),
+            }
         })
     })
 }
 
+// Environments are persistent, so every interpreter can start from the same
+// standard library. It's built the first time it's asked for.
+static BUILTINS: OnceLock<Environment> = OnceLock::new();
+
+// Whether a_macro is the builtin macro first bound to name, rather than
+// something a program has defined in its place
+pub(crate) fn is_builtin_macro(name: &str, a_macro: &RuntimeExpression) -> bool {
+    matches!(
+        BUILTINS.get_or_init(builtins).get(name),
+        Some(builtin @ BuiltinMacro(..)) if &builtin == a_macro
+    )
+}
+
+static STANDARD_LIBRARY: OnceLock<Environment> = OnceLock::new();
+
 pub fn standard_library() -> Environment {
-    let (_result, new_env) = execute(PROGRAM_CODE.to_owned(), builtins())
+    STANDARD_LIBRARY.get_or_init(build_standard_library).clone()
+}
+
+// Parses and runs the standard library, checking its assertions along the way
+pub fn build_standard_library() -> Environment {
+    let env = execute_library("examples/standard_library.nana", PROGRAM_CODE, builtins());
+    execute_library("examples/supervisor.nana", SUPERVISOR_CODE, env)
+}
+
+fn execute_library(file: &str, code: &str, env: Environment) -> Environment {
+    let (_result, new_env) = execute_file_contents(file, code.to_owned(), env)
         .head()
         .unwrap()
         .clone();
@@ -313,3 +818,4 @@ pub fn standard_library() -> Environment {
 }
 
 pub static PROGRAM_CODE: &str = include_str!("../../examples/standard_library.nana");
+pub static SUPERVISOR_CODE: &str = include_str!("../../examples/supervisor.nana");
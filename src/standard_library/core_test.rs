@@ -1,9 +1,15 @@
 use im::{hashmap, vector};
 
 use crate::environment::Environment;
-use crate::expressions::RuntimeExpression::{Macro, Number, String as NString, Symbol};
+use crate::expressions::print;
+use crate::expressions::RuntimeExpression::{
+    Keyword, List, Local, Macro, Number, String as NString,
+};
+use crate::expressions::{Params, Slot};
 use crate::helpers::strip_functions;
-use crate::standard_library::core::standard_library;
+use crate::interpreter::Interpreter;
+use crate::span::Span;
+use crate::standard_library::core::{build_standard_library, standard_library};
 use crate::{eval::execute_once, s};
 
 #[test]
@@ -83,9 +89,11 @@ fn test_macro() {
     let program = r#"Macro [a b] b"#;
     let mut actual = execute_once(String::from(program), standard_library());
     assert!(match actual.pop_front().unwrap() {
-        Macro(args, _, body) => {
-            assert_eq!(vector![s!("a"), s!("b")], args);
-            assert_eq!(vector![Symbol(s!("b"))], body);
+        Macro(_name, args, _, body) => {
+            assert_eq!(Params::from(vector![s!("a"), s!("b")]), args);
+            // The body is resolved to the second param's slot
+            let b = Local(s!("b"), Slot { depth: 0, index: 1 }, Span::default());
+            assert_eq!(vector![b], body);
             true
         }
         _ => false,
@@ -103,3 +111,278 @@ fn test_func() {
     let expected = vector![Number(1)];
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn test_rest_params() {
+    let program = r#"
+    Func rest [a & others]
+        [a others]
+
+    rest(1)
+    rest(1 2 3)
+    rest()"#;
+    let actual = strip_functions(execute_once(String::from(program), standard_library()));
+    let expected = vector![
+        List(vector![Number(1), List(vector![])]),
+        List(vector![Number(1), List(vector![Number(2), Number(3)])]),
+    ];
+    assert_eq!(expected, actual.take(2));
+    assert_eq!(
+        print(actual[2].clone()),
+        ":error(:arity \"rest takes at least 1 argument but was given 0\" :at(\"7:5\"))"
+    );
+}
+
+#[test]
+fn test_failed_defaults_are_returned() {
+    let program = "Fn [a [b add(a 1)]] b";
+    let actual = execute_once(String::from(program), standard_library());
+    assert_eq!(
+        print(actual[0].clone()),
+        ":error(:argument \"add takes exactly 2 numbers\" :at(\"1:10\"))"
+    );
+}
+
+#[test]
+fn test_optional_params() {
+    let program = r#"
+    Def two 2
+    Func optional [a [b two] [c add(two 1)]]
+        [a b c]
+
+    optional(1)
+    optional(1 5)
+    optional(1 5 6)
+    optional(1 5 6 7)"#;
+    let actual = strip_functions(execute_once(String::from(program), standard_library()));
+    let expected = vector![
+        Number(2),
+        List(vector![Number(1), Number(2), Number(3)]),
+        List(vector![Number(1), Number(5), Number(3)]),
+        List(vector![Number(1), Number(5), Number(6)]),
+    ];
+    assert_eq!(expected, actual.take(4));
+    assert_eq!(
+        print(actual[4].clone()),
+        ":error(:arity \"optional takes 1 to 3 arguments but was given 4\" :at(\"9:5\"))"
+    );
+}
+
+#[test]
+fn test_macros_take_their_rest_as_a_list() {
+    let program = r#"
+    Defmacro "Second" [first & rest]
+      Quote
+        last([Unquote first Unquote rest])
+
+    Second 1 [2 3]
+    Second 1 2"#;
+    let actual = strip_functions(execute_once(String::from(program), standard_library()));
+    assert_eq!(
+        actual.iter().cloned().map(print).collect::<Vec<_>>(),
+        vec![
+            "[2 3]",
+            ":error(:argument \"Second takes its remaining forms in a list\")"
+        ]
+    );
+}
+
+#[test]
+fn test_macros_cannot_have_defaults() {
+    let actual = execute_once(s!("Macro [a [b 1]] a"), standard_library());
+    assert_eq!(
+        print(actual[0].clone()),
+        ":error(:argument \"Macro params can't have defaults. Use & to take a list of forms instead\" :at(\"1:1\"))"
+    );
+}
+
+#[test]
+fn test_rest_patterns() {
+    let program = "
+    Match [1 2 3] [[a & rest] [a rest]]
+    Match [1] [[a b & rest] :long _ :short]";
+    let actual = execute_once(String::from(program), standard_library());
+    let expected = vector![
+        List(vector![Number(1), List(vector![Number(2), Number(3)])]),
+        Keyword(s!("short")),
+    ];
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_the_standard_library_is_only_built_once() {
+    // Builtins only compare equal to themselves, so a library built again
+    // won't equal the cached one
+    let add = |library: Environment| library.get("add").unwrap();
+    assert_eq!(add(standard_library()), add(standard_library()));
+    assert_ne!(add(build_standard_library()), add(standard_library()));
+}
+
+#[test]
+fn test_await() {
+    let mut interpreter = Interpreter::new();
+    let results = interpreter
+        .eval_str(
+            "Func count-down [n result] Match n [0 result _ count-down(decrement(n) result)]
+             Def slow spawn(Fn [] count-down(500 :slow))
+             Def fast spawn(Fn [] count-down(5 :fast))
+             Def failing spawn(Fn [] missing())
+             [await(slow) await-all([slow fast]) await-any([slow fast])]
+             await(failing)
+             await(1)
+             await-any([])",
+        )
+        .unwrap();
+
+    let printed: Vec<String> = results.into_iter().skip(4).map(print).collect();
+    assert_eq!(
+        printed,
+        vec![
+            "[:slow [:slow :fast] :fast]",
+            ":error(:not-found \"No function 'missing' found\" :at(\"4:38\"))",
+            ":error(:argument \"await takes a process handle\" :at(\"7:14\"))",
+            ":error(:argument \"await-any takes a non-empty list of process handles\" :at(\"8:14\"))",
+        ]
+    );
+}
+
+#[test]
+fn test_receive() {
+    let mut interpreter = Interpreter::new();
+    let results = interpreter
+        .eval_str(
+            "Do [send(self() :first) send(self() [:second 2]) :sent]
+             Receive [[:second n] n]
+             Receive [message message]
+             Receive [_ :nothing after(0) :timed-out]
+             Receive [_ :nothing]
+             send(1 :message)
+             Receive [:odd]",
+        )
+        .unwrap();
+
+    let printed: Vec<String> = results.into_iter().map(print).collect();
+    assert_eq!(
+        printed,
+        vec![
+            ":sent",
+            "2",
+            ":first",
+            ":timed-out",
+            ":error(:deadlock \"Receive is waiting for a message that can never arrive\")",
+            ":error(:argument \"send takes a pid and a message\" :at(\"6:14\"))",
+            ":error(:argument \"Receive cases must be a list with an even number of elements\" :at(\"7:14\"))",
+        ]
+    );
+}
+
+#[test]
+fn test_links_and_monitors() {
+    let mut interpreter = Interpreter::new();
+    let results = interpreter
+        .eval_str(
+            "Func wait [] Receive [:never :received]
+             Def waiter spawn(Fn [] Do [link(spawn(Fn [] exit(:crashed))) wait()])
+             Def watched spawn(Fn [] wait())
+             Def linked spawn(Fn [] :error(:failed))
+             Do [monitor(watched) exit(watched :stopped) Receive [[:down _ reason] reason]]
+             Do [link(linked) Receive [[:exit _ reason] reason]]
+             [await(waiter) await(watched) await(linked)]
+             Do [monitor(linked) Receive [[:down _ reason] reason]]
+             exit(:top-level)
+             exit(1 :reason)",
+        )
+        .unwrap();
+
+    let printed: Vec<String> = results.into_iter().skip(4).map(print).collect();
+    assert_eq!(
+        printed,
+        vec![
+            ":stopped",
+            ":error(:failed)",
+            "[:exit(:crashed) :exit(:stopped) :error(:failed :at(\"7:44\"))]",
+            ":error(:failed)",
+            ":error(:no-process \"exit can only end a spawned process\" :at(\"9:14\"))",
+            ":error(:argument \"exit takes a reason, or a pid and a reason\" :at(\"10:14\"))",
+        ]
+    );
+}
+
+#[test]
+fn test_panic_only_ends_the_calling_process() {
+    let mut interpreter = Interpreter::new();
+    let results = interpreter
+        .eval_str(
+            "Def panicky spawn(Fn [] Do [panic(:boom) :unreachable])
+             Do [monitor(panicky) Receive [[:down _ reason] reason]]
+             await(panicky)
+             panic(:top-level 1)",
+        )
+        .unwrap();
+
+    let printed: Vec<String> = results.into_iter().skip(1).map(print).collect();
+    assert_eq!(
+        printed,
+        vec![
+            ":error(:panic :boom)",
+            ":exit(:error(:panic :boom))",
+            ":error(:panic :top-level 1 :at(\"4:14\"))",
+        ]
+    );
+}
+
+#[test]
+fn test_supervisors_restart_children_that_panic() {
+    let mut interpreter = Interpreter::new();
+    let results = interpreter
+        .eval_str(
+            "Func counter [count]
+               Receive [[:next from] Do [send(from count) counter(increment(count))]]
+             Def starts spawn(Fn [] counter(0))
+             Func next-start [] Do [send(starts [:next self()]) Receive [n n]]
+             Func child [] Match next-start() [0 panic(:first-try) _ :done]
+             await(supervisor([child]))
+             next-start()",
+        )
+        .unwrap();
+
+    let printed: Vec<String> = results.into_iter().skip(4).map(print).collect();
+    assert_eq!(printed, vec![":ok", "2"]);
+}
+
+#[test]
+fn test_spawn_with() {
+    let mut interpreter = Interpreter::new();
+    let results = interpreter
+        .eval_str(
+            "spawn-with(:priority :urgent Fn [] 1)
+             spawn-with(:colour :red Fn [] 1)
+             spawn-with(:priority :high 1)",
+        )
+        .unwrap();
+
+    let printed: Vec<String> = results.into_iter().map(print).collect();
+    assert_eq!(
+        printed,
+        vec![
+            ":error(:argument \"A process's priority must be :low, :normal or :high\" :at(\"1:1\"))",
+            ":error(:argument \"spawn-with takes options like :priority :high followed by a function\" :at(\"2:14\"))",
+            ":error(:argument \"spawn-with takes options like :priority :high followed by a function\" :at(\"3:14\"))",
+        ]
+    );
+}
+
+#[test]
+fn test_sleep() {
+    let mut interpreter = Interpreter::new();
+    let results = interpreter.eval_str("sleep(0)\nsleep(:soon)").unwrap();
+
+    let printed: Vec<String> = results.into_iter().map(print).collect();
+    assert_eq!(
+        printed,
+        vec![
+            ":ok",
+            ":error(:argument \"sleep takes a number of milliseconds\" :at(\"2:1\"))",
+        ]
+    );
+}
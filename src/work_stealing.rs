@@ -0,0 +1,343 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use im::{vector, Vector};
+
+use crate::clock::{self, with_clock};
+use crate::output::{self, with_sink};
+use crate::process::Process::{Complete, Spawn, Waiting};
+use crate::process::{Fuel, OutOfFuel, Partial, Process, Value, Wake};
+use crate::scheduler::{self, Priority, Task, REDUCTIONS_PER_TURN, STEPS_BETWEEN_TIME_CHECKS};
+
+// How long an idle worker waits before looking for work again, in case it
+// missed being told about some
+const IDLE_WAIT: Duration = Duration::from_millis(1);
+
+// Deeply nested processes take a lot of stack to step, so workers get as much
+// as a main thread usually has
+const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+// Runs the tasks on a pool of threads until they've all completed, or until
+// the fuel runs out. Each worker has a queue of its own, which the tasks it
+// spawns or gives a turn to go on. A worker with nothing left in its queue
+// steals from the back of another's. High priority tasks go to the front of a
+// queue, otherwise priorities are ignored.
+//
+// Turns last for REDUCTIONS_PER_TURN reductions, as they do with the
+// Reductions scheduler, but which order processes complete in depends on how
+// the threads happen to run.
+pub(crate) fn run_tasks_on_threads<I: Value, S: Value>(
+    tasks: Vector<Task<I, S>>,
+    threads: usize,
+    fuel: Fuel,
+) -> Result<Partial<I, S>, OutOfFuel<Partial<I, S>>> {
+    let threads = threads.max(1);
+    let pool = Pool {
+        queues: (0..threads).map(|_| Mutex::new(VecDeque::new())).collect(),
+        parked: Mutex::new(Parked {
+            tasks: vec![],
+            events_seen: scheduler::events(),
+            next_deadline: None,
+        }),
+        live: AtomicUsize::new(0),
+        idle: (Mutex::new(()), Condvar::new()),
+        stopped: AtomicBool::new(false),
+        out_of_fuel: Mutex::new(None),
+        steps: AtomicU64::new(0),
+        max_steps: fuel.max_steps,
+        time_limit: fuel.time_limit.map(|limit| clock::now() + limit),
+        main_result: Mutex::new(None),
+        spawned_results: Mutex::new(vector![]),
+    };
+    tasks.into_iter().for_each(|task| pool.add(0, task));
+
+    // Workers tell the time and write their output with whatever this thread
+    // is using
+    let clock = clock::current_clock();
+    let sink = output::current_sink();
+    thread::scope(|scope| {
+        for worker in 1..threads {
+            let (pool, clock, sink) = (&pool, clock.clone(), sink.clone());
+            thread::Builder::new()
+                .name(format!("nana-worker-{}", worker))
+                .stack_size(WORKER_STACK_SIZE)
+                .spawn_scoped(scope, move || {
+                    with_clock(clock, || with_sink(sink, || pool.work(worker)))
+                })
+                .expect("Couldn't start a worker thread");
+        }
+        pool.work(0);
+    });
+
+    // Workers that ran out counted a step they didn't take
+    let steps = pool.steps.into_inner();
+    let steps = pool
+        .max_steps
+        .map_or(steps, |max_steps| max_steps.min(steps));
+    let results = (
+        pool.main_result.into_inner().unwrap(),
+        pool.spawned_results.into_inner().unwrap(),
+    );
+    match pool.out_of_fuel.into_inner().unwrap() {
+        None => Ok(results),
+        Some(timed_out) => Err(OutOfFuel {
+            completed: results,
+            steps,
+            timed_out,
+        }),
+    }
+}
+
+struct Pool<I: Clone, S: Clone> {
+    queues: Vec<Mutex<VecDeque<Task<I, S>>>>,
+    parked: Mutex<Parked<I, S>>,
+    // Tasks that haven't completed, whether they're queued, taking a turn or
+    // parked
+    live: AtomicUsize,
+    idle: (Mutex<()>, Condvar),
+    stopped: AtomicBool,
+    // Whether it was the time limit, once the fuel has run out
+    out_of_fuel: Mutex<Option<bool>>,
+    steps: AtomicU64,
+    max_steps: Option<u64>,
+    time_limit: Option<Duration>,
+    main_result: Mutex<Option<I>>,
+    spawned_results: Mutex<Vector<S>>,
+}
+
+// Tasks that are waiting, which are only looked at again once there's been an
+// event or one of their deadlines has passed
+struct Parked<I: Clone, S: Clone> {
+    tasks: Vec<(Arc<dyn Wake>, Task<I, S>)>,
+    events_seen: usize,
+    next_deadline: Option<Duration>,
+}
+
+// Stops the other workers if one panics, rather than leaving them waiting for
+// tasks that will never complete
+struct StopOnPanic<'a, I: Value, S: Value>(&'a Pool<I, S>);
+
+impl<I: Value, S: Value> Drop for StopOnPanic<'_, I, S> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            self.0.stop();
+        }
+    }
+}
+
+impl<I: Value, S: Value> Pool<I, S> {
+    fn work(&self, worker: usize) {
+        let _stop_on_panic = StopOnPanic(self);
+        let mut steps_since_time_check = 0;
+        while !self.stopped.load(Ordering::Acquire) {
+            self.unpark_ready(worker);
+            match self.find_task(worker) {
+                Some(task) => self.take_turn(worker, task, &mut steps_since_time_check),
+                None => self.wait_for_work(worker),
+            }
+        }
+    }
+
+    fn take_turn(&self, worker: usize, mut task: Task<I, S>, steps_since_time_check: &mut u64) {
+        let mut used = 0;
+        loop {
+            task = match task {
+                Task::Main(Complete(result)) => {
+                    *self.main_result.lock().unwrap() = Some(result);
+                    return self.completed();
+                }
+                Task::Spawned(Complete(result)) => {
+                    self.spawned_results.lock().unwrap().push_back(result);
+                    return self.completed();
+                }
+                Task::Main(Spawn(continuation, spawned_processes)) => {
+                    self.spawn(worker, spawned_processes);
+                    Task::Main((*continuation).clone())
+                }
+                Task::Spawned(Spawn(continuation, spawned_processes)) => {
+                    self.spawn(worker, spawned_processes);
+                    Task::Spawned((*continuation).clone())
+                }
+                Task::Main(Waiting(wake, continuation)) => {
+                    return self.park(worker, wake, Task::Main((*continuation).clone()));
+                }
+                Task::Spawned(Waiting(wake, continuation)) => {
+                    return self.park(worker, wake, Task::Spawned((*continuation).clone()));
+                }
+                running => {
+                    if used >= REDUCTIONS_PER_TURN {
+                        return self.queue(worker, running);
+                    }
+                    if self.out_of_fuel(steps_since_time_check) {
+                        return;
+                    }
+                    let (next, reductions) = running.step();
+                    used += reductions;
+                    next
+                }
+            }
+        }
+    }
+
+    // Counts a step, and stops every worker if it's one too many. A turn that
+    // another worker has stopped ends too.
+    fn out_of_fuel(&self, steps_since_time_check: &mut u64) -> bool {
+        let steps = self.steps.fetch_add(1, Ordering::Relaxed);
+        *steps_since_time_check += 1;
+        if self.max_steps.is_some_and(|max_steps| steps >= max_steps) {
+            self.run_out(false);
+        } else if *steps_since_time_check >= STEPS_BETWEEN_TIME_CHECKS {
+            *steps_since_time_check = 0;
+            if self.past_time_limit() {
+                self.run_out(true);
+            }
+        }
+        self.stopped.load(Ordering::Acquire)
+    }
+
+    fn past_time_limit(&self) -> bool {
+        self.time_limit.is_some_and(|limit| clock::now() >= limit)
+    }
+
+    // The first worker to run out decides why
+    fn run_out(&self, timed_out: bool) {
+        self.out_of_fuel.lock().unwrap().get_or_insert(timed_out);
+        self.stop();
+    }
+
+    fn stop(&self) {
+        self.stopped.store(true, Ordering::Release);
+        self.idle.1.notify_all();
+    }
+
+    fn completed(&self) {
+        if self.live.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.stop();
+        }
+    }
+
+    fn add(&self, worker: usize, task: Task<I, S>) {
+        self.live.fetch_add(1, Ordering::AcqRel);
+        self.queue(worker, task);
+    }
+
+    fn queue(&self, worker: usize, task: Task<I, S>) {
+        let mut queue = self.queues[worker].lock().unwrap();
+        match task.priority() {
+            Priority::High => queue.push_front(task),
+            _ => queue.push_back(task),
+        }
+        drop(queue);
+        self.idle.1.notify_one();
+    }
+
+    fn spawn(&self, worker: usize, processes: Vector<Process<S>>) {
+        processes
+            .into_iter()
+            .for_each(|process| self.add(worker, Task::Spawned(process)));
+    }
+
+    // The front of the worker's own queue, or else the back of someone else's
+    fn find_task(&self, worker: usize) -> Option<Task<I, S>> {
+        if let Some(task) = self.queues[worker].lock().unwrap().pop_front() {
+            return Some(task);
+        }
+        let others = self.queues.len();
+        (1..others)
+            .map(|offset| (worker + offset) % others)
+            .find_map(|victim| self.queues[victim].lock().unwrap().pop_back())
+    }
+
+    // Whatever it's waiting for may have already happened
+    fn park(&self, worker: usize, wake: Arc<dyn Wake>, task: Task<I, S>) {
+        if wake.ready() {
+            return self.queue(worker, task);
+        }
+        let mut parked = self.parked.lock().unwrap();
+        if let Some(deadline) = wake.deadline() {
+            parked.next_deadline = Some(
+                parked
+                    .next_deadline
+                    .map_or(deadline, |next| next.min(deadline)),
+            );
+        }
+        parked.tasks.push((wake, task));
+    }
+
+    // Skipped if another worker is already looking at the parked tasks
+    fn unpark_ready(&self, worker: usize) {
+        let events = scheduler::events();
+        let Ok(mut parked) = self.parked.try_lock() else {
+            return;
+        };
+        let timed_out = parked
+            .next_deadline
+            .is_some_and(|deadline| clock::now() >= deadline);
+        if events != parked.events_seen || timed_out {
+            parked.events_seen = events;
+            self.unpark(worker, &mut parked, |wake| wake.ready());
+        }
+    }
+
+    fn unpark(
+        &self,
+        worker: usize,
+        parked: &mut Parked<I, S>,
+        ready: impl Fn(&Arc<dyn Wake>) -> bool,
+    ) -> bool {
+        let (woken, waiting): (Vec<_>, Vec<_>) =
+            parked.tasks.drain(..).partition(|(wake, _)| ready(wake));
+        parked.tasks = waiting;
+        parked.next_deadline = parked
+            .tasks
+            .iter()
+            .filter_map(|(wake, _)| wake.deadline())
+            .min();
+        let any_woken = !woken.is_empty();
+        woken
+            .into_iter()
+            .for_each(|(_, task)| self.queue(worker, task));
+        any_woken
+    }
+
+    // Once every task that's left is parked, nothing but a deadline can wake
+    // them. One worker sleeps until then, or abandons them all if none of
+    // them has a deadline. Otherwise there are tasks some other worker is
+    // running, so this one waits to see if they spawn any more.
+    fn wait_for_work(&self, worker: usize) {
+        if self.live.load(Ordering::Acquire) == 0 {
+            return self.stop();
+        }
+        let mut parked = self.parked.lock().unwrap();
+        let everything_parked =
+            !parked.tasks.is_empty() && parked.tasks.len() == self.live.load(Ordering::Acquire);
+        if !everything_parked || self.unpark(worker, &mut parked, |wake| wake.ready()) {
+            drop(parked);
+            let (lock, condvar) = &self.idle;
+            let _ = condvar
+                .wait_timeout(lock.lock().unwrap(), IDLE_WAIT)
+                .unwrap();
+            return;
+        }
+        if self.past_time_limit() {
+            self.run_out(true);
+            return;
+        }
+        match parked.next_deadline {
+            Some(deadline) => {
+                drop(parked);
+                clock::sleep_until(
+                    self.time_limit
+                        .map_or(deadline, |limit| limit.min(deadline)),
+                );
+            }
+            None => {
+                parked.tasks.iter().for_each(|(wake, _)| wake.abandon());
+                self.unpark(worker, &mut parked, |_| true);
+            }
+        }
+    }
+}
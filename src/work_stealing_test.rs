@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::clock::{with_clock, Clock, VirtualClock};
+use crate::errors::error_variety;
+use crate::expressions::print;
+use crate::expressions::Arity;
+use crate::expressions::RuntimeExpression::Keyword;
+use crate::interpreter::Interpreter;
+use crate::output::{with_sink, Buffer};
+use crate::parsers::diagnostics::parse_program;
+use crate::parsers::nana::input;
+use crate::process::{Fuel, Process};
+use crate::s;
+
+fn on_threads(threads: usize) -> Interpreter {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_threads(threads);
+    interpreter
+}
+
+#[test]
+fn test_processes_give_the_same_results_on_many_threads() {
+    let code = "Func count-down [n result] Match n [0 result _ count-down(decrement(n) result)]
+                Def handles [spawn(Fn [] count-down(300 1))
+                             spawn(Fn [] count-down(200 2))
+                             spawn(Fn [] count-down(100 3))
+                             spawn(Fn [] count-down(400 4))]
+                await-all(handles)";
+
+    let results = on_threads(4).eval_str(code).unwrap();
+    assert_eq!(print(results.last().unwrap().clone()), "[1 2 3 4]");
+}
+
+#[test]
+fn test_processes_send_messages_between_threads() {
+    let results = on_threads(4).eval_file("examples/mailboxes.nana").unwrap();
+    assert_eq!(print(results.last().unwrap().clone()), "[0 1 2 :timed-out]");
+
+    let results = on_threads(4)
+        .eval_file("examples/supervision.nana")
+        .unwrap();
+    let printed: Vec<_> = results.into_iter().rev().take(2).map(print).collect();
+    assert_eq!(
+        printed,
+        [
+            "[:exit([:too-many-restarts :second-try]) 2 :stopped]",
+            "[:ok 3 :stopped]",
+        ]
+    );
+}
+
+#[test]
+fn test_busy_processes_are_stolen_by_other_threads() {
+    let threads = Arc::new(Mutex::new(HashSet::new()));
+    let seen = threads.clone();
+    let mut interpreter = on_threads(4);
+    interpreter.define_builtin("note-thread", Arity::Exactly(0), move |_args| {
+        seen.lock().unwrap().insert(thread::current().id());
+        Process::Complete(Keyword(s!("ok")))
+    });
+
+    interpreter
+        .eval_str(
+            "Func busy [n] Match [note-thread() n] [[_ 0] :done _ busy(decrement(n))]
+             await-all([spawn(Fn [] busy(2000)) spawn(Fn [] busy(2000))
+                        spawn(Fn [] busy(2000)) spawn(Fn [] busy(2000))])",
+        )
+        .unwrap();
+    assert!(threads.lock().unwrap().len() > 1);
+}
+
+#[test]
+fn test_workers_use_the_callers_clock_and_sink() {
+    let clock = VirtualClock::default();
+    let logs = Buffer::default();
+    let (_, spawned) = with_clock(Arc::new(clock.clone()), || {
+        with_sink(Arc::new(logs.clone()), || {
+            on_threads(4)
+                .run(parse_program(input(
+                    "spawn(Fn [] Do [sleep(30000) log(:slow)])
+                     spawn(Fn [] Do [sleep(10000) log(:fast)])",
+                )))
+                .unwrap()
+        })
+    });
+
+    assert_eq!(spawned.len(), 2);
+    assert_eq!(logs.lines(), [":fast", ":slow"]);
+    assert_eq!(clock.now(), Duration::from_secs(30));
+}
+
+#[test]
+fn test_running_out_of_fuel_stops_every_thread() {
+    let mut interpreter = on_threads(4);
+    interpreter.set_fuel(Fuel::steps(10_000));
+
+    let (results, spawned) = interpreter
+        .run(parse_program(input(
+            "Func forever [] forever()
+             spawn(Fn [] forever())
+             spawn(Fn [] forever())
+             forever()",
+        )))
+        .unwrap();
+
+    assert_eq!(error_variety(results.last().unwrap()), Some("out-of-fuel"));
+    assert!(spawned.is_empty());
+}